@@ -1,46 +1,171 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use rusqlite::params;
 use tauri::AppHandle;
 use tauri::Manager;
 
+use crate::commands::export::exports_dir;
 use crate::db::DbState;
 use crate::errors::AppError;
-use crate::models::brand_voice::{AnalyzeVoiceRequest, BrandVoiceProfile, StyleAttributes};
+use crate::models::brand_voice::{
+    AnalyzeVoiceRequest, BlendVoicesRequest, BrandVoiceExport, BrandVoiceProfile, BrandVoiceSample,
+    BrandVoiceVersion, ExportedBrandVoice, FormatVoiceOverride, SampleExcerpt, SampleSource,
+    StyleAttributes, StyleFieldDiff, Stylometrics, UpdateVoiceRequest, VoiceComparison, VoiceScore,
+};
+use crate::models::platform::OutputFormat;
 use crate::services::brand_voice as brand_voice_service;
 use crate::services::claude_api::ClaudeApiClient;
-use crate::services::usage_tracker;
+use crate::services::{stylometry, url_cache, url_fetcher, usage_tracker, voice_scoring, workspace};
+
+const PROFILE_COLUMNS: &str =
+    "id, name, description, style_attributes_json, is_default, created_at, updated_at, stylometrics_json";
+
+fn profile_from_row(row: &rusqlite::Row) -> rusqlite::Result<BrandVoiceProfile> {
+    let style_json: String = row.get(3)?;
+    let style: StyleAttributes = serde_json::from_str(&style_json).unwrap_or_default();
+
+    Ok(BrandVoiceProfile {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        style_attributes: style,
+        is_default: row.get::<_, i32>(4)? != 0,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+        stylometrics: row
+            .get::<_, Option<String>>(7)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+    })
+}
+
+pub(crate) fn get_profile(
+    conn: &rusqlite::Connection,
+    id: &str,
+) -> Result<BrandVoiceProfile, AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM brand_voice_profiles WHERE id = ?1 AND workspace_id = ?2",
+            PROFILE_COLUMNS
+        ),
+        params![id, workspace::active_workspace_id(conn)],
+        profile_from_row,
+    )
+    .map_err(|_| AppError::NotFound(format!("Brand voice profile '{}' not found", id)))
+}
+
+pub(crate) fn get_sample_texts(conn: &rusqlite::Connection, profile_id: &str) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT sample_text FROM brand_voice_samples WHERE profile_id = ?1 ORDER BY created_at ASC",
+    )?;
+    let samples = stmt
+        .query_map(params![profile_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(samples)
+}
+
+fn version_from_row(row: &rusqlite::Row) -> rusqlite::Result<BrandVoiceVersion> {
+    let style_json: String = row.get(3)?;
+    Ok(BrandVoiceVersion {
+        id: row.get(0)?,
+        profile_id: row.get(1)?,
+        version_number: row.get(2)?,
+        style_attributes: serde_json::from_str(&style_json).unwrap_or_default(),
+        change_source: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn get_version(conn: &rusqlite::Connection, version_id: &str) -> Result<BrandVoiceVersion, AppError> {
+    conn.query_row(
+        "SELECT v.id, v.profile_id, v.version_number, v.style_attributes_json, v.change_source, v.created_at \
+         FROM brand_voice_versions v JOIN brand_voice_profiles p ON p.id = v.profile_id \
+         WHERE v.id = ?1 AND p.workspace_id = ?2",
+        params![version_id, workspace::active_workspace_id(conn)],
+        version_from_row,
+    )
+    .map_err(|_| AppError::NotFound(format!("Brand voice version '{}' not found", version_id)))
+}
+
+/// Stores an immutable snapshot of a profile's style attributes as its next version.
+pub(crate) fn record_version(
+    conn: &rusqlite::Connection,
+    profile_id: &str,
+    style: &StyleAttributes,
+    change_source: &str,
+) -> Result<BrandVoiceVersion, AppError> {
+    let version_number: u32 = conn.query_row(
+        "SELECT COALESCE(MAX(version_number), 0) + 1 FROM brand_voice_versions WHERE profile_id = ?1",
+        params![profile_id],
+        |row| row.get(0),
+    )?;
+
+    let version = BrandVoiceVersion {
+        id: uuid::Uuid::new_v4().to_string(),
+        profile_id: profile_id.to_string(),
+        version_number,
+        style_attributes: style.clone(),
+        change_source: change_source.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    conn.execute(
+        "INSERT INTO brand_voice_versions (id, profile_id, version_number, style_attributes_json, change_source, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            version.id,
+            version.profile_id,
+            version.version_number,
+            serde_json::to_string(style)?,
+            version.change_source,
+            version.created_at
+        ],
+    )?;
+
+    Ok(version)
+}
+
+pub(crate) fn latest_version_id(
+    conn: &rusqlite::Connection,
+    profile_id: &str,
+) -> Result<Option<String>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM brand_voice_versions WHERE profile_id = ?1 ORDER BY version_number DESC LIMIT 1",
+    )?;
+    let id = stmt
+        .query_map(params![profile_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .next();
+    Ok(id)
+}
+
+/// Profile names keyed by voice version id, for labelling outputs.
+pub(crate) fn voice_names_by_version(
+    conn: &rusqlite::Connection,
+) -> Result<HashMap<String, String>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT v.id, p.name FROM brand_voice_versions v \
+         JOIN brand_voice_profiles p ON p.id = v.profile_id",
+    )?;
+    let names = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(names)
+}
 
 #[tauri::command]
 pub async fn get_brand_voices(app: AppHandle) -> Result<Vec<BrandVoiceProfile>, AppError> {
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, name, description, style_attributes_json, is_default, created_at, updated_at FROM brand_voice_profiles ORDER BY is_default DESC, name ASC",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM brand_voice_profiles WHERE workspace_id = ?1 ORDER BY is_default DESC, name ASC",
+        PROFILE_COLUMNS
+    ))?;
 
     let profiles = stmt
-        .query_map([], |row| {
-            let style_json: String = row.get(3)?;
-            let style: StyleAttributes =
-                serde_json::from_str(&style_json).unwrap_or_else(|_| StyleAttributes {
-                    tone: String::new(),
-                    vocabulary_level: String::new(),
-                    sentence_style: String::new(),
-                    personality_traits: vec![],
-                    signature_phrases: vec![],
-                    avoid_phrases: vec![],
-                });
-
-            Ok(BrandVoiceProfile {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?,
-                style_attributes: style,
-                is_default: row.get::<_, i32>(4)? != 0,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })?
+        .query_map(params![workspace::active_workspace_id(&conn)], profile_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
@@ -61,38 +186,44 @@ pub async fn analyze_brand_voice(
     let db = app.state::<DbState>();
     let api_key = usage_tracker::get_api_key(&db).await?;
 
+    let excerpts = gather_excerpts(&db, &request.samples, &request.sources).await?;
+    let texts: Vec<String> = excerpts.iter().map(|e| e.text.clone()).collect();
+
     let claude = app.state::<ClaudeApiClient>();
-    let style =
-        brand_voice_service::analyze_voice_samples(&claude, &api_key, &request.samples).await?;
+    let style = brand_voice_service::analyze_voice_samples(&claude, &api_key, &texts).await?;
 
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
     let style_json =
         serde_json::to_string(&style).map_err(|e| AppError::Validation(e.to_string()))?;
 
-    // Check if this is the first profile (make it default)
+    // The profile is saved with its samples and first version, or not at all
     let conn = db.conn.lock().await;
-    let count: u32 = conn.query_row(
-        "SELECT COUNT(*) FROM brand_voice_profiles",
-        [],
+    let tx = conn.unchecked_transaction()?;
+
+    // Check if this is the first profile (make it default)
+    let workspace_id = workspace::active_workspace_id(&tx);
+    let count: u32 = tx.query_row(
+        "SELECT COUNT(*) FROM brand_voice_profiles WHERE workspace_id = ?1",
+        params![workspace_id],
         |row| row.get(0),
     )?;
     let is_default = count == 0;
 
-    conn.execute(
-        "INSERT INTO brand_voice_profiles (id, name, description, style_attributes_json, is_default, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        params![id, request.name, request.description, style_json, is_default as i32, now, now],
+    tx.execute(
+        "INSERT INTO brand_voice_profiles (id, name, description, style_attributes_json, is_default, created_at, updated_at, workspace_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![id, request.name, request.description, style_json, is_default as i32, now, now, workspace_id],
     )?;
 
     // Save samples
-    for sample in &request.samples {
-        let sample_id = uuid::Uuid::new_v4().to_string();
-        conn.execute(
-            "INSERT INTO brand_voice_samples (id, profile_id, sample_text) VALUES (?1, ?2, ?3)",
-            params![sample_id, id, sample],
-        )?;
+    for excerpt in &excerpts {
+        insert_sample(&tx, &id, excerpt)?;
     }
 
+    record_version(&tx, &id, &style, "analyze")?;
+    let stylometrics = stylometry::refresh_profile(&tx, &id)?;
+    tx.commit()?;
+
     Ok(BrandVoiceProfile {
         id,
         name: request.name,
@@ -101,6 +232,7 @@ pub async fn analyze_brand_voice(
         is_default,
         created_at: now.clone(),
         updated_at: now,
+        stylometrics,
     })
 }
 
@@ -109,7 +241,10 @@ pub async fn delete_brand_voice(app: AppHandle, id: String) -> Result<(), AppErr
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
 
-    let affected = conn.execute("DELETE FROM brand_voice_profiles WHERE id = ?1", params![id])?;
+    let affected = conn.execute(
+        "DELETE FROM brand_voice_profiles WHERE id = ?1 AND workspace_id = ?2",
+        params![id, workspace::active_workspace_id(&conn)],
+    )?;
 
     if affected == 0 {
         return Err(AppError::NotFound(format!(
@@ -131,12 +266,13 @@ pub async fn delete_brand_voice(app: AppHandle, id: String) -> Result<(), AppErr
 pub async fn set_default_voice(app: AppHandle, id: String) -> Result<(), AppError> {
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
+    let workspace_id = workspace::active_workspace_id(&conn);
 
     // Verify the profile exists
     let exists: bool = conn
         .query_row(
-            "SELECT COUNT(*) FROM brand_voice_profiles WHERE id = ?1",
-            params![id],
+            "SELECT COUNT(*) FROM brand_voice_profiles WHERE id = ?1 AND workspace_id = ?2",
+            params![id, workspace_id],
             |row| row.get::<_, u32>(0),
         )
         .map(|count| count > 0)?;
@@ -148,10 +284,10 @@ pub async fn set_default_voice(app: AppHandle, id: String) -> Result<(), AppErro
         )));
     }
 
-    // Clear all defaults
+    // Clear the workspace's defaults
     conn.execute(
-        "UPDATE brand_voice_profiles SET is_default = 0",
-        [],
+        "UPDATE brand_voice_profiles SET is_default = 0 WHERE workspace_id = ?1",
+        params![workspace_id],
     )?;
 
     // Set the new default
@@ -162,3 +298,779 @@ pub async fn set_default_voice(app: AppHandle, id: String) -> Result<(), AppErro
 
     Ok(())
 }
+
+#[tauri::command]
+pub async fn update_brand_voice(
+    app: AppHandle,
+    request: UpdateVoiceRequest,
+) -> Result<BrandVoiceProfile, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let mut profile = get_profile(&conn, &request.id)?;
+
+    if let Some(name) = request.name {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Brand voice name cannot be empty".to_string(),
+            ));
+        }
+        profile.name = name;
+    }
+
+    if let Some(description) = request.description {
+        // An empty description clears it
+        profile.description = Some(description).filter(|d| !d.trim().is_empty());
+    }
+
+    let previous_style_json = serde_json::to_string(&profile.style_attributes)?;
+    if let Some(mut style) = request.style_attributes {
+        // A stale or missing copy of the overrides must not replace the stored ones
+        style.format_overrides = std::mem::take(&mut profile.style_attributes.format_overrides);
+        profile.style_attributes = style;
+    }
+    if let Some(format_overrides) = request.format_overrides {
+        profile.style_attributes.format_overrides = format_overrides;
+    }
+
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    let style_json = serde_json::to_string(&profile.style_attributes)?;
+
+    if style_json != previous_style_json {
+        record_version(&conn, &profile.id, &profile.style_attributes, "manual_edit")?;
+    }
+
+    conn.execute(
+        "UPDATE brand_voice_profiles SET name = ?1, description = ?2, style_attributes_json = ?3, updated_at = ?4 WHERE id = ?5",
+        params![profile.name, profile.description, style_json, profile.updated_at, profile.id],
+    )?;
+
+    Ok(profile)
+}
+
+#[tauri::command]
+pub async fn get_voice_samples(
+    app: AppHandle,
+    profile_id: String,
+) -> Result<Vec<BrandVoiceSample>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    get_profile(&conn, &profile_id)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, profile_id, sample_text, created_at, source_kind, source_ref FROM brand_voice_samples WHERE profile_id = ?1 ORDER BY created_at ASC",
+    )?;
+
+    let samples = stmt
+        .query_map(params![profile_id], |row| {
+            Ok(BrandVoiceSample {
+                id: row.get(0)?,
+                profile_id: row.get(1)?,
+                sample_text: row.get(2)?,
+                created_at: row.get(3)?,
+                source_kind: row.get(4)?,
+                source_ref: row.get(5)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(samples)
+}
+
+#[tauri::command]
+pub async fn add_voice_samples(
+    app: AppHandle,
+    profile_id: String,
+    samples: Vec<String>,
+    sources: Option<Vec<SampleSource>>,
+) -> Result<Vec<BrandVoiceSample>, AppError> {
+    let sources = sources.unwrap_or_default();
+    if samples.is_empty() && sources.is_empty() {
+        return Err(AppError::Validation(
+            "At least one writing sample is required".to_string(),
+        ));
+    }
+
+    let db = app.state::<DbState>();
+    {
+        let conn = db.conn.lock().await;
+        get_profile(&conn, &profile_id)?;
+    }
+
+    let excerpts = gather_excerpts(&db, &samples, &sources).await?;
+
+    let conn = db.conn.lock().await;
+    let mut added = Vec::new();
+    for excerpt in &excerpts {
+        added.push(insert_sample(&conn, &profile_id, excerpt)?);
+    }
+
+    stylometry::refresh_profile(&conn, &profile_id)?;
+
+    Ok(added)
+}
+
+/// Resolves pasted text and sources into the excerpts that would be saved as
+/// samples, without saving them.
+#[tauri::command]
+pub async fn preview_voice_samples(
+    app: AppHandle,
+    samples: Vec<String>,
+    sources: Vec<SampleSource>,
+) -> Result<Vec<SampleExcerpt>, AppError> {
+    let db = app.state::<DbState>();
+    gather_excerpts(&db, &samples, &sources).await
+}
+
+pub(crate) fn insert_sample(
+    conn: &rusqlite::Connection,
+    profile_id: &str,
+    excerpt: &SampleExcerpt,
+) -> Result<BrandVoiceSample, AppError> {
+    let sample = BrandVoiceSample {
+        id: uuid::Uuid::new_v4().to_string(),
+        profile_id: profile_id.to_string(),
+        sample_text: excerpt.text.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        source_kind: excerpt.source_kind.clone(),
+        source_ref: excerpt.source_ref.clone(),
+    };
+
+    conn.execute(
+        "INSERT INTO brand_voice_samples (id, profile_id, sample_text, created_at, source_kind, source_ref) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            sample.id,
+            sample.profile_id,
+            sample.sample_text,
+            sample.created_at,
+            sample.source_kind,
+            sample.source_ref
+        ],
+    )?;
+
+    Ok(sample)
+}
+
+/// Splits pasted samples and the text behind each source into sample-sized
+/// excerpts. Outputs must have been approved to be used.
+async fn gather_excerpts(
+    db: &DbState,
+    pasted: &[String],
+    sources: &[SampleSource],
+) -> Result<Vec<SampleExcerpt>, AppError> {
+    brand_voice_service::validate_samples(pasted)?;
+
+    let mut documents: Vec<(String, &str, Option<String>)> = pasted
+        .iter()
+        .map(|text| (text.clone(), "pasted", None))
+        .collect();
+
+    let policy = url_fetcher::load_fetch_policy(db).await?;
+    for source in sources {
+        match source {
+            SampleSource::Url { url } => {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(AppError::Validation(
+                        "URL must start with http:// or https://".to_string(),
+                    ));
+                }
+                let body = url_cache::fetch_page(db, url, &policy, false).await?;
+                let page = url_fetcher::parse_page(&body.text())?;
+                documents.push((page.text, "url", Some(url.clone())));
+            }
+            SampleSource::File { path } => {
+                let text = brand_voice_service::read_sample_file(path)?;
+                documents.push((text, "file", Some(path.clone())));
+            }
+            SampleSource::Output { output_id } => {
+                let conn = db.conn.lock().await;
+                let (text, approved_at): (String, Option<String>) = conn
+                    .query_row(
+                        "SELECT output_text, approved_at FROM repurposed_outputs WHERE id = ?1 \
+                         AND content_input_id IN (SELECT id FROM content_inputs WHERE workspace_id = ?2)",
+                        params![output_id, workspace::active_workspace_id(&conn)],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .map_err(|_| AppError::NotFound(format!("Output '{}' not found", output_id)))?;
+                if approved_at.is_none() {
+                    return Err(AppError::Validation(format!(
+                        "Output '{}' must be approved before it can be used as a sample",
+                        output_id
+                    )));
+                }
+                documents.push((text, "output", Some(output_id.clone())));
+            }
+        }
+    }
+
+    let mut excerpts = Vec::new();
+    for (text, source_kind, source_ref) in documents {
+        let pieces = brand_voice_service::split_into_excerpts(&text);
+        if pieces.is_empty() {
+            return Err(AppError::Validation(format!(
+                "No usable sample text found in {}",
+                source_ref.as_deref().unwrap_or("pasted sample")
+            )));
+        }
+        excerpts.extend(pieces.into_iter().map(|text| SampleExcerpt {
+            text,
+            source_kind: source_kind.to_string(),
+            source_ref: source_ref.clone(),
+        }));
+    }
+
+    Ok(excerpts)
+}
+
+#[tauri::command]
+pub async fn remove_voice_sample(app: AppHandle, sample_id: String) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let profile_id: String = conn
+        .query_row(
+            "SELECT profile_id FROM brand_voice_samples WHERE id = ?1",
+            params![sample_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound(format!("Writing sample '{}' not found", sample_id)))?;
+    // Samples of profiles in other workspaces are not visible here
+    get_profile(&conn, &profile_id)
+        .map_err(|_| AppError::NotFound(format!("Writing sample '{}' not found", sample_id)))?;
+
+    conn.execute(
+        "DELETE FROM brand_voice_samples WHERE id = ?1",
+        params![sample_id],
+    )?;
+    stylometry::refresh_profile(&conn, &profile_id)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn reanalyze_brand_voice(app: AppHandle, id: String) -> Result<BrandVoiceProfile, AppError> {
+    let db = app.state::<DbState>();
+    let api_key = usage_tracker::get_api_key(&db).await?;
+
+    let samples = {
+        let conn = db.conn.lock().await;
+        get_profile(&conn, &id)?;
+        get_sample_texts(&conn, &id)?
+    };
+
+    let claude = app.state::<ClaudeApiClient>();
+    let mut style = brand_voice_service::analyze_voice_samples(&claude, &api_key, &samples).await?;
+
+    let conn = db.conn.lock().await;
+    let mut profile = get_profile(&conn, &id)?;
+    // Per-format overrides are set by hand, so re-analysis keeps them
+    style.format_overrides = std::mem::take(&mut profile.style_attributes.format_overrides);
+    profile.style_attributes = style;
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    let style_json = serde_json::to_string(&profile.style_attributes)?;
+
+    conn.execute(
+        "UPDATE brand_voice_profiles SET style_attributes_json = ?1, updated_at = ?2 WHERE id = ?3",
+        params![style_json, profile.updated_at, id],
+    )?;
+    record_version(&conn, &id, &profile.style_attributes, "reanalyze")?;
+
+    Ok(profile)
+}
+
+#[tauri::command]
+pub async fn list_voice_versions(
+    app: AppHandle,
+    profile_id: String,
+) -> Result<Vec<BrandVoiceVersion>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    get_profile(&conn, &profile_id)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, profile_id, version_number, style_attributes_json, change_source, created_at FROM brand_voice_versions WHERE profile_id = ?1 ORDER BY version_number DESC",
+    )?;
+
+    let versions = stmt
+        .query_map(params![profile_id], version_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(versions)
+}
+
+#[tauri::command]
+pub async fn diff_voice_versions(
+    app: AppHandle,
+    from_version_id: String,
+    to_version_id: String,
+) -> Result<Vec<StyleFieldDiff>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let from = get_version(&conn, &from_version_id)?;
+    let to = get_version(&conn, &to_version_id)?;
+
+    brand_voice_service::diff_style_attributes(&from.style_attributes, &to.style_attributes)
+}
+
+/// Sets or clears (`voice_override: None`) the override for one output format.
+#[tauri::command]
+pub async fn set_voice_format_override(
+    app: AppHandle,
+    profile_id: String,
+    format: OutputFormat,
+    voice_override: Option<FormatVoiceOverride>,
+) -> Result<BrandVoiceProfile, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let mut profile = get_profile(&conn, &profile_id)?;
+    let change_source = format!("format_override:{}", format);
+    let changed = match voice_override {
+        Some(voice_override) => {
+            profile
+                .style_attributes
+                .format_overrides
+                .insert(format, voice_override);
+            true
+        }
+        None => profile
+            .style_attributes
+            .format_overrides
+            .remove(&format)
+            .is_some(),
+    };
+
+    if !changed {
+        return Ok(profile);
+    }
+
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "UPDATE brand_voice_profiles SET style_attributes_json = ?1, updated_at = ?2 WHERE id = ?3",
+        params![
+            serde_json::to_string(&profile.style_attributes)?,
+            profile.updated_at,
+            profile.id
+        ],
+    )?;
+    record_version(&conn, &profile.id, &profile.style_attributes, &change_source)?;
+
+    Ok(profile)
+}
+
+/// Makes an earlier version current again by recording it as a new version,
+/// so the history stays append-only.
+#[tauri::command]
+pub async fn restore_voice_version(
+    app: AppHandle,
+    version_id: String,
+) -> Result<BrandVoiceProfile, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let version = get_version(&conn, &version_id)?;
+    let mut profile = get_profile(&conn, &version.profile_id)?;
+
+    profile.style_attributes = version.style_attributes;
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    let style_json = serde_json::to_string(&profile.style_attributes)?;
+
+    conn.execute(
+        "UPDATE brand_voice_profiles SET style_attributes_json = ?1, updated_at = ?2 WHERE id = ?3",
+        params![style_json, profile.updated_at, profile.id],
+    )?;
+    record_version(
+        &conn,
+        &profile.id,
+        &profile.style_attributes,
+        &format!("restore:v{}", version.version_number),
+    )?;
+
+    Ok(profile)
+}
+
+fn build_voice_export(
+    conn: &rusqlite::Connection,
+    profile_id: Option<&str>,
+    include_samples: bool,
+) -> Result<BrandVoiceExport, AppError> {
+    let profiles: Vec<BrandVoiceProfile> = match profile_id {
+        Some(id) => vec![get_profile(conn, id)?],
+        None => {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {} FROM brand_voice_profiles WHERE workspace_id = ?1 ORDER BY is_default DESC, name ASC",
+                PROFILE_COLUMNS
+            ))?;
+            let profiles = stmt
+                .query_map(params![workspace::active_workspace_id(conn)], profile_from_row)?
+                .filter_map(|r| r.ok())
+                .collect();
+            profiles
+        }
+    };
+
+    if profiles.is_empty() {
+        return Err(AppError::Validation(
+            "There are no brand voices to export".to_string(),
+        ));
+    }
+
+    let mut exported = Vec::new();
+    for profile in profiles {
+        let samples = if include_samples {
+            get_sample_texts(conn, &profile.id)?
+        } else {
+            Vec::new()
+        };
+        exported.push(ExportedBrandVoice {
+            name: profile.name,
+            description: profile.description,
+            style_attributes: profile.style_attributes,
+            is_default: profile.is_default,
+            created_at: profile.created_at,
+            updated_at: profile.updated_at,
+            samples,
+        });
+    }
+
+    Ok(BrandVoiceExport {
+        format: brand_voice_service::VOICE_EXPORT_FORMAT.to_string(),
+        schema_version: brand_voice_service::VOICE_EXPORT_SCHEMA_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        profiles: exported,
+    })
+}
+
+fn write_voice_export(
+    app: &AppHandle,
+    export: &BrandVoiceExport,
+    target_path: Option<String>,
+    default_stem: &str,
+) -> Result<String, AppError> {
+    let output_path = match target_path {
+        Some(path) => PathBuf::from(path),
+        None => exports_dir(app)?.join(format!(
+            "{}_{}.json",
+            default_stem,
+            chrono::Utc::now().format("%Y%m%d_%H%M%S")
+        )),
+    };
+
+    let json = serde_json::to_string_pretty(export)?;
+    std::fs::write(&output_path, json)
+        .map_err(|e| AppError::FileIo(format!("Failed to write brand voice file: {}", e)))?;
+
+    output_path
+        .to_str()
+        .map(str::to_string)
+        .ok_or_else(|| AppError::FileIo("Invalid path encoding".to_string()))
+}
+
+#[tauri::command]
+pub async fn export_brand_voice(
+    app: AppHandle,
+    id: String,
+    include_samples: Option<bool>,
+    target_path: Option<String>,
+) -> Result<String, AppError> {
+    let export = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        build_voice_export(&conn, Some(&id), include_samples.unwrap_or(true))?
+    };
+
+    let stem: String = export.profiles[0]
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    write_voice_export(&app, &export, target_path, &format!("brand_voice_{}", stem))
+}
+
+#[tauri::command]
+pub async fn export_all_brand_voices(
+    app: AppHandle,
+    include_samples: Option<bool>,
+    target_path: Option<String>,
+) -> Result<String, AppError> {
+    let export = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        build_voice_export(&conn, None, include_samples.unwrap_or(true))?
+    };
+
+    write_voice_export(&app, &export, target_path, "brand_voices")
+}
+
+/// Imports every profile in an exported file as a new profile. Clashing names
+/// get an "(imported)" suffix, and an imported profile only becomes the
+/// default when no default exists yet.
+#[tauri::command]
+pub async fn import_brand_voice(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<BrandVoiceProfile>, AppError> {
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| AppError::FileIo(format!("Failed to read '{}': {}", path, e)))?;
+    let export = brand_voice_service::parse_voice_export(&json)?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    // All profiles in the file are imported, or none are
+    let tx = conn.unchecked_transaction()?;
+    let workspace_id = workspace::active_workspace_id(&tx);
+
+    let mut existing_names: Vec<String> = {
+        let mut stmt = tx.prepare("SELECT name FROM brand_voice_profiles WHERE workspace_id = ?1")?;
+        let names = stmt
+            .query_map(params![workspace_id], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        names
+    };
+    let mut has_default: bool = tx.query_row(
+        "SELECT COUNT(*) FROM brand_voice_profiles WHERE is_default = 1 AND workspace_id = ?1",
+        params![workspace_id],
+        |row| row.get::<_, u32>(0),
+    )? > 0;
+
+    let mut imported = Vec::new();
+    for voice in export.profiles {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let name = brand_voice_service::unique_voice_name(&voice.name, &existing_names);
+        let is_default = voice.is_default && !has_default;
+        let style_json = serde_json::to_string(&voice.style_attributes)?;
+
+        tx.execute(
+            "INSERT INTO brand_voice_profiles (id, name, description, style_attributes_json, is_default, created_at, updated_at, workspace_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![id, name, voice.description, style_json, is_default as i32, now, now, workspace_id],
+        )?;
+
+        for sample in &voice.samples {
+            let excerpt = SampleExcerpt {
+                text: sample.clone(),
+                source_kind: "import".to_string(),
+                source_ref: Some(path.clone()),
+            };
+            insert_sample(&tx, &id, &excerpt)?;
+        }
+
+        record_version(&tx, &id, &voice.style_attributes, "import")?;
+        let stylometrics = stylometry::refresh_profile(&tx, &id)?;
+
+        existing_names.push(name.clone());
+        has_default |= is_default;
+        imported.push(BrandVoiceProfile {
+            id,
+            name,
+            description: voice.description,
+            style_attributes: voice.style_attributes,
+            is_default,
+            created_at: now.clone(),
+            updated_at: now,
+            stylometrics,
+        });
+    }
+
+    tx.commit()?;
+    Ok(imported)
+}
+
+/// Scores a stored output against the voice it was generated with, or against
+/// `voice_id` if given, and saves the result on the output.
+#[tauri::command]
+pub async fn score_output_voice(
+    app: AppHandle,
+    output_id: String,
+    voice_id: Option<String>,
+    use_llm: Option<bool>,
+) -> Result<VoiceScore, AppError> {
+    let db = app.state::<DbState>();
+
+    let (text, style, samples, use_llm) = {
+        let conn = db.conn.lock().await;
+        let (text, format, voice_version_id): (String, String, Option<String>) = conn
+            .query_row(
+                "SELECT output_text, format, voice_version_id FROM repurposed_outputs WHERE id = ?1 \
+                 AND content_input_id IN (SELECT id FROM content_inputs WHERE workspace_id = ?2)",
+                params![output_id, workspace::active_workspace_id(&conn)],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|_| AppError::NotFound(format!("Output '{}' not found", output_id)))?;
+
+        let (profile_id, style) = match (voice_id, voice_version_id) {
+            (Some(voice_id), _) => {
+                let profile = get_profile(&conn, &voice_id)?;
+                (profile.id, profile.style_attributes)
+            }
+            (None, Some(version_id)) => {
+                let version = get_version(&conn, &version_id)?;
+                (version.profile_id, version.style_attributes)
+            }
+            (None, None) => {
+                return Err(AppError::Validation(
+                    "This output was not generated with a brand voice; choose a voice to score against".to_string(),
+                ))
+            }
+        };
+
+        // Score against the voice as adjusted for this output's format
+        let style = match serde_json::from_value::<OutputFormat>(serde_json::Value::String(format)) {
+            Ok(format) => brand_voice_service::style_for_format(&style, &format).0,
+            Err(_) => style,
+        };
+
+        let samples = get_sample_texts(&conn, &profile_id)?;
+        let use_llm = use_llm.unwrap_or_else(|| voice_scoring::llm_scoring_enabled(&conn));
+        (text, style, samples, use_llm)
+    };
+
+    let mut score = voice_scoring::score_output(&text, &style, &samples);
+    if use_llm {
+        let api_key = usage_tracker::get_api_key(&db).await?;
+        let claude = app.state::<ClaudeApiClient>();
+        let judgement = claude.judge_voice(&api_key, &text, &style).await?;
+        score = voice_scoring::with_judgement(score, judgement);
+    }
+
+    let conn = db.conn.lock().await;
+    conn.execute(
+        "UPDATE repurposed_outputs SET voice_score_json = ?1 WHERE id = ?2",
+        params![serde_json::to_string(&score)?, output_id],
+    )?;
+
+    Ok(score)
+}
+
+/// Measures writing samples locally, so a voice can be previewed without an
+/// API key.
+#[tauri::command]
+pub async fn analyze_voice_stylometrics(samples: Vec<String>) -> Result<Stylometrics, AppError> {
+    stylometry::analyze(&samples).ok_or_else(|| {
+        AppError::Validation("At least one writing sample is required".to_string())
+    })
+}
+
+#[tauri::command]
+pub async fn compare_brand_voices(
+    app: AppHandle,
+    left_id: String,
+    right_id: String,
+) -> Result<VoiceComparison, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let left = get_profile(&conn, &left_id)?;
+    let right = get_profile(&conn, &right_id)?;
+
+    let style_differences =
+        brand_voice_service::diff_style_attributes(&left.style_attributes, &right.style_attributes)?;
+    let stylometric_differences = match (&left.stylometrics, &right.stylometrics) {
+        (Some(l), Some(r)) => brand_voice_service::diff_stylometrics(l, r),
+        _ => Vec::new(),
+    };
+
+    Ok(VoiceComparison {
+        left,
+        right,
+        style_differences,
+        stylometric_differences,
+    })
+}
+
+/// Creates a new profile from weighted source voices. Samples are drawn from
+/// each source in proportion to its weight and keep a reference to it.
+#[tauri::command]
+pub async fn blend_brand_voices(
+    app: AppHandle,
+    request: BlendVoicesRequest,
+) -> Result<BrandVoiceProfile, AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::Validation(
+            "Brand voice name cannot be empty".to_string(),
+        ));
+    }
+    if request.sources.len() < 2 {
+        return Err(AppError::Validation(
+            "Choose at least two brand voices to blend".to_string(),
+        ));
+    }
+    if request.sources.iter().any(|s| !s.weight.is_finite() || s.weight <= 0.0) {
+        return Err(AppError::Validation(
+            "Blend weights must be positive numbers".to_string(),
+        ));
+    }
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let mut profiles: Vec<(BrandVoiceProfile, f64)> = Vec::new();
+    for source in &request.sources {
+        if profiles.iter().any(|(p, _)| p.id == source.profile_id) {
+            return Err(AppError::Validation(format!(
+                "Brand voice '{}' is listed more than once",
+                source.profile_id
+            )));
+        }
+        profiles.push((get_profile(&conn, &source.profile_id)?, source.weight));
+    }
+    let total_weight: f64 = profiles.iter().map(|(_, w)| w).sum();
+
+    let style = brand_voice_service::blend_style_attributes(
+        &profiles
+            .iter()
+            .map(|(p, w)| (&p.style_attributes, *w))
+            .collect::<Vec<_>>(),
+    );
+
+    // The blend is saved with its samples and first version, or not at all
+    let tx = conn.unchecked_transaction()?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    tx.execute(
+        "INSERT INTO brand_voice_profiles (id, name, description, style_attributes_json, is_default, created_at, updated_at, workspace_id) VALUES (?1, ?2, ?3, ?4, 0, ?5, ?6, ?7)",
+        params![
+            id,
+            request.name,
+            request.description,
+            serde_json::to_string(&style)?,
+            now,
+            now,
+            workspace::active_workspace_id(&tx)
+        ],
+    )?;
+
+    for (profile, weight) in &profiles {
+        let samples = get_sample_texts(&tx, &profile.id)?;
+        let budget = (brand_voice_service::SAMPLE_TOKEN_BUDGET as f64 * weight / total_weight) as usize;
+        for i in brand_voice_service::select_representative(&samples, budget) {
+            let excerpt = SampleExcerpt {
+                text: samples[i].clone(),
+                source_kind: "blend".to_string(),
+                source_ref: Some(profile.id.clone()),
+            };
+            insert_sample(&tx, &id, &excerpt)?;
+        }
+    }
+
+    record_version(&tx, &id, &style, "blend")?;
+    let stylometrics = stylometry::refresh_profile(&tx, &id)?;
+    tx.commit()?;
+
+    Ok(BrandVoiceProfile {
+        id,
+        name: request.name,
+        description: request.description,
+        style_attributes: style,
+        is_default: false,
+        created_at: now.clone(),
+        updated_at: now,
+        stylometrics,
+    })
+}
@@ -1,11 +1,22 @@
+use std::collections::{HashMap, HashSet};
+
 use rusqlite::params;
 use tauri::AppHandle;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 use crate::db::DbState;
 use crate::errors::AppError;
-use crate::models::content::{ContentInput, FetchedContent};
-use crate::services::url_fetcher;
+use crate::models::content::{
+    ContentInput, CrawlPageIssue, CrawlProgress, CrawlReport, CrawlRequest, DuplicateCheck,
+    FetchedContent,
+};
+use crate::services::crawler::{self, RobotsRules};
+use crate::services::dedup;
+use crate::services::{url_cache, url_fetcher, workspace};
+
+const DEFAULT_CRAWL_DELAY_MS: u64 = 1000;
+const DEFAULT_CRAWL_MAX_PAGES: u32 = 50;
+const CRAWL_MAX_PAGES_LIMIT: u32 = 500;
 
 #[tauri::command]
 pub async fn save_content(
@@ -13,6 +24,7 @@ pub async fn save_content(
     text: String,
     source_url: Option<String>,
     title: Option<String>,
+    reuse_existing: Option<bool>,
 ) -> Result<ContentInput, AppError> {
     if text.trim().is_empty() {
         return Err(AppError::Validation("Content text cannot be empty".to_string()));
@@ -21,13 +33,75 @@ pub async fn save_content(
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
 
+    if reuse_existing.unwrap_or(false) {
+        if let Some(existing) = dedup::find_duplicates(&conn, &text, source_url.as_deref())?.exact {
+            return get_content_input(&conn, &existing.content_input_id);
+        }
+    }
+
+    insert_content_input(&conn, &workspace::active_workspace_id(&conn), source_url, text, title)
+}
+
+#[tauri::command]
+pub async fn check_duplicate_content(
+    app: AppHandle,
+    text: String,
+    source_url: Option<String>,
+) -> Result<DuplicateCheck, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    dedup::find_duplicates(&conn, &text, source_url.as_deref())
+}
+
+pub(crate) fn get_content_input(
+    conn: &rusqlite::Connection,
+    id: &str,
+) -> Result<ContentInput, AppError> {
+    get_workspace_content_input(conn, &workspace::active_workspace_id(conn), id)
+}
+
+/// A content input, if it belongs to `workspace_id`.
+pub(crate) fn get_workspace_content_input(
+    conn: &rusqlite::Connection,
+    workspace_id: &str,
+    id: &str,
+) -> Result<ContentInput, AppError> {
+    conn.query_row(
+        "SELECT id, source_url, raw_text, title, word_count, created_at FROM content_inputs WHERE id = ?1 AND workspace_id = ?2",
+        params![id, workspace_id],
+        |row| {
+            Ok(ContentInput {
+                id: row.get(0)?,
+                source_url: row.get(1)?,
+                raw_text: row.get(2)?,
+                title: row.get(3)?,
+                word_count: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        },
+    )
+    .map_err(|_| AppError::NotFound(format!("Content input '{}' not found", id)))
+}
+
+pub(crate) fn insert_content_input(
+    conn: &rusqlite::Connection,
+    workspace_id: &str,
+    source_url: Option<String>,
+    text: String,
+    title: Option<String>,
+) -> Result<ContentInput, AppError> {
     let id = uuid::Uuid::new_v4().to_string();
     let word_count = text.split_whitespace().count() as u32;
     let created_at = chrono::Utc::now().to_rfc3339();
 
+    let content_hash = dedup::content_hash(&text);
+    let canonical_url = source_url.as_deref().and_then(dedup::canonicalize_url);
+    let signature = dedup::minhash_signature(&text);
+
     conn.execute(
-        "INSERT INTO content_inputs (id, source_url, raw_text, title, word_count, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![id, source_url, text, title, word_count, created_at],
+        "INSERT INTO content_inputs (id, source_url, raw_text, title, word_count, created_at, content_hash, canonical_url, minhash_signature, workspace_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![id, source_url, text, title, word_count, created_at, content_hash, canonical_url, signature, workspace_id],
     )?;
 
     Ok(ContentInput {
@@ -41,7 +115,11 @@ pub async fn save_content(
 }
 
 #[tauri::command]
-pub async fn fetch_url(url: String) -> Result<FetchedContent, AppError> {
+pub async fn fetch_url(
+    app: AppHandle,
+    url: String,
+    force_refresh: Option<bool>,
+) -> Result<FetchedContent, AppError> {
     if url.trim().is_empty() {
         return Err(AppError::Validation("URL cannot be empty".to_string()));
     }
@@ -50,5 +128,186 @@ pub async fn fetch_url(url: String) -> Result<FetchedContent, AppError> {
         return Err(AppError::Validation("URL must start with http:// or https://".to_string()));
     }
 
-    url_fetcher::fetch_url(&url).await
+    let db = app.state::<DbState>();
+    let policy = url_fetcher::load_fetch_policy(&db).await?;
+
+    let body = url_cache::fetch_page(&db, &url, &policy, force_refresh.unwrap_or(false)).await?;
+    url_fetcher::parse_page(&body.text())
+}
+
+/// Re-runs text extraction on the cached HTML for a URL without a network request.
+#[tauri::command]
+pub async fn reextract_cached_url(app: AppHandle, url: String) -> Result<FetchedContent, AppError> {
+    let db = app.state::<DbState>();
+    let cached = url_cache::get_cached(&db, &url)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No cached copy of '{}'", url)))?;
+
+    url_fetcher::parse_page(&cached.body.text())
+}
+
+#[tauri::command]
+pub async fn crawl_site(app: AppHandle, request: CrawlRequest) -> Result<CrawlReport, AppError> {
+    let db = app.state::<DbState>();
+    let policy = url_fetcher::load_fetch_policy(&db).await?;
+
+    let (mut urls, sitemap_failures) = match (&request.sitemap_url, &request.index_url) {
+        (Some(sitemap_url), None) => {
+            validate_http_url(sitemap_url)?;
+            crawler::urls_from_sitemap(sitemap_url, &policy).await?
+        }
+        (None, Some(index_url)) => {
+            validate_http_url(index_url)?;
+            let pattern = request
+                .link_pattern
+                .as_deref()
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| {
+                    AppError::Validation(
+                        "A link pattern is required when crawling from an index page".to_string(),
+                    )
+                })?;
+            (crawler::urls_from_index(index_url, pattern, &policy).await?, Vec::new())
+        }
+        _ => {
+            return Err(AppError::Validation(
+                "Provide either a sitemap URL or an index page URL".to_string(),
+            ))
+        }
+    };
+
+    let max_pages = request
+        .max_pages
+        .unwrap_or(DEFAULT_CRAWL_MAX_PAGES)
+        .clamp(1, CRAWL_MAX_PAGES_LIMIT);
+    urls.truncate(max_pages as usize);
+
+    let base_delay = request.delay_ms.unwrap_or(DEFAULT_CRAWL_DELAY_MS);
+    let total = urls.len() as u32;
+
+    let mut robots_by_origin: HashMap<String, RobotsRules> = HashMap::new();
+    let mut seen_urls: HashSet<String> = HashSet::new();
+    let mut report = CrawlReport {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        failed: sitemap_failures,
+    };
+    let mut fetched_any = false;
+
+    for (i, url) in urls.iter().enumerate() {
+        let url_str = url.to_string();
+        let origin = url.origin().ascii_serialization();
+        if !robots_by_origin.contains_key(&origin) {
+            robots_by_origin.insert(origin.clone(), crawler::fetch_robots(url, &policy).await);
+        }
+        let robots = &robots_by_origin[&origin];
+
+        let outcome = if !robots.is_allowed(url.path()) {
+            PageOutcome::Skipped("Disallowed by robots.txt".to_string())
+        } else if !seen_urls.insert(url_str.clone()) || source_url_exists(&db, &url_str).await? {
+            PageOutcome::Skipped("Already imported".to_string())
+        } else {
+            if fetched_any {
+                let delay = base_delay.max(robots.crawl_delay_ms.unwrap_or(0));
+                tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            }
+            fetched_any = true;
+
+            match url_fetcher::fetch_url(&url_str, &policy).await {
+                Ok(fetched) => import_crawled_page(&db, &url_str, fetched, &mut seen_urls).await?,
+                Err(e) => PageOutcome::Failed(e.to_string()),
+            }
+        };
+
+        let status = match outcome {
+            PageOutcome::Imported(input) => {
+                report.imported.push(input);
+                "imported"
+            }
+            PageOutcome::Skipped(reason) => {
+                report.skipped.push(CrawlPageIssue {
+                    url: url_str.clone(),
+                    reason,
+                });
+                "skipped"
+            }
+            PageOutcome::Failed(reason) => {
+                report.failed.push(CrawlPageIssue {
+                    url: url_str.clone(),
+                    reason,
+                });
+                "failed"
+            }
+        };
+
+        let _ = app.emit(
+            "crawl-progress",
+            CrawlProgress {
+                url: url_str,
+                current: i as u32 + 1,
+                total,
+                status: status.to_string(),
+            },
+        );
+    }
+
+    Ok(report)
+}
+
+enum PageOutcome {
+    Imported(ContentInput),
+    Skipped(String),
+    Failed(String),
+}
+
+/// Stores a crawled page unless its canonical URL or text is already known.
+async fn import_crawled_page(
+    db: &DbState,
+    url: &str,
+    fetched: FetchedContent,
+    seen_urls: &mut HashSet<String>,
+) -> Result<PageOutcome, AppError> {
+    let source_url = fetched.canonical_url.unwrap_or_else(|| url.to_string());
+    if source_url != url
+        && (!seen_urls.insert(source_url.clone()) || source_url_exists(db, &source_url).await?)
+    {
+        return Ok(PageOutcome::Skipped("Canonical URL already imported".to_string()));
+    }
+
+    let conn = db.conn.lock().await;
+    let duplicate_text: bool = conn.query_row(
+        "SELECT COUNT(*) FROM content_inputs WHERE content_hash = ?1 AND workspace_id = ?2",
+        params![dedup::content_hash(&fetched.text), workspace::active_workspace_id(&conn)],
+        |row| row.get::<_, u32>(0),
+    )? > 0;
+    if duplicate_text {
+        return Ok(PageOutcome::Skipped("Identical content already imported".to_string()));
+    }
+
+    insert_content_input(
+        &conn,
+        &workspace::active_workspace_id(&conn),
+        Some(source_url),
+        fetched.text,
+        fetched.title,
+    )
+        .map(PageOutcome::Imported)
+}
+
+async fn source_url_exists(db: &DbState, url: &str) -> Result<bool, AppError> {
+    let conn = db.conn.lock().await;
+    let count: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM content_inputs WHERE (source_url = ?1 OR canonical_url = ?2) AND workspace_id = ?3",
+        params![url, dedup::canonicalize_url(url), workspace::active_workspace_id(&conn)],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+fn validate_http_url(url: &str) -> Result<(), AppError> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(AppError::Validation("URL must start with http:// or https://".to_string()));
+    }
+    Ok(())
 }
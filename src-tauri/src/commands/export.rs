@@ -1,13 +1,44 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use rusqlite::params;
 use tauri::AppHandle;
 use tauri::Manager;
 
-use crate::commands::history::get_history_detail;
+use crate::commands::brand_voice::voice_names_by_version;
+use crate::commands::content::get_content_input;
+use crate::commands::history::{
+    filtered_input_ids, load_history_detail, load_key_points, load_output,
+};
+use crate::db::DbState;
 use crate::errors::AppError;
-use crate::services::pdf_export;
+use crate::models::content::{ContentInput, HistoryDetail, HistoryFilter, JsonExportItem, KeyPoints};
+use crate::models::export::{
+    ExportManifest, ExportRequest, ExportedFile, IcsExportRequest, PdfExport, RenderedEmail,
+};
+use crate::models::webhook::WebhookEvent;
+use crate::services::export_bundle::{self, RenderContext};
+use crate::services::{
+    data_export, docx_export, email_render, ics_export, markdown_export, pdf_export, schedule,
+    webhooks, workspace,
+};
 
+/// Writes a content input and its outputs as a PDF using the workspace's
+/// template. Returns the path and any layout warnings.
 #[tauri::command]
-pub async fn export_pdf(app: AppHandle, content_input_id: String) -> Result<String, AppError> {
-    let detail = get_history_detail(app.clone(), content_input_id.clone()).await?;
+pub async fn export_pdf(
+    app: AppHandle,
+    content_input_id: String,
+    target_path: Option<String>,
+) -> Result<PdfExport, AppError> {
+    let (detail, template) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        (
+            load_history_detail(&conn, &content_input_id)?,
+            pdf_export::load_template(&conn),
+        )
+    };
 
     if detail.outputs.is_empty() {
         return Err(AppError::Validation(
@@ -15,26 +46,638 @@ pub async fn export_pdf(app: AppHandle, content_input_id: String) -> Result<Stri
         ));
     }
 
-    // Create output path in app data dir
+    let output_path = output_path(&app, target_path, "pdf", std::slice::from_ref(&detail))?;
+    let output_path_str = output_path
+        .to_str()
+        .ok_or_else(|| AppError::PdfExport("Invalid path encoding".to_string()))?;
+
+    let export =
+        pdf_export::export_to_pdf(std::slice::from_ref(&detail), &template, output_path_str)?;
+    notify_exported(&app, "pdf", std::slice::from_ref(&export.path), &[detail]).await;
+    Ok(export)
+}
+
+/// Exports selected outputs of one or more content inputs, either as one
+/// document or as a ZIP archive with a file per input. Returns a manifest of
+/// what was written.
+#[tauri::command]
+pub async fn export_selection(
+    app: AppHandle,
+    request: ExportRequest,
+) -> Result<ExportManifest, AppError> {
+    let (details, template, voices, key_points, scheduled) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+
+        let details = load_selection(&conn, &request)?;
+        let key_points: HashMap<String, KeyPoints> = details
+            .iter()
+            .filter_map(|d| Some((d.input.id.clone(), load_key_points(&conn, &d.input.id)?)))
+            .collect();
+        let output_ids: Vec<String> = details
+            .iter()
+            .flat_map(|d| d.outputs.iter().map(|o| o.id.clone()))
+            .collect();
+        (
+            details,
+            pdf_export::load_template(&conn),
+            voice_names_by_version(&conn)?,
+            key_points,
+            schedule::scheduled_times(&conn, &output_ids)?,
+        )
+    };
+
+    let context = RenderContext {
+        template: &template,
+        voices: &voices,
+        key_points: &key_points,
+        scheduled: &scheduled,
+        split_parts: request.split_parts,
+    };
+    let extension = request.file_type.extension();
+    let mut warnings = Vec::new();
+
+    if request.zip {
+        let mut files = Vec::new();
+        for detail in &details {
+            let (bytes, mut file_warnings) =
+                export_bundle::render(request.file_type, std::slice::from_ref(detail), &context)?;
+            warnings.append(&mut file_warnings);
+            files.push((format!("{}.{}", file_stem(&detail.input, None), extension), bytes));
+        }
+        let sizes: Vec<u64> = files.iter().map(|(_, bytes)| bytes.len() as u64).collect();
+        let (archive, names) = export_bundle::build_zip(files)?;
+
+        let archive_path = output_path(&app, request.target_path, "zip", &details)?;
+        write_file(&archive_path, &archive)?;
+
+        let files = details
+            .iter()
+            .zip(names)
+            .zip(sizes)
+            .map(|((detail, name), size_bytes)| ExportedFile {
+                path: name,
+                content_input_ids: vec![detail.input.id.clone()],
+                output_ids: detail.outputs.iter().map(|o| o.id.clone()).collect(),
+                size_bytes,
+            })
+            .collect();
+
+        let archive_path = archive_path.to_string_lossy().to_string();
+        notify_exported(&app, "zip", std::slice::from_ref(&archive_path), &details).await;
+        return Ok(ExportManifest {
+            archive_path: Some(archive_path),
+            files,
+            warnings,
+        });
+    }
+
+    let (bytes, mut render_warnings) = export_bundle::render(request.file_type, &details, &context)?;
+    warnings.append(&mut render_warnings);
+
+    let path = output_path(&app, request.target_path, extension, &details)?;
+    write_file(&path, &bytes)?;
+    let path = path.to_string_lossy().to_string();
+    notify_exported(&app, extension, std::slice::from_ref(&path), &details).await;
+
+    Ok(ExportManifest {
+        archive_path: None,
+        files: vec![ExportedFile {
+            path,
+            content_input_ids: details.iter().map(|d| d.input.id.clone()).collect(),
+            output_ids: details
+                .iter()
+                .flat_map(|d| d.outputs.iter().map(|o| o.id.clone()))
+                .collect(),
+            size_bytes: bytes.len() as u64,
+        }],
+        warnings,
+    })
+}
+
+/// Loads the content inputs an export request selects, keeping only the
+/// outputs it asks for. Inputs left without outputs are dropped.
+fn load_selection(
+    conn: &rusqlite::Connection,
+    request: &ExportRequest,
+) -> Result<Vec<HistoryDetail>, AppError> {
+    let mut input_ids = request.content_input_ids.clone();
+
+    if let Some(ref output_ids) = request.output_ids {
+        let workspace_id = workspace::active_workspace_id(conn);
+        let mut found = Vec::new();
+        for output_id in output_ids {
+            let input_id: String = conn
+                .query_row(
+                    "SELECT content_input_id FROM repurposed_outputs WHERE id = ?1 \
+                     AND content_input_id IN (SELECT id FROM content_inputs WHERE workspace_id = ?2)",
+                    params![output_id, workspace_id],
+                    |row| row.get(0),
+                )
+                .map_err(|_| AppError::NotFound(format!("Output '{}' not found", output_id)))?;
+            found.push(input_id);
+        }
+
+        if request.content_input_ids.is_empty() {
+            for input_id in found {
+                if !input_ids.contains(&input_id) {
+                    input_ids.push(input_id);
+                }
+            }
+        }
+    }
+
+    if input_ids.is_empty() {
+        return Err(AppError::Validation(
+            "Select at least one item or output to export".to_string(),
+        ));
+    }
+
+    let mut details = Vec::new();
+    for id in &input_ids {
+        let mut detail = load_history_detail(conn, id)?;
+        detail.outputs.retain(|output| {
+            request.output_ids.as_ref().is_none_or(|ids| ids.contains(&output.id))
+                && request.formats.as_ref().is_none_or(|formats| formats.contains(&output.format))
+        });
+        if !detail.outputs.is_empty() {
+            details.push(detail);
+        }
+    }
+
+    if details.is_empty() {
+        return Err(AppError::Validation("No outputs to export".to_string()));
+    }
+    Ok(details)
+}
+
+/// The chosen target path with its folder created, or a fresh file in the
+/// exports folder named after the single input exported or the current time.
+fn output_path(
+    app: &AppHandle,
+    target_path: Option<String>,
+    extension: &str,
+    details: &[HistoryDetail],
+) -> Result<PathBuf, AppError> {
+    if let Some(path) = target_path {
+        let path = PathBuf::from(path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            create_target_dir(parent.to_path_buf())?;
+        }
+        return Ok(path);
+    }
+
+    let stem = match details {
+        [detail] => file_stem(&detail.input, None),
+        _ => format!("export_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S")),
+    };
+    Ok(unique_path(&exports_dir(app)?, &stem, extension))
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), AppError> {
+    std::fs::write(path, contents)
+        .map_err(|e| AppError::FileIo(format!("Failed to write '{}': {}", path.display(), e)))
+}
+
+/// Writes a content input and its outputs as a Word document. Returns the path.
+#[tauri::command]
+pub async fn export_docx(
+    app: AppHandle,
+    content_input_id: String,
+    target_path: Option<String>,
+) -> Result<String, AppError> {
+    let detail = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        load_history_detail(&conn, &content_input_id)?
+    };
+
+    if detail.outputs.is_empty() {
+        return Err(AppError::Validation("No outputs to export".to_string()));
+    }
+
+    let output_path = output_path(&app, target_path, "docx", std::slice::from_ref(&detail))?;
+    let output_path_str = output_path
+        .to_str()
+        .ok_or_else(|| AppError::FileIo("Invalid path encoding".to_string()))?;
+
+    let path = docx_export::export_to_docx(std::slice::from_ref(&detail), output_path_str)?;
+    notify_exported(&app, "docx", std::slice::from_ref(&path), &[detail]).await;
+    Ok(path)
+}
+
+/// Writes a content input as Markdown with YAML front matter, either as one
+/// file or one file per output. Returns the paths written.
+#[tauri::command]
+pub async fn export_markdown(
+    app: AppHandle,
+    content_input_id: String,
+    per_output: Option<bool>,
+    target_dir: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let (detail, voices) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        (
+            load_history_detail(&conn, &content_input_id)?,
+            voice_names_by_version(&conn)?,
+        )
+    };
+
+    if detail.outputs.is_empty() {
+        return Err(AppError::Validation("No outputs to export".to_string()));
+    }
+
+    let dir = match target_dir {
+        Some(dir) => create_target_dir(PathBuf::from(dir))?,
+        None => exports_dir(&app)?,
+    };
+
+    let paths = write_markdown(&dir, &detail, &voices, per_output.unwrap_or(false))?;
+    notify_exported(&app, "markdown", &paths, &[detail]).await;
+    Ok(paths)
+}
+
+/// Exports every content input in the workspace matching `filter` into a
+/// folder of Markdown files. Returns the folder path.
+#[tauri::command]
+pub async fn export_history_markdown(
+    app: AppHandle,
+    filter: Option<HistoryFilter>,
+    per_output: Option<bool>,
+    target_dir: Option<String>,
+) -> Result<String, AppError> {
+    let (details, voices) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        let details = filtered_input_ids(&conn, &filter.unwrap_or_default())?
+            .iter()
+            .map(|id| load_history_detail(&conn, id))
+            .collect::<Result<Vec<_>, _>>()?;
+        (details, voice_names_by_version(&conn)?)
+    };
+
+    if details.iter().all(|detail| detail.outputs.is_empty()) {
+        return Err(AppError::Validation("No outputs to export".to_string()));
+    }
+
+    let dir = match target_dir {
+        Some(dir) => create_target_dir(PathBuf::from(dir))?,
+        None => create_target_dir(
+            exports_dir(&app)?
+                .join(format!("markdown_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"))),
+        )?,
+    };
+
+    let per_output = per_output.unwrap_or(false);
+    let details: Vec<HistoryDetail> =
+        details.into_iter().filter(|detail| !detail.outputs.is_empty()).collect();
+    let mut paths = Vec::new();
+    for detail in &details {
+        paths.append(&mut write_markdown(&dir, detail, &voices, per_output)?);
+    }
+    notify_exported(&app, "markdown", &paths, &details).await;
+
+    Ok(dir.to_string_lossy().to_string())
+}
+
+/// Writes a single content input, or the history matching `filter`, as
+/// versioned JSON including the extracted key points. Returns the path.
+#[tauri::command]
+pub async fn export_json(
+    app: AppHandle,
+    content_input_id: Option<String>,
+    filter: Option<HistoryFilter>,
+    target_path: Option<String>,
+) -> Result<String, AppError> {
+    let items =
+        load_export_items(&app, content_input_id.as_deref(), filter.unwrap_or_default()).await?;
+    let input_ids: Vec<String> = items.iter().map(|item| item.input.id.clone()).collect();
+    let json = data_export::build_json(items)?;
+    let path = write_export(&app, target_path, "json", json)?;
+    notify_exported_ids(&app, "json", std::slice::from_ref(&path), input_ids).await;
+    Ok(path)
+}
+
+/// Writes a single content input, or the history matching `filter`, as CSV
+/// with one row per output. `split_parts` gives each tweet of a thread and
+/// each email of a sequence its own row. Returns the path.
+#[tauri::command]
+pub async fn export_csv(
+    app: AppHandle,
+    content_input_id: Option<String>,
+    filter: Option<HistoryFilter>,
+    split_parts: Option<bool>,
+    target_path: Option<String>,
+) -> Result<String, AppError> {
+    let items =
+        load_export_items(&app, content_input_id.as_deref(), filter.unwrap_or_default()).await?;
+    let scheduled = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        let output_ids: Vec<String> = items
+            .iter()
+            .flat_map(|item| item.outputs.iter().map(|o| o.id.clone()))
+            .collect();
+        schedule::scheduled_times(&conn, &output_ids)?
+    };
+    let csv = data_export::build_csv(&items, split_parts.unwrap_or(false), &scheduled);
+    let path = write_export(&app, target_path, "csv", csv)?;
+    let input_ids = items.iter().map(|item| item.input.id.clone()).collect();
+    notify_exported_ids(&app, "csv", std::slice::from_ref(&path), input_ids).await;
+    Ok(path)
+}
+
+/// Renders a newsletter or email sequence output as HTML emails for previewing.
+#[tauri::command]
+pub async fn render_email_html(
+    app: AppHandle,
+    output_id: String,
+) -> Result<Vec<RenderedEmail>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let output = load_output(&conn, &output_id)?;
+    email_render::render_output(&output, &email_render::load_template(&conn))
+}
+
+/// Writes a newsletter or each email of a sequence as an .html file, or as a
+/// multipart .eml with a plain-text alternative. Returns the paths written.
+#[tauri::command]
+pub async fn export_email(
+    app: AppHandle,
+    output_id: String,
+    eml: Option<bool>,
+    target_dir: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let (input, output, template) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+        let output = load_output(&conn, &output_id)?;
+        (
+            get_content_input(&conn, &output.content_input_id)?,
+            output,
+            email_render::load_template(&conn),
+        )
+    };
+
+    let emails = email_render::render_output(&output, &template)?;
+    if emails.is_empty() {
+        return Err(AppError::Validation("No emails to export".to_string()));
+    }
+
+    let dir = match target_dir {
+        Some(dir) => create_target_dir(PathBuf::from(dir))?,
+        None => exports_dir(&app)?,
+    };
+    let stem = file_stem(&input, Some(&output.format));
+    let eml = eml.unwrap_or(false);
+
+    let mut written = Vec::new();
+    for email in &emails {
+        let stem = match email.number {
+            Some(number) => format!("{}-{}", stem, number),
+            None => stem.clone(),
+        };
+        let (extension, contents) = if eml {
+            ("eml", email_render::build_eml(email, &template))
+        } else {
+            ("html", email.html.clone())
+        };
+        let path = unique_path(&dir, &stem, extension);
+        write_file(&path, contents.as_bytes())?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    notify_exported_ids(&app, if eml { "eml" } else { "html" }, &written, vec![input.id]).await;
+    Ok(written)
+}
+
+/// Writes outputs as iCalendar events from a start time, one per post, tweet
+/// or email. Returns the path written.
+#[tauri::command]
+pub async fn export_ics(app: AppHandle, request: IcsExportRequest) -> Result<String, AppError> {
+    if request.output_ids.is_empty() {
+        return Err(AppError::Validation("No outputs to export".to_string()));
+    }
+    let cadence_hours = request.cadence_hours.unwrap_or(24);
+    if cadence_hours == 0 {
+        return Err(AppError::Validation(
+            "Cadence must be at least one hour".to_string(),
+        ));
+    }
+    let duration_minutes = request.duration_minutes.unwrap_or(30).max(1);
+    let start = schedule::parse_datetime(&request.start_at)?;
+
+    // A repeated id would emit two events with the same UID
+    let mut seen = HashSet::new();
+    let output_ids: Vec<String> = request
+        .output_ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+
+    let (outputs, scheduled) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+
+        let mut outputs = Vec::new();
+        for output_id in &output_ids {
+            let output = load_output(&conn, output_id)?;
+            let title = get_content_input(&conn, &output.content_input_id)?.title;
+            outputs.push((output, title));
+        }
+        (outputs, schedule::scheduled_times(&conn, &output_ids)?)
+    };
+
+    let events = ics_export::plan_events(
+        &outputs,
+        start,
+        chrono::Duration::hours(cadence_hours as i64),
+        &scheduled,
+    )?;
+    if events.is_empty() {
+        return Err(AppError::Validation("No events to export".to_string()));
+    }
+    let ics = ics_export::build_ics(&events, chrono::Duration::minutes(duration_minutes as i64));
+    let path = write_export(&app, request.target_path, "ics", ics)?;
+    let mut seen = HashSet::new();
+    let input_ids: Vec<String> = outputs
+        .iter()
+        .map(|(output, _)| output.content_input_id.clone())
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+    notify_exported_ids(&app, "ics", std::slice::from_ref(&path), input_ids).await;
+    Ok(path)
+}
+
+/// Loads one input, or every input matching the filter, keeping only outputs
+/// in the filter's format when one is set.
+async fn load_export_items(
+    app: &AppHandle,
+    content_input_id: Option<&str>,
+    filter: HistoryFilter,
+) -> Result<Vec<JsonExportItem>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let ids = match content_input_id {
+        Some(id) => vec![id.to_string()],
+        None => filtered_input_ids(&conn, &filter)?,
+    };
+
+    let mut items = Vec::new();
+    for id in ids {
+        let mut detail = load_history_detail(&conn, &id)?;
+        if let Some(ref format) = filter.format {
+            detail.outputs.retain(|output| &output.format == format);
+        }
+        if detail.outputs.is_empty() {
+            continue;
+        }
+        items.push(JsonExportItem {
+            key_points: load_key_points(&conn, &id),
+            input: detail.input,
+            outputs: detail.outputs,
+        });
+    }
+
+    if items.is_empty() {
+        return Err(AppError::Validation("No outputs to export".to_string()));
+    }
+    Ok(items)
+}
+
+/// Tells webhook endpoints which files an export wrote and what they hold.
+async fn notify_exported(
+    app: &AppHandle,
+    file_type: &str,
+    paths: &[String],
+    details: &[HistoryDetail],
+) {
+    let input_ids = details.iter().map(|detail| detail.input.id.clone()).collect();
+    notify_exported_ids(app, file_type, paths, input_ids).await;
+}
+
+async fn notify_exported_ids(
+    app: &AppHandle,
+    file_type: &str,
+    paths: &[String],
+    content_input_ids: Vec<String>,
+) {
+    let db = app.state::<DbState>();
+    webhooks::dispatch(
+        &db.conn,
+        WebhookEvent::ExportCompleted,
+        serde_json::json!({
+            "file_type": file_type,
+            "paths": paths,
+            "content_input_ids": content_input_ids,
+        }),
+    )
+    .await;
+}
+
+fn write_export(
+    app: &AppHandle,
+    target_path: Option<String>,
+    extension: &str,
+    contents: String,
+) -> Result<String, AppError> {
+    let path = output_path(app, target_path, extension, &[])?;
+    write_file(&path, contents.as_bytes())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn write_markdown(
+    dir: &Path,
+    detail: &HistoryDetail,
+    voices: &HashMap<String, String>,
+    per_output: bool,
+) -> Result<Vec<String>, AppError> {
+    let files: Vec<(String, String)> = if per_output {
+        detail
+            .outputs
+            .iter()
+            .map(|output| {
+                (
+                    file_stem(&detail.input, Some(&output.format)),
+                    markdown_export::render_output(&detail.input, output, voices),
+                )
+            })
+            .collect()
+    } else {
+        vec![(
+            file_stem(&detail.input, None),
+            markdown_export::render_input(detail, voices),
+        )]
+    };
+
+    let mut written = Vec::new();
+    for (stem, markdown) in files {
+        let path = unique_path(dir, &stem, "md");
+        std::fs::write(&path, markdown)
+            .map_err(|e| AppError::FileIo(format!("Failed to write '{}': {}", path.display(), e)))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// `dir/stem.ext`, or `dir/stem-2.ext` and so on if that file already exists.
+pub(crate) fn unique_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.{}", stem, n, extension));
+        n += 1;
+    }
+    path
+}
+
+pub(crate) fn create_target_dir(dir: PathBuf) -> Result<PathBuf, AppError> {
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::FileIo(format!("Failed to create '{}': {}", dir.display(), e)))?;
+    Ok(dir)
+}
+
+/// Returns `app_data_dir/exports`, creating it if needed.
+pub(crate) fn exports_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
     let app_dir = app
         .path()
         .app_data_dir()
-        .map_err(|e| AppError::PdfExport(format!("Failed to get app data dir: {}", e)))?;
+        .map_err(|e| AppError::FileIo(format!("Failed to get app data dir: {}", e)))?;
     let exports_dir = app_dir.join("exports");
     std::fs::create_dir_all(&exports_dir)
-        .map_err(|e| AppError::PdfExport(format!("Failed to create exports dir: {}", e)))?;
-
-    let filename = format!(
-        "export_{}_{}.pdf",
-        &content_input_id[..8],
-        chrono::Utc::now().format("%Y%m%d_%H%M%S")
-    );
-    let output_path = exports_dir.join(&filename);
-    let output_path_str = output_path
-        .to_str()
-        .ok_or_else(|| AppError::PdfExport("Invalid path encoding".to_string()))?;
+        .map_err(|e| AppError::FileIo(format!("Failed to create exports dir: {}", e)))?;
+    Ok(exports_dir)
+}
 
-    pdf_export::export_to_pdf(&detail.input, &detail.outputs, output_path_str)?;
+/// "2026-03-14-post-title", plus the format when exporting a single output.
+pub(crate) fn file_stem(input: &ContentInput, format: Option<&str>) -> String {
+    let date = input.created_at.get(..10).unwrap_or_default();
+    let slug = slugify(input.title.as_deref().unwrap_or("untitled"));
+    let mut stem = if date.is_empty() { slug } else { format!("{}-{}", date, slug) };
+    if let Some(format) = format {
+        stem.push('-');
+        stem.push_str(&slugify(format));
+    }
+    stem
+}
 
-    Ok(output_path_str.to_string())
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let slug: String = slug.chars().take(60).collect();
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "untitled".to_string()
+    } else {
+        slug.to_string()
+    }
 }
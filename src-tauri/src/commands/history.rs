@@ -1,16 +1,23 @@
-use rusqlite::params;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter};
 use tauri::AppHandle;
 use tauri::Manager;
 
 use crate::db::DbState;
 use crate::errors::AppError;
-use crate::models::content::{ContentInput, HistoryDetail, HistoryItem, HistoryPage, RepurposedOutput};
+use crate::commands::content::get_content_input;
+use crate::models::content::{
+    HistoryDetail, HistoryFilter, HistoryItem, HistoryPage, KeyPoints, RepurposedOutput,
+};
+use crate::models::webhook::WebhookEvent;
+use crate::services::{webhooks, workspace};
 
 #[tauri::command]
 pub async fn get_history(
     app: AppHandle,
     page: Option<u32>,
     page_size: Option<u32>,
+    filter: Option<HistoryFilter>,
 ) -> Result<HistoryPage, AppError> {
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
@@ -18,23 +25,26 @@ pub async fn get_history(
     let page = page.unwrap_or(1).max(1);
     let page_size = page_size.unwrap_or(20).min(100);
     let offset = (page - 1) * page_size;
+    let (where_clause, values) = filter_clause(&conn, &filter.unwrap_or_default())?;
 
     let total: u32 = conn.query_row(
-        "SELECT COUNT(*) FROM content_inputs",
-        [],
+        &format!("SELECT COUNT(*) FROM content_inputs ci WHERE {}", where_clause),
+        params_from_iter(values.iter()),
         |row| row.get(0),
     )?;
 
-    let mut stmt = conn.prepare(
+    let mut stmt = conn.prepare(&format!(
         "SELECT ci.id, ci.title, ci.word_count, ci.created_at, \
          (SELECT COUNT(*) FROM repurposed_outputs WHERE content_input_id = ci.id) as format_count \
          FROM content_inputs ci \
+         WHERE {} \
          ORDER BY ci.created_at DESC \
-         LIMIT ?1 OFFSET ?2",
-    )?;
+         LIMIT {} OFFSET {}",
+        where_clause, page_size, offset
+    ))?;
 
     let items = stmt
-        .query_map(params![page_size, offset], |row| {
+        .query_map(params_from_iter(values.iter()), |row| {
             Ok(HistoryItem {
                 id: row.get(0)?,
                 title: row.get(1)?,
@@ -54,74 +64,250 @@ pub async fn get_history(
     })
 }
 
+/// Ids of the content inputs in the active workspace matching `filter`,
+/// newest first.
+pub(crate) fn filtered_input_ids(
+    conn: &rusqlite::Connection,
+    filter: &HistoryFilter,
+) -> Result<Vec<String>, AppError> {
+    let (where_clause, values) = filter_clause(conn, filter)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT ci.id FROM content_inputs ci WHERE {} ORDER BY ci.created_at DESC",
+        where_clause
+    ))?;
+    let ids = stmt
+        .query_map(params_from_iter(values.iter()), |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(ids)
+}
+
+/// Builds the WHERE clause (over `content_inputs ci`) and its parameters for
+/// a history filter, always scoped to the active workspace.
+fn filter_clause(
+    conn: &rusqlite::Connection,
+    filter: &HistoryFilter,
+) -> Result<(String, Vec<Value>), AppError> {
+    let mut conditions = vec!["ci.workspace_id = ?".to_string()];
+    let mut values = vec![Value::Text(workspace::active_workspace_id(conn))];
+
+    if let Some(search) = filter.search.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        let escaped = search
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        conditions.push(
+            "(ci.title LIKE ? ESCAPE '\\' OR ci.raw_text LIKE ? ESCAPE '\\')".to_string(),
+        );
+        let pattern = format!("%{}%", escaped);
+        values.push(Value::Text(pattern.clone()));
+        values.push(Value::Text(pattern));
+    }
+
+    if let Some(ref format) = filter.format {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM repurposed_outputs ro \
+             WHERE ro.content_input_id = ci.id AND ro.format = ?)"
+                .to_string(),
+        );
+        values.push(Value::Text(format.clone()));
+    }
+
+    if let Some(ref from) = filter.from {
+        conditions.push("substr(ci.created_at, 1, 10) >= ?".to_string());
+        values.push(Value::Text(validate_date(from)?));
+    }
+
+    if let Some(ref to) = filter.to {
+        conditions.push("substr(ci.created_at, 1, 10) <= ?".to_string());
+        values.push(Value::Text(validate_date(to)?));
+    }
+
+    Ok((conditions.join(" AND "), values))
+}
+
+fn validate_date(date: &str) -> Result<String, AppError> {
+    chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| AppError::Validation(format!("Invalid date '{}', expected YYYY-MM-DD", date)))
+}
+
+/// The key points extracted when an input was repurposed, if they were stored.
+pub(crate) fn load_key_points(conn: &rusqlite::Connection, input_id: &str) -> Option<KeyPoints> {
+    conn.query_row(
+        "SELECT key_points_json FROM content_inputs WHERE id = ?1",
+        params![input_id],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok())
+}
+
 #[tauri::command]
 pub async fn get_history_detail(app: AppHandle, id: String) -> Result<HistoryDetail, AppError> {
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
+    load_history_detail(&conn, &id)
+}
 
-    let input = conn
-        .query_row(
-            "SELECT id, source_url, raw_text, title, word_count, created_at FROM content_inputs WHERE id = ?1",
-            params![id],
-            |row| {
-                Ok(ContentInput {
-                    id: row.get(0)?,
-                    source_url: row.get(1)?,
-                    raw_text: row.get(2)?,
-                    title: row.get(3)?,
-                    word_count: row.get(4)?,
-                    created_at: row.get(5)?,
-                })
-            },
-        )
-        .map_err(|_| AppError::NotFound(format!("Content input '{}' not found", id)))?;
-
-    let mut stmt = conn.prepare(
-        "SELECT id, content_input_id, format, output_text, created_at FROM repurposed_outputs WHERE content_input_id = ?1 ORDER BY created_at ASC",
-    )?;
+pub(crate) fn load_history_detail(
+    conn: &rusqlite::Connection,
+    id: &str,
+) -> Result<HistoryDetail, AppError> {
+    let input = get_content_input(conn, id)?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM repurposed_outputs WHERE content_input_id = ?1 ORDER BY created_at ASC",
+        OUTPUT_COLUMNS
+    ))?;
 
     let outputs = stmt
-        .query_map(params![id], |row| {
-            Ok(RepurposedOutput {
-                id: row.get(0)?,
-                content_input_id: row.get(1)?,
-                format: row.get(2)?,
-                output_text: row.get(3)?,
-                created_at: row.get(4)?,
-            })
-        })?
+        .query_map(params![id], output_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
     Ok(HistoryDetail { input, outputs })
 }
 
+/// A single output, if it belongs to the active workspace.
+pub(crate) fn load_output(
+    conn: &rusqlite::Connection,
+    output_id: &str,
+) -> Result<RepurposedOutput, AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM repurposed_outputs WHERE id = ?1 \
+             AND content_input_id IN (SELECT id FROM content_inputs WHERE workspace_id = ?2)",
+            OUTPUT_COLUMNS
+        ),
+        params![output_id, workspace::active_workspace_id(conn)],
+        output_from_row,
+    )
+    .map_err(|_| AppError::NotFound(format!("Output '{}' not found", output_id)))
+}
+
+const OUTPUT_COLUMNS: &str = "id, content_input_id, format, output_text, created_at, voice_version_id, \
+     voice_score_json, phrase_flags_json, approved_at, tone, length";
+
+fn output_from_row(row: &rusqlite::Row) -> rusqlite::Result<RepurposedOutput> {
+    Ok(RepurposedOutput {
+        id: row.get(0)?,
+        content_input_id: row.get(1)?,
+        format: row.get(2)?,
+        output_text: row.get(3)?,
+        created_at: row.get(4)?,
+        voice_version_id: row.get(5)?,
+        voice_score: row
+            .get::<_, Option<String>>(6)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        phrase_flags: row
+            .get::<_, Option<String>>(7)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        approved_at: row.get(8)?,
+        tone: row.get(9)?,
+        length: row.get(10)?,
+    })
+}
+
 #[tauri::command]
 pub async fn delete_history_item(app: AppHandle, id: String) -> Result<(), AppError> {
     let db = app.state::<DbState>();
-    let conn = db.conn.lock().await;
 
-    // Delete outputs first (in case CASCADE isn't enabled)
-    conn.execute(
-        "DELETE FROM repurposed_outputs WHERE content_input_id = ?1",
-        params![id],
-    )?;
+    let detail = {
+        let conn = db.conn.lock().await;
 
-    // Delete usage records
-    conn.execute(
-        "DELETE FROM usage_records WHERE content_input_id = ?1",
-        params![id],
-    )?;
+        // Only inputs in the active workspace can be deleted; what is deleted
+        // goes into the webhook payload
+        let detail = load_history_detail(&conn, &id)?;
+
+        // Delete outputs first (in case CASCADE isn't enabled)
+        conn.execute(
+            "DELETE FROM schedule_entries WHERE output_id IN \
+             (SELECT id FROM repurposed_outputs WHERE content_input_id = ?1)",
+            params![id],
+        )?;
+        conn.execute(
+            "DELETE FROM published_posts WHERE output_id IN \
+             (SELECT id FROM repurposed_outputs WHERE content_input_id = ?1)",
+            params![id],
+        )?;
+        conn.execute(
+            "DELETE FROM repurposed_outputs WHERE content_input_id = ?1",
+            params![id],
+        )?;
+
+        // Delete usage records
+        conn.execute(
+            "DELETE FROM usage_records WHERE content_input_id = ?1",
+            params![id],
+        )?;
+
+        // Delete the input
+        let affected = conn.execute("DELETE FROM content_inputs WHERE id = ?1", params![id])?;
+
+        if affected == 0 {
+            return Err(AppError::NotFound(format!(
+                "Content input '{}' not found",
+                id
+            )));
+        }
+        detail
+    };
+
+    webhooks::dispatch(&db.conn, WebhookEvent::ContentDeleted, serde_json::to_value(&detail)?)
+        .await;
+
+    Ok(())
+}
+
+/// Marks an output as approved, which makes it available as a brand voice sample.
+#[tauri::command]
+pub async fn set_output_approved(
+    app: AppHandle,
+    output_id: String,
+    approved: bool,
+) -> Result<Option<String>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
 
-    // Delete the input
-    let affected = conn.execute("DELETE FROM content_inputs WHERE id = ?1", params![id])?;
+    let approved_at = approved.then(|| chrono::Utc::now().to_rfc3339());
+    let affected = conn.execute(
+        "UPDATE repurposed_outputs SET approved_at = ?1 WHERE id = ?2 \
+         AND content_input_id IN (SELECT id FROM content_inputs WHERE workspace_id = ?3)",
+        params![approved_at, output_id, workspace::active_workspace_id(&conn)],
+    )?;
 
     if affected == 0 {
-        return Err(AppError::NotFound(format!(
-            "Content input '{}' not found",
-            id
-        )));
+        return Err(AppError::NotFound(format!("Output '{}' not found", output_id)));
     }
 
-    Ok(())
+    Ok(approved_at)
+}
+
+/// Approved outputs in the active workspace, newest first, for picking as
+/// brand voice samples.
+#[tauri::command]
+pub async fn list_approved_outputs(
+    app: AppHandle,
+    limit: Option<u32>,
+) -> Result<Vec<RepurposedOutput>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM repurposed_outputs WHERE approved_at IS NOT NULL \
+         AND content_input_id IN (SELECT id FROM content_inputs WHERE workspace_id = ?1) \
+         ORDER BY approved_at DESC LIMIT ?2",
+        OUTPUT_COLUMNS
+    ))?;
+    let outputs = stmt
+        .query_map(
+            params![workspace::active_workspace_id(&conn), limit.unwrap_or(50).clamp(1, 200)],
+            output_from_row,
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(outputs)
 }
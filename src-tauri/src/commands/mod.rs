@@ -2,6 +2,10 @@ pub mod brand_voice;
 pub mod content;
 pub mod export;
 pub mod history;
+pub mod publishing;
 pub mod repurpose;
+pub mod schedule;
 pub mod settings;
 pub mod usage;
+pub mod webhooks;
+pub mod workspace;
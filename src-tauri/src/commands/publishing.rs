@@ -0,0 +1,345 @@
+use rusqlite::params;
+use tauri::AppHandle;
+use tauri::Manager;
+
+use crate::commands::history::load_output;
+use crate::db::DbState;
+use crate::errors::AppError;
+use crate::models::content::RepurposedOutput;
+use crate::models::publishing::{
+    PublishPlatform, PublishedPost, PublishingAccount, SavePublishingAccountRequest,
+};
+use crate::publishers::{self, bluesky};
+use crate::services::{output_text, workspace};
+
+const ACCOUNT_COLUMNS: &str = "id, platform, label, base_url, handle, secret, created_at";
+
+/// Publishing accounts in the active workspace.
+#[tauri::command]
+pub async fn list_publishing_accounts(app: AppHandle) -> Result<Vec<PublishingAccount>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM publishing_accounts WHERE workspace_id = ?1 ORDER BY created_at ASC",
+        ACCOUNT_COLUMNS
+    ))?;
+    let accounts = stmt
+        .query_map(params![workspace::active_workspace_id(&conn)], |row| {
+            Ok(account_from_row(row)?.0)
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(accounts)
+}
+
+/// Creates an account, or updates one when the request carries its id.
+#[tauri::command]
+pub async fn save_publishing_account(
+    app: AppHandle,
+    request: SavePublishingAccountRequest,
+) -> Result<PublishingAccount, AppError> {
+    let label = request.label.trim().to_string();
+    if label.is_empty() {
+        return Err(AppError::Validation("Account label cannot be empty".to_string()));
+    }
+    let base_url = match (request.platform, request.base_url.as_deref()) {
+        (_, Some(url)) if !url.trim().is_empty() => publishers::normalize_base_url(url)?,
+        (PublishPlatform::Bluesky, _) => bluesky::DEFAULT_PDS_URL.to_string(),
+        (PublishPlatform::Mastodon, _) => {
+            return Err(AppError::Validation(
+                "Mastodon accounts need the instance URL".to_string(),
+            ))
+        }
+    };
+    let handle = request
+        .handle
+        .map(|h| h.trim().trim_start_matches('@').to_string())
+        .filter(|h| !h.is_empty());
+    if request.platform == PublishPlatform::Bluesky && handle.is_none() {
+        return Err(AppError::Validation("Bluesky accounts need a handle".to_string()));
+    }
+    let secret = request
+        .secret
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let id = match request.id {
+        Some(id) => {
+            let (_, existing_secret) = load_account(&conn, &id)?;
+            conn.execute(
+                "UPDATE publishing_accounts SET platform = ?1, label = ?2, base_url = ?3, \
+                 handle = ?4, secret = ?5 WHERE id = ?6",
+                params![
+                    request.platform.to_string(),
+                    label,
+                    base_url,
+                    handle,
+                    secret.unwrap_or(existing_secret),
+                    id
+                ],
+            )?;
+            id
+        }
+        None => {
+            let secret = secret.ok_or_else(|| {
+                AppError::Validation(match request.platform {
+                    PublishPlatform::Mastodon => "An access token is required".to_string(),
+                    PublishPlatform::Bluesky => "An app password is required".to_string(),
+                })
+            })?;
+            let id = uuid::Uuid::new_v4().to_string();
+            conn.execute(
+                "INSERT INTO publishing_accounts (id, workspace_id, platform, label, base_url, handle, secret, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    id,
+                    workspace::active_workspace_id(&conn),
+                    request.platform.to_string(),
+                    label,
+                    base_url,
+                    handle,
+                    secret,
+                    chrono::Utc::now().to_rfc3339()
+                ],
+            )?;
+            id
+        }
+    };
+
+    Ok(load_account(&conn, &id)?.0)
+}
+
+/// Removes an account and its credentials. Posts made with it stay recorded.
+#[tauri::command]
+pub async fn delete_publishing_account(app: AppHandle, id: String) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    load_account(&conn, &id)?;
+    conn.execute(
+        "UPDATE published_posts SET account_id = NULL WHERE account_id = ?1",
+        params![id],
+    )?;
+    conn.execute("DELETE FROM publishing_accounts WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Posts an output to an account, a thread as a chain of replies. Every post
+/// is length-checked before the first goes out. Posts that were made are
+/// recorded even when a later one fails. The output is marked in progress for
+/// the account while posting, so a second request can't post it again.
+#[tauri::command]
+pub async fn publish_output(
+    app: AppHandle,
+    output_id: String,
+    account_id: String,
+) -> Result<Vec<PublishedPost>, AppError> {
+    let (output, account, secret, posts) = {
+        let db = app.state::<DbState>();
+        let conn = db.conn.lock().await;
+
+        let output = load_output(&conn, &output_id)?;
+        let (account, secret) = load_account(&conn, &account_id)?;
+        let posts = output_posts(&output)?;
+        let already: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM published_posts WHERE output_id = ?1 AND account_id = ?2",
+            params![output_id, account_id],
+            |row| row.get(0),
+        )?;
+        if already > 0 {
+            return Err(AppError::Validation(format!(
+                "This output was already published to '{}'",
+                account.label
+            )));
+        }
+        let claimed = conn.execute(
+            "INSERT OR IGNORE INTO publishing_in_progress (output_id, account_id, started_at) \
+             VALUES (?1, ?2, ?3)",
+            params![output_id, account_id, chrono::Utc::now().to_rfc3339()],
+        )?;
+        if claimed == 0 {
+            return Err(AppError::Validation(format!(
+                "This output is already being published to '{}'",
+                account.label
+            )));
+        }
+        (output, account, secret, posts)
+    };
+
+    let outcome: Result<_, AppError> = async {
+        let publisher = publishers::connect(
+            publishers::http_client(),
+            account.platform,
+            &account.base_url,
+            account.handle.as_deref(),
+            &secret,
+        )
+        .await?;
+        publishers::validate_posts(publisher.as_ref(), &posts)?;
+        Ok(publishers::publish_thread(publisher.as_ref(), &posts).await)
+    }
+    .await;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    let (refs, error) = match outcome {
+        Ok(result) => result,
+        Err(e) => {
+            release_claim(&conn, &output.id, &account.id)?;
+            return Err(e);
+        }
+    };
+
+    let published_at = chrono::Utc::now().to_rfc3339();
+    let mut published = Vec::with_capacity(refs.len());
+    for (i, post) in refs.into_iter().enumerate() {
+        let record = PublishedPost {
+            id: uuid::Uuid::new_v4().to_string(),
+            output_id: output.id.clone(),
+            account_id: Some(account.id.clone()),
+            platform: account.platform,
+            part_number: i as u32 + 1,
+            remote_id: post.id,
+            url: post.url,
+            published_at: published_at.clone(),
+        };
+        conn.execute(
+            "INSERT INTO published_posts (id, output_id, account_id, platform, part_number, remote_id, url, published_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                record.id,
+                record.output_id,
+                record.account_id,
+                record.platform.to_string(),
+                record.part_number,
+                record.remote_id,
+                record.url,
+                record.published_at
+            ],
+        )?;
+        published.push(record);
+    }
+    release_claim(&conn, &output.id, &account.id)?;
+
+    match error {
+        Some(e) if published.is_empty() => Err(e),
+        Some(e) => Err(AppError::Publish(format!(
+            "Posted {} of {} before stopping: {}",
+            published.len(),
+            posts.len(),
+            e
+        ))),
+        None => Ok(published),
+    }
+}
+
+/// Posts made from an output, in thread order.
+#[tauri::command]
+pub async fn get_published_posts(
+    app: AppHandle,
+    output_id: String,
+) -> Result<Vec<PublishedPost>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    load_output(&conn, &output_id)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, output_id, account_id, platform, part_number, remote_id, url, published_at \
+         FROM published_posts WHERE output_id = ?1 ORDER BY published_at ASC, part_number ASC",
+    )?;
+    let posts = stmt
+        .query_map(params![output_id], |row| {
+            Ok(PublishedPost {
+                id: row.get(0)?,
+                output_id: row.get(1)?,
+                account_id: row.get(2)?,
+                platform: PublishPlatform::parse(&row.get::<_, String>(3)?)
+                    .unwrap_or(PublishPlatform::Mastodon),
+                part_number: row.get(4)?,
+                remote_id: row.get(5)?,
+                url: row.get(6)?,
+                published_at: row.get(7)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(posts)
+}
+
+fn release_claim(
+    conn: &rusqlite::Connection,
+    output_id: &str,
+    account_id: &str,
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM publishing_in_progress WHERE output_id = ?1 AND account_id = ?2",
+        params![output_id, account_id],
+    )?;
+    Ok(())
+}
+
+/// Drops in-progress markers left by a publish the app quit during, since
+/// nothing can be posting at startup. Posts that went out before the quit
+/// were not recorded, so the platform is the place to check before retrying.
+pub fn clear_interrupted_publishes(conn: &rusqlite::Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM publishing_in_progress", [])?;
+    Ok(())
+}
+
+/// The posts an output goes out as: one per tweet of a thread, otherwise one.
+fn output_posts(output: &RepurposedOutput) -> Result<Vec<String>, AppError> {
+    match output.format.as_str() {
+        "twitter_thread" => Ok(output_text::split_tweets(&output.output_text)),
+        "newsletter" | "email_sequence" => Err(AppError::Validation(format!(
+            "{} outputs are sent by email, not posted",
+            output_text::format_display_name(&output.format)
+        ))),
+        _ => Ok(vec![output.output_text.trim().to_string()]),
+    }
+}
+
+/// An account in the active workspace, with its secret.
+fn load_account(
+    conn: &rusqlite::Connection,
+    id: &str,
+) -> Result<(PublishingAccount, String), AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM publishing_accounts WHERE id = ?1 AND workspace_id = ?2",
+            ACCOUNT_COLUMNS
+        ),
+        params![id, workspace::active_workspace_id(conn)],
+        account_from_row,
+    )
+    .map_err(|_| AppError::NotFound(format!("Publishing account '{}' not found", id)))
+}
+
+fn account_from_row(row: &rusqlite::Row) -> rusqlite::Result<(PublishingAccount, String)> {
+    let platform: String = row.get(1)?;
+    let secret: String = row.get(5)?;
+    let account = PublishingAccount {
+        id: row.get(0)?,
+        platform: PublishPlatform::parse(&platform).ok_or_else(|| {
+            rusqlite::Error::InvalidColumnType(1, platform.clone(), rusqlite::types::Type::Text)
+        })?,
+        label: row.get(2)?,
+        base_url: row.get(3)?,
+        handle: row.get(4)?,
+        masked_secret: mask_secret(&secret),
+        created_at: row.get(6)?,
+    };
+    Ok((account, secret))
+}
+
+fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() > 8 {
+        format!("{}...", chars[..4].iter().collect::<String>())
+    } else {
+        "****".to_string()
+    }
+}
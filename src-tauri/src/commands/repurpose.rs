@@ -1,13 +1,19 @@
+use futures::future::join_all;
 use rusqlite::params;
 use tauri::AppHandle;
 use tauri::Manager;
 
+use crate::commands::brand_voice::{get_profile, get_sample_texts, latest_version_id};
+use crate::commands::content::{get_workspace_content_input, insert_content_input};
 use crate::db::DbState;
 use crate::errors::AppError;
-use crate::models::brand_voice::StyleAttributes;
-use crate::models::content::{RepurposeRequest, RepurposeResponse, RepurposedOutput};
+use crate::models::brand_voice::{StyleAttributes, VoiceGuide, VoiceScore};
+use crate::models::content::{FlaggedPhrase, RepurposeRequest, RepurposeResponse, RepurposedOutput};
+use crate::models::webhook::WebhookEvent;
+use crate::services::brand_voice as brand_voice_service;
 use crate::services::claude_api::ClaudeApiClient;
-use crate::services::usage_tracker;
+use crate::services::phrase_guard::{self, EnforcementMode};
+use crate::services::{dedup, usage_tracker, voice_scoring, webhooks, workspace};
 
 #[tauri::command]
 pub async fn repurpose_content(
@@ -26,82 +32,215 @@ pub async fn repurpose_content(
 
     let db = app.state::<DbState>();
 
+    // Everything is saved to, counted against and announced from the workspace
+    // active now, even if the user switches while the outputs are generated
+    let workspace_id = workspace::active_workspace_id(&*db.conn.lock().await);
+
+    // Duplicate input is sent back before anything is generated or saved,
+    // unless the caller attaches it to the earlier input or saves it anyway
+    if request.existing_input_id.is_none() && !request.save_anyway {
+        let conn = db.conn.lock().await;
+        let duplicate_of =
+            dedup::closest_match(&conn, &request.content, request.source_url.as_deref())?;
+        if duplicate_of.is_some() {
+            return Ok(RepurposeResponse {
+                content_input_id: None,
+                outputs: Vec::new(),
+                duplicate_of,
+            });
+        }
+    }
+
     // Check usage limit
     usage_tracker::check_usage_limit(&db).await?;
 
     // Get API key
     let api_key = usage_tracker::get_api_key(&db).await?;
 
-    // Load brand voice if specified
-    let voice: Option<StyleAttributes> = if let Some(ref voice_id) = request.voice_id {
+    // Load brand voice if specified, along with the version that will refine the
+    // outputs and the samples they are scored against
+    let voice: Option<(VoiceGuide, Option<String>, Vec<String>)> = {
         let conn = db.conn.lock().await;
-        let style_json: String = conn
-            .query_row(
-                "SELECT style_attributes_json FROM brand_voice_profiles WHERE id = ?1",
-                params![voice_id],
-                |row| row.get(0),
-            )
-            .map_err(|_| AppError::NotFound(format!("Brand voice profile '{}' not found", voice_id)))?;
-        let style: StyleAttributes = serde_json::from_str(&style_json)?;
-        Some(style)
-    } else {
-        // Check for default voice
-        let conn = db.conn.lock().await;
-        let result: Result<String, _> = conn.query_row(
-            "SELECT style_attributes_json FROM brand_voice_profiles WHERE is_default = 1",
-            [],
-            |row| row.get(0),
-        );
-        match result {
-            Ok(style_json) => {
-                let style: StyleAttributes = serde_json::from_str(&style_json)?;
-                Some(style)
+        let profile = match request.voice_id {
+            Some(ref voice_id) => Some(get_profile(&conn, voice_id)?),
+            None => {
+                // Check for the workspace's default voice
+                let default_id: Option<String> = conn
+                    .query_row(
+                        "SELECT id FROM brand_voice_profiles WHERE is_default = 1 AND workspace_id = ?1",
+                        params![workspace_id],
+                        |row| row.get(0),
+                    )
+                    .ok();
+                default_id.map(|id| get_profile(&conn, &id)).transpose()?
             }
-            Err(_) => None,
+        };
+
+        match profile {
+            Some(profile) => Some((
+                VoiceGuide {
+                    style: profile.style_attributes,
+                    stylometrics: profile.stylometrics,
+                },
+                latest_version_id(&conn, &profile.id)?,
+                get_sample_texts(&conn, &profile.id)?,
+            )),
+            None => None,
         }
     };
+    let voice_version_id = voice.as_ref().and_then(|(_, version_id, _)| version_id.clone());
+    let use_llm_scoring = voice.is_some() && {
+        let conn = db.conn.lock().await;
+        voice_scoring::llm_scoring_enabled(&conn)
+    };
 
     let config = request.config.unwrap_or_default();
 
-    // Save content input
-    let content_input_id = uuid::Uuid::new_v4().to_string();
-    let word_count = request.content.split_whitespace().count() as u32;
-    let created_at = chrono::Utc::now().to_rfc3339();
+    // Tone and length the request leaves out come from the workspace
+    let (tone, length) = {
+        let conn = db.conn.lock().await;
+        let settings = workspace::get_workspace(&conn, &workspace_id)?;
+        (
+            request.tone.unwrap_or(settings.default_tone),
+            request.length.unwrap_or(settings.default_length),
+        )
+    };
 
-    {
+    // Save content input, or attach to an existing one the user picked
+    let content_input_id = {
         let conn = db.conn.lock().await;
-        conn.execute(
-            "INSERT INTO content_inputs (id, source_url, raw_text, title, word_count, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![content_input_id, request.source_url, request.content, request.title, word_count, created_at],
-        )?;
-    }
+        match request.existing_input_id {
+            Some(ref existing_id) => {
+                get_workspace_content_input(&conn, &workspace_id, existing_id)?.id
+            }
+            None => {
+                insert_content_input(
+                    &conn,
+                    &workspace_id,
+                    request.source_url.clone(),
+                    request.content.clone(),
+                    request.title.clone(),
+                )?
+                .id
+            }
+        }
+    };
 
     // Call Claude API
     let claude = app.state::<ClaudeApiClient>();
-    let results = claude
+    let (key_points, mut results) = claude
         .repurpose(
             &api_key,
             &request.content,
             &request.formats,
-            &request.tone,
-            &request.length,
-            voice.as_ref(),
+            &tone,
+            &length,
+            voice.as_ref().map(|(guide, _, _)| guide),
             &config,
         )
         .await?;
 
-    // Save outputs
+    // Enforce the voice's avoid-phrases and the global banned list. In re-ask
+    // mode the model gets a chance to rewrite; anything left is flagged.
+    let (banned, enforcement_mode) = {
+        let conn = db.conn.lock().await;
+        let avoid_phrases = voice
+            .as_ref()
+            .map(|(guide, _, _)| guide.style.avoid_phrases.as_slice())
+            .unwrap_or_default();
+        (
+            phrase_guard::collect_banned(avoid_phrases, &phrase_guard::load_global_banned(&conn)),
+            phrase_guard::load_mode(&conn),
+        )
+    };
+
+    if enforcement_mode == EnforcementMode::Reask && !banned.is_empty() {
+        for _ in 0..phrase_guard::MAX_FIX_ATTEMPTS {
+            let pending: Vec<(usize, Vec<String>)> = results
+                .iter()
+                .enumerate()
+                .filter_map(|(i, (_, text))| {
+                    let phrases =
+                        phrase_guard::distinct_phrases(&phrase_guard::find_banned(text, &banned));
+                    (!phrases.is_empty()).then_some((i, phrases))
+                })
+                .collect();
+            if pending.is_empty() {
+                break;
+            }
+
+            let fixes = join_all(pending.iter().map(|(i, phrases)| {
+                let (format, text) = &results[*i];
+                claude.remove_banned_phrases(&api_key, text, phrases, format)
+            }))
+            .await;
+
+            // A failed rewrite keeps the original text, which is then flagged
+            for ((i, _), fixed) in pending.iter().zip(fixes) {
+                if let Ok(fixed) = fixed {
+                    results[*i].1 = fixed;
+                }
+            }
+        }
+    }
+
+    let phrase_flags: Vec<Vec<FlaggedPhrase>> = results
+        .iter()
+        .map(|(_, text)| phrase_guard::find_banned(text, &banned))
+        .collect();
+
+    // Score each output against the voice as adjusted for its format; a failed
+    // LLM judgement falls back to the local checks alone
+    let mut scores: Vec<Option<VoiceScore>> = vec![None; results.len()];
+    if let Some((VoiceGuide { style, .. }, _, samples)) = &voice {
+        let format_styles: Vec<StyleAttributes> = results
+            .iter()
+            .map(|(format, _)| brand_voice_service::style_for_format(style, format).0)
+            .collect();
+
+        let judgements = if use_llm_scoring {
+            join_all(
+                results
+                    .iter()
+                    .zip(&format_styles)
+                    .map(|((_, text), format_style)| claude.judge_voice(&api_key, text, format_style)),
+            )
+            .await
+        } else {
+            Vec::new()
+        };
+
+        for (i, ((_, text), format_style)) in results.iter().zip(&format_styles).enumerate() {
+            let score = voice_scoring::score_output(text, format_style, samples);
+            scores[i] = Some(match judgements.get(i) {
+                Some(Ok(judgement)) => voice_scoring::with_judgement(score, judgement.clone()),
+                _ => score,
+            });
+        }
+    }
+
+    // Save outputs, and the key points they were adapted from. An existing
+    // input keeps the key points its earlier outputs were adapted from.
+    let tone = tone.to_string();
+    let length = length.to_string();
     let mut outputs = Vec::new();
-    {
+    let input = {
         let conn = db.conn.lock().await;
-        for (format, text) in &results {
+        conn.execute(
+            "UPDATE content_inputs SET key_points_json = COALESCE(key_points_json, ?1) WHERE id = ?2",
+            params![serde_json::to_string(&key_points)?, content_input_id],
+        )?;
+
+        for (((format, text), voice_score), flags) in results.iter().zip(scores).zip(phrase_flags) {
             let output_id = uuid::Uuid::new_v4().to_string();
             let output_created_at = chrono::Utc::now().to_rfc3339();
             let format_str = format.to_string();
+            let score_json = voice_score.as_ref().map(serde_json::to_string).transpose()?;
+            let flags_json = serde_json::to_string(&flags)?;
 
             conn.execute(
-                "INSERT INTO repurposed_outputs (id, content_input_id, format, output_text, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![output_id, content_input_id, format_str, text, output_created_at],
+                "INSERT INTO repurposed_outputs (id, content_input_id, format, output_text, created_at, voice_version_id, voice_score_json, phrase_flags_json, tone, length) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![output_id, content_input_id, format_str, text, output_created_at, voice_version_id, score_json, flags_json, tone, length],
             )?;
 
             outputs.push(RepurposedOutput {
@@ -110,16 +249,33 @@ pub async fn repurpose_content(
                 format: format_str,
                 output_text: text.clone(),
                 created_at: output_created_at,
+                voice_version_id: voice_version_id.clone(),
+                voice_score,
+                phrase_flags: flags,
+                approved_at: None,
+                tone: Some(tone.clone()),
+                length: Some(length.clone()),
             });
         }
-    }
+
+        get_workspace_content_input(&conn, &workspace_id, &content_input_id)?
+    };
 
     // Record usage
     let format_count = results.len() as u32;
-    usage_tracker::record_usage(&db, &content_input_id, format_count).await?;
+    usage_tracker::record_usage(&db, &workspace_id, &content_input_id, format_count).await?;
+
+    webhooks::dispatch_to_workspace(
+        &db.conn,
+        &workspace_id,
+        WebhookEvent::RepurposeCompleted,
+        serde_json::json!({ "input": input, "outputs": outputs }),
+    )
+    .await;
 
     Ok(RepurposeResponse {
-        content_input_id,
+        content_input_id: Some(content_input_id),
         outputs,
+        duplicate_of: None,
     })
 }
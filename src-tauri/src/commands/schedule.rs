@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, Utc};
+use rusqlite::params;
+use tauri::AppHandle;
+use tauri::Manager;
+
+use crate::commands::history::load_output;
+use crate::db::DbState;
+use crate::errors::AppError;
+use crate::models::schedule::{
+    CalendarDay, CalendarView, CalendarViewKind, ScheduleEntry, ScheduleRequest,
+    SpreadScheduleRequest,
+};
+use crate::services::schedule;
+
+const DEFAULT_INTERVAL_HOURS: u32 = 24;
+
+/// Puts an output, or one tweet or email of it, on the calendar. Scheduling
+/// the same output and part again moves the existing slot.
+#[tauri::command]
+pub async fn schedule_output(
+    app: AppHandle,
+    request: ScheduleRequest,
+) -> Result<ScheduleEntry, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let output = load_output(&conn, &request.output_id)?;
+    schedule::validate_part(&output, request.part_number)?;
+    let scheduled_at = schedule::parse_datetime(&request.scheduled_at)?;
+    let channel = schedule::normalize_channel(request.channel.as_deref(), &output.format)?;
+
+    let id = schedule::upsert_entry(&conn, &output.id, request.part_number, &scheduled_at, &channel)?;
+    schedule::load_entry(&conn, &id)
+}
+
+/// Schedules every part of a thread or email sequence from a start time.
+#[tauri::command]
+pub async fn spread_schedule(
+    app: AppHandle,
+    request: SpreadScheduleRequest,
+) -> Result<Vec<ScheduleEntry>, AppError> {
+    let interval_hours = request.interval_hours.unwrap_or(DEFAULT_INTERVAL_HOURS);
+    if interval_hours == 0 {
+        return Err(AppError::Validation(
+            "Interval must be at least one hour".to_string(),
+        ));
+    }
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let output = load_output(&conn, &request.output_id)?;
+    let start = schedule::parse_datetime(&request.start_at)?;
+    let channel = schedule::normalize_channel(request.channel.as_deref(), &output.format)?;
+    let times = schedule::spread_times(&output, start, Duration::hours(interval_hours as i64))?;
+
+    // A whole-output slot would double up with the per-part ones
+    conn.execute(
+        "DELETE FROM schedule_entries WHERE output_id = ?1 AND part_number IS NULL",
+        params![output.id],
+    )?;
+
+    times
+        .iter()
+        .map(|(part_number, at)| {
+            let id = schedule::upsert_entry(&conn, &output.id, Some(*part_number), at, &channel)?;
+            schedule::load_entry(&conn, &id)
+        })
+        .collect()
+}
+
+/// Moves a slot to another time, and optionally another channel.
+#[tauri::command]
+pub async fn move_schedule_entry(
+    app: AppHandle,
+    id: String,
+    scheduled_at: String,
+    channel: Option<String>,
+) -> Result<ScheduleEntry, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let entry = schedule::load_entry(&conn, &id)?;
+    let scheduled_at = schedule::parse_datetime(&scheduled_at)?;
+    let channel = match channel {
+        Some(channel) => schedule::normalize_channel(Some(&channel), &entry.format)?,
+        None => entry.channel,
+    };
+
+    conn.execute(
+        "UPDATE schedule_entries SET scheduled_at = ?1, channel = ?2, updated_at = ?3 WHERE id = ?4",
+        params![
+            schedule::format_datetime(&scheduled_at),
+            channel,
+            Utc::now().to_rfc3339(),
+            id
+        ],
+    )?;
+    schedule::load_entry(&conn, &id)
+}
+
+#[tauri::command]
+pub async fn unschedule_entry(app: AppHandle, id: String) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    // Only slots in the active workspace can be removed
+    schedule::load_entry(&conn, &id)?;
+    conn.execute("DELETE FROM schedule_entries WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Slots from `from` up to but not including `to`.
+#[tauri::command]
+pub async fn list_schedule(
+    app: AppHandle,
+    from: String,
+    to: String,
+) -> Result<Vec<ScheduleEntry>, AppError> {
+    let from = schedule::parse_datetime(&from)?;
+    let to = schedule::parse_datetime(&to)?;
+    if to <= from {
+        return Err(AppError::Validation(
+            "The end of the range must be after its start".to_string(),
+        ));
+    }
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    schedule::list_entries(&conn, &from, &to)
+}
+
+/// The week or month around `date` (default today), Monday first, with each
+/// day's slots. Days are local to `utc_offset_minutes`, which is the offset
+/// the UI runs in (e.g. 120 for UTC+2).
+#[tauri::command]
+pub async fn get_calendar_view(
+    app: AppHandle,
+    kind: CalendarViewKind,
+    date: Option<String>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<CalendarView, AppError> {
+    let offset = FixedOffset::east_opt(utc_offset_minutes.unwrap_or(0) * 60)
+        .ok_or_else(|| AppError::Validation("UTC offset out of range".to_string()))?;
+    let anchor = match date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+            AppError::Validation(format!("Invalid date '{}', expected YYYY-MM-DD", date))
+        })?,
+        None => Utc::now().with_timezone(&offset).date_naive(),
+    };
+
+    let (range_start, range_end) = match kind {
+        CalendarViewKind::Week => {
+            let monday = week_start(anchor);
+            (monday, monday + Duration::days(6))
+        }
+        CalendarViewKind::Month => {
+            let first = anchor.with_day(1).unwrap_or(anchor);
+            let next_month = first
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(first);
+            (first, next_month - Duration::days(1))
+        }
+    };
+    let start_date = week_start(range_start);
+    let end_date = week_start(range_end) + Duration::days(6);
+
+    let to_utc = |date: NaiveDate| {
+        date.and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_local_timezone(offset)
+            .single()
+            .map(|dt| dt.to_utc())
+            .ok_or_else(|| AppError::Validation("Date out of range".to_string()))
+    };
+    let from = to_utc(start_date)?;
+    let to = to_utc(end_date + Duration::days(1))?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    let entries = schedule::list_entries(&conn, &from, &to)?;
+
+    let mut by_day: HashMap<NaiveDate, Vec<ScheduleEntry>> = HashMap::new();
+    for entry in entries {
+        if let Ok(at) = schedule::parse_datetime(&entry.scheduled_at) {
+            by_day
+                .entry(at.with_timezone(&offset).date_naive())
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    let days = start_date
+        .iter_days()
+        .take_while(|day| *day <= end_date)
+        .map(|day| CalendarDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            in_range: day >= range_start && day <= range_end,
+            entries: by_day.remove(&day).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(CalendarView {
+        kind,
+        start_date: start_date.format("%Y-%m-%d").to_string(),
+        end_date: end_date.format("%Y-%m-%d").to_string(),
+        days,
+    })
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
@@ -4,19 +4,18 @@ use tauri::Manager;
 
 use crate::db::DbState;
 use crate::errors::AppError;
+use crate::models::export::{EmailTemplate, PdfTemplate};
+use crate::services::phrase_guard::{self, EnforcementMode};
+use crate::services::{email_render, pdf_export, url_cache, url_fetcher, voice_scoring, workspace};
 
+/// The active workspace's API key, masked.
 #[tauri::command]
 pub async fn get_api_key(app: AppHandle) -> Result<String, AppError> {
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
 
-    let key: String = conn
-        .query_row(
-            "SELECT value FROM app_settings WHERE key = 'claude_api_key'",
-            [],
-            |row| row.get(0),
-        )
-        .map_err(|e| AppError::Database(format!("Failed to get API key: {}", e)))?;
+    let workspace_id = workspace::active_workspace_id(&conn);
+    let key = workspace::get_setting(&conn, &workspace_id, "claude_api_key").unwrap_or_default();
 
     // Return masked version for display (don't expose full key to frontend)
     if key.is_empty() {
@@ -33,6 +32,16 @@ pub async fn get_api_key(app: AppHandle) -> Result<String, AppError> {
 
 #[tauri::command]
 pub async fn set_api_key(app: AppHandle, api_key: String) -> Result<(), AppError> {
+    let trimmed = validate_api_key(&api_key)?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let workspace_id = workspace::active_workspace_id(&conn);
+    workspace::set_setting(&conn, &workspace_id, "claude_api_key", &trimmed)
+}
+
+pub(crate) fn validate_api_key(api_key: &str) -> Result<String, AppError> {
     let trimmed = api_key.trim().to_string();
 
     if !trimmed.is_empty() && !trimmed.starts_with("sk-ant-") {
@@ -41,13 +50,175 @@ pub async fn set_api_key(app: AppHandle, api_key: String) -> Result<(), AppError
         ));
     }
 
+    Ok(trimmed)
+}
+
+#[tauri::command]
+pub async fn get_url_fetch_allowlist(app: AppHandle) -> Result<Vec<String>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let value: String = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'url_fetch_allowlist'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or_default();
+
+    Ok(url_fetcher::parse_allowlist_setting(&value))
+}
+
+#[tauri::command]
+pub async fn set_url_fetch_allowlist(app: AppHandle, entries: Vec<String>) -> Result<(), AppError> {
+    // Reject malformed hosts/CIDRs before storing them
+    url_fetcher::FetchPolicy::from_allowlist(&entries)?;
+
+    let value = url_fetcher::parse_allowlist_setting(&entries.join(",")).join(",");
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('url_fetch_allowlist', ?1)",
+        params![value],
+    )?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_url_cache_ttl(app: AppHandle) -> Result<u64, AppError> {
+    let db = app.state::<DbState>();
+    Ok(url_cache::get_ttl_seconds(&db).await)
+}
+
+#[tauri::command]
+pub async fn set_url_cache_ttl(app: AppHandle, seconds: u64) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('url_cache_ttl_seconds', ?1)",
+        params![seconds.to_string()],
+    )?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_voice_scoring_use_llm(app: AppHandle) -> Result<bool, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    Ok(voice_scoring::llm_scoring_enabled(&conn))
+}
+
+#[tauri::command]
+pub async fn set_voice_scoring_use_llm(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('voice_scoring_use_llm', ?1)",
+        params![enabled.to_string()],
+    )?;
+
+    Ok(())
+}
+
+/// Phrases banned from every output regardless of brand voice, e.g. legal or
+/// compliance terms.
+#[tauri::command]
+pub async fn get_banned_phrases(app: AppHandle) -> Result<Vec<String>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    Ok(phrase_guard::load_global_banned(&conn))
+}
+
+#[tauri::command]
+pub async fn set_banned_phrases(app: AppHandle, phrases: Vec<String>) -> Result<(), AppError> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for phrase in phrases {
+        let phrase = phrase.trim().to_string();
+        if !phrase.is_empty() && !cleaned.iter().any(|p| p.eq_ignore_ascii_case(&phrase)) {
+            cleaned.push(phrase);
+        }
+    }
+
     let db = app.state::<DbState>();
     let conn = db.conn.lock().await;
 
     conn.execute(
-        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('claude_api_key', ?1)",
-        params![trimmed],
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('banned_phrases', ?1)",
+        params![serde_json::to_string(&cleaned)?],
     )?;
 
     Ok(())
 }
+
+#[tauri::command]
+pub async fn get_phrase_enforcement_mode(app: AppHandle) -> Result<String, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    Ok(phrase_guard::load_mode(&conn).as_str().to_string())
+}
+
+#[tauri::command]
+pub async fn set_phrase_enforcement_mode(app: AppHandle, mode: String) -> Result<(), AppError> {
+    let mode = EnforcementMode::parse(&mode)?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('phrase_enforcement_mode', ?1)",
+        params![mode.as_str()],
+    )?;
+
+    Ok(())
+}
+
+/// The active workspace's branding for PDF exports.
+#[tauri::command]
+pub async fn get_pdf_template(app: AppHandle) -> Result<PdfTemplate, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    Ok(pdf_export::load_template(&conn))
+}
+
+#[tauri::command]
+pub async fn set_pdf_template(app: AppHandle, template: PdfTemplate) -> Result<PdfTemplate, AppError> {
+    let template = pdf_export::validate_template(template)?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let workspace_id = workspace::active_workspace_id(&conn);
+    workspace::set_setting(&conn, &workspace_id, "pdf_template", &serde_json::to_string(&template)?)?;
+
+    Ok(template)
+}
+
+/// The active workspace's styling for HTML email exports.
+#[tauri::command]
+pub async fn get_email_template(app: AppHandle) -> Result<EmailTemplate, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    Ok(email_render::load_template(&conn))
+}
+
+#[tauri::command]
+pub async fn set_email_template(
+    app: AppHandle,
+    template: EmailTemplate,
+) -> Result<EmailTemplate, AppError> {
+    let template = email_render::validate_template(template)?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let workspace_id = workspace::active_workspace_id(&conn);
+    workspace::set_setting(&conn, &workspace_id, "email_template", &serde_json::to_string(&template)?)?;
+
+    Ok(template)
+}
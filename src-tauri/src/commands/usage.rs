@@ -3,7 +3,7 @@ use tauri::Manager;
 
 use crate::db::DbState;
 use crate::errors::AppError;
-use crate::models::usage::UsageInfo;
+use crate::models::usage::{UsageInfo, WorkspaceUsage};
 use crate::services::usage_tracker;
 
 #[tauri::command]
@@ -11,3 +11,13 @@ pub async fn get_usage_info(app: AppHandle) -> Result<UsageInfo, AppError> {
     let db = app.state::<DbState>();
     usage_tracker::get_usage_info(&db).await
 }
+
+/// Per-workspace usage for a month ("YYYY-MM"), for billing clients.
+#[tauri::command]
+pub async fn get_usage_report(
+    app: AppHandle,
+    month: Option<String>,
+) -> Result<Vec<WorkspaceUsage>, AppError> {
+    let db = app.state::<DbState>();
+    usage_tracker::get_usage_report(&db, month.as_deref()).await
+}
@@ -0,0 +1,236 @@
+use rusqlite::params;
+use tauri::AppHandle;
+use tauri::Manager;
+
+use crate::db::DbState;
+use crate::errors::AppError;
+use crate::models::webhook::{
+    DeliveryStatus, SaveWebhookRequest, WebhookDelivery, WebhookEndpoint, WebhookEvent,
+    DEFAULT_WEBHOOK_EVENTS,
+};
+use crate::services::{webhooks, workspace};
+
+const ENDPOINT_COLUMNS: &str = "id, url, secret, events_json, enabled, created_at";
+
+/// Webhook endpoints in the active workspace.
+#[tauri::command]
+pub async fn list_webhooks(app: AppHandle) -> Result<Vec<WebhookEndpoint>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM webhook_endpoints WHERE workspace_id = ?1 ORDER BY created_at ASC",
+        ENDPOINT_COLUMNS
+    ))?;
+    let endpoints = stmt
+        .query_map(params![workspace::active_workspace_id(&conn)], endpoint_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(endpoints)
+}
+
+/// Creates an endpoint, or updates one when the request carries its id.
+#[tauri::command]
+pub async fn save_webhook(
+    app: AppHandle,
+    request: SaveWebhookRequest,
+) -> Result<WebhookEndpoint, AppError> {
+    let url = validate_url(&request.url)?;
+    let events = match request.events {
+        Some(events) if events.is_empty() => {
+            return Err(AppError::Validation(
+                "Choose at least one event for the webhook".to_string(),
+            ))
+        }
+        Some(events) => events,
+        None => DEFAULT_WEBHOOK_EVENTS.to_vec(),
+    };
+    let events_json = serde_json::to_string(&events)?;
+    let secret = request
+        .secret
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let id = match request.id {
+        Some(id) => {
+            let existing = load_endpoint(&conn, &id)?;
+            conn.execute(
+                "UPDATE webhook_endpoints SET url = ?1, secret = ?2, events_json = ?3, enabled = ?4 \
+                 WHERE id = ?5",
+                params![
+                    url,
+                    secret.unwrap_or(existing.secret),
+                    events_json,
+                    request.enabled.unwrap_or(existing.enabled),
+                    id
+                ],
+            )?;
+            id
+        }
+        None => {
+            let id = uuid::Uuid::new_v4().to_string();
+            conn.execute(
+                "INSERT INTO webhook_endpoints (id, workspace_id, url, secret, events_json, enabled, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    workspace::active_workspace_id(&conn),
+                    url,
+                    secret.unwrap_or_else(generate_secret),
+                    events_json,
+                    request.enabled.unwrap_or(true),
+                    chrono::Utc::now().to_rfc3339()
+                ],
+            )?;
+            id
+        }
+    };
+
+    load_endpoint(&conn, &id)
+}
+
+/// Removes an endpoint along with its delivery log.
+#[tauri::command]
+pub async fn delete_webhook(app: AppHandle, id: String) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    load_endpoint(&conn, &id)?;
+    conn.execute("DELETE FROM webhook_deliveries WHERE endpoint_id = ?1", params![id])?;
+    conn.execute("DELETE FROM webhook_endpoints WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Recent deliveries, newest first, for one endpoint or all in the workspace.
+#[tauri::command]
+pub async fn list_webhook_deliveries(
+    app: AppHandle,
+    endpoint_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<WebhookDelivery>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    let limit = limit.unwrap_or(50).clamp(1, 500);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT d.id, d.endpoint_id, d.event, d.status, d.attempts, d.response_status, d.error, \
+         d.created_at, d.completed_at \
+         FROM webhook_deliveries d JOIN webhook_endpoints e ON e.id = d.endpoint_id \
+         WHERE e.workspace_id = ?1 AND (?2 IS NULL OR d.endpoint_id = ?2) \
+         ORDER BY d.created_at DESC LIMIT {}",
+        limit
+    ))?;
+    let deliveries = stmt
+        .query_map(
+            params![workspace::active_workspace_id(&conn), endpoint_id],
+            delivery_from_row,
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(deliveries)
+}
+
+/// Sends a sample event to one endpoint, once and without retries, and
+/// returns how it went. Works for disabled endpoints too.
+#[tauri::command]
+pub async fn send_test_webhook(app: AppHandle, id: String) -> Result<WebhookDelivery, AppError> {
+    let db = app.state::<DbState>();
+
+    let delivery = {
+        let conn = db.conn.lock().await;
+        let endpoint = load_endpoint(&conn, &id)?;
+        let data = serde_json::json!({
+            "message": "Test event from ContentEngine",
+            "endpoint_id": endpoint.id,
+        });
+        webhooks::queue(
+            &conn,
+            &workspace::active_workspace_id(&conn),
+            &[(endpoint.id, endpoint.url, endpoint.secret)],
+            WebhookEvent::Test,
+            data,
+        )?
+        .pop()
+        .ok_or_else(|| AppError::Validation("Failed to queue test event".to_string()))?
+    };
+
+    let outcome =
+        webhooks::deliver(&webhooks::http_client(), &delivery, 1, webhooks::backoff).await;
+
+    let conn = db.conn.lock().await;
+    webhooks::record_outcome(&conn, &delivery.id, &outcome)?;
+    conn.query_row(
+        "SELECT id, endpoint_id, event, status, attempts, response_status, error, created_at, completed_at \
+         FROM webhook_deliveries WHERE id = ?1",
+        params![delivery.id],
+        delivery_from_row,
+    )
+    .map_err(AppError::from)
+}
+
+fn validate_url(url: &str) -> Result<String, AppError> {
+    let url = url.trim();
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| AppError::Validation(format!("Invalid webhook URL '{}'", url)))?;
+    if !matches!(parsed.scheme(), "https" | "http") || parsed.host_str().is_none() {
+        return Err(AppError::Validation(format!(
+            "Webhook URL '{}' must be an http(s) address",
+            url
+        )));
+    }
+    Ok(url.to_string())
+}
+
+fn generate_secret() -> String {
+    format!(
+        "whsec_{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// An endpoint in the active workspace.
+fn load_endpoint(conn: &rusqlite::Connection, id: &str) -> Result<WebhookEndpoint, AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM webhook_endpoints WHERE id = ?1 AND workspace_id = ?2",
+            ENDPOINT_COLUMNS
+        ),
+        params![id, workspace::active_workspace_id(conn)],
+        endpoint_from_row,
+    )
+    .map_err(|_| AppError::NotFound(format!("Webhook '{}' not found", id)))
+}
+
+fn endpoint_from_row(row: &rusqlite::Row) -> rusqlite::Result<WebhookEndpoint> {
+    Ok(WebhookEndpoint {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        secret: row.get(2)?,
+        events: serde_json::from_str(&row.get::<_, String>(3)?).unwrap_or_default(),
+        enabled: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn delivery_from_row(row: &rusqlite::Row) -> rusqlite::Result<WebhookDelivery> {
+    let status = match row.get::<_, String>(3)?.as_str() {
+        "delivered" => DeliveryStatus::Delivered,
+        "failed" => DeliveryStatus::Failed,
+        _ => DeliveryStatus::Pending,
+    };
+    Ok(WebhookDelivery {
+        id: row.get(0)?,
+        endpoint_id: row.get(1)?,
+        event: row.get(2)?,
+        status,
+        attempts: row.get(4)?,
+        response_status: row.get(5)?,
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        completed_at: row.get(8)?,
+    })
+}
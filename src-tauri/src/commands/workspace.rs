@@ -0,0 +1,215 @@
+use rusqlite::params;
+use tauri::AppHandle;
+use tauri::Manager;
+
+use crate::commands::settings::validate_api_key;
+use crate::db::DbState;
+use crate::errors::AppError;
+use crate::models::workspace::{UpdateWorkspaceRequest, Workspace};
+use crate::services::workspace::{self, DEFAULT_WORKSPACE_ID};
+
+#[tauri::command]
+pub async fn list_workspaces(app: AppHandle) -> Result<Vec<Workspace>, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    workspace::list_workspace_ids(&conn)?
+        .iter()
+        .map(|id| workspace::get_workspace(&conn, id))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_active_workspace(app: AppHandle) -> Result<Workspace, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    workspace::get_workspace(&conn, &workspace::active_workspace_id(&conn))
+}
+
+/// Creates an empty workspace. Settings it doesn't override come from the
+/// app-wide defaults, so it has no API key until one is set.
+#[tauri::command]
+pub async fn create_workspace(app: AppHandle, name: String) -> Result<Workspace, AppError> {
+    let name = validate_name(&name)?;
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    ensure_name_available(&conn, &name, None)?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO workspaces (id, name, created_at) VALUES (?1, ?2, ?3)",
+        params![id, name, chrono::Utc::now().to_rfc3339()],
+    )?;
+
+    workspace::get_workspace(&conn, &id)
+}
+
+/// Makes a workspace active. History, brand voices, usage and the API key all
+/// follow the active workspace.
+#[tauri::command]
+pub async fn switch_workspace(app: AppHandle, id: String) -> Result<Workspace, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    workspace::get_workspace(&conn, &id)?;
+    workspace::set_active_workspace(&conn, &id)?;
+
+    workspace::get_workspace(&conn, &id)
+}
+
+#[tauri::command]
+pub async fn update_workspace(
+    app: AppHandle,
+    request: UpdateWorkspaceRequest,
+) -> Result<Workspace, AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+    let id = request.id;
+
+    workspace::get_workspace(&conn, &id)?;
+
+    if let Some(ref name) = request.name {
+        let name = validate_name(name)?;
+        ensure_name_available(&conn, &name, Some(&id))?;
+        conn.execute(
+            "UPDATE workspaces SET name = ?1 WHERE id = ?2",
+            params![name, id],
+        )?;
+    }
+
+    if let Some(ref voice_id) = request.default_voice_id {
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(*) FROM brand_voice_profiles WHERE id = ?1 AND workspace_id = ?2",
+            params![voice_id, id],
+            |row| row.get::<_, u32>(0),
+        )? > 0;
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Brand voice profile '{}' not found in this workspace",
+                voice_id
+            )));
+        }
+
+        conn.execute(
+            "UPDATE brand_voice_profiles SET is_default = (id = ?1) WHERE workspace_id = ?2",
+            params![voice_id, id],
+        )?;
+    }
+
+    if let Some(tone) = request.default_tone {
+        workspace::set_setting(&conn, &id, "default_tone", &tone.to_string())?;
+    }
+
+    if let Some(length) = request.default_length {
+        workspace::set_setting(&conn, &id, "default_length", &length.to_string())?;
+    }
+
+    if let Some(limit) = request.monthly_usage_limit {
+        workspace::set_setting(&conn, &id, "monthly_usage_limit", &limit.to_string())?;
+    }
+
+    if let Some(ref api_key) = request.api_key {
+        workspace::set_setting(&conn, &id, "claude_api_key", &validate_api_key(api_key)?)?;
+    }
+
+    workspace::get_workspace(&conn, &id)
+}
+
+/// Deletes a workspace with all of its content, outputs, brand voices and
+/// usage records. The default workspace cannot be deleted; deleting the
+/// active workspace switches back to it.
+#[tauri::command]
+pub async fn delete_workspace(app: AppHandle, id: String) -> Result<(), AppError> {
+    if id == DEFAULT_WORKSPACE_ID {
+        return Err(AppError::Validation(
+            "The default workspace cannot be deleted".to_string(),
+        ));
+    }
+
+    let db = app.state::<DbState>();
+    let conn = db.conn.lock().await;
+
+    workspace::get_workspace(&conn, &id)?;
+    let was_active = workspace::active_workspace_id(&conn) == id;
+
+    let tx = conn.unchecked_transaction()?;
+
+    // Delete children explicitly in case CASCADE isn't enabled
+    tx.execute(
+        "DELETE FROM schedule_entries WHERE output_id IN \
+         (SELECT ro.id FROM repurposed_outputs ro \
+          JOIN content_inputs ci ON ci.id = ro.content_input_id WHERE ci.workspace_id = ?1)",
+        params![id],
+    )?;
+    tx.execute(
+        "DELETE FROM published_posts WHERE output_id IN \
+         (SELECT ro.id FROM repurposed_outputs ro \
+          JOIN content_inputs ci ON ci.id = ro.content_input_id WHERE ci.workspace_id = ?1)",
+        params![id],
+    )?;
+    tx.execute("DELETE FROM publishing_accounts WHERE workspace_id = ?1", params![id])?;
+    tx.execute(
+        "DELETE FROM webhook_deliveries WHERE endpoint_id IN \
+         (SELECT id FROM webhook_endpoints WHERE workspace_id = ?1)",
+        params![id],
+    )?;
+    tx.execute("DELETE FROM webhook_endpoints WHERE workspace_id = ?1", params![id])?;
+    tx.execute(
+        "DELETE FROM repurposed_outputs WHERE content_input_id IN \
+         (SELECT id FROM content_inputs WHERE workspace_id = ?1)",
+        params![id],
+    )?;
+    tx.execute("DELETE FROM usage_records WHERE workspace_id = ?1", params![id])?;
+    tx.execute("DELETE FROM content_inputs WHERE workspace_id = ?1", params![id])?;
+    tx.execute(
+        "DELETE FROM brand_voice_samples WHERE profile_id IN \
+         (SELECT id FROM brand_voice_profiles WHERE workspace_id = ?1)",
+        params![id],
+    )?;
+    tx.execute(
+        "DELETE FROM brand_voice_versions WHERE profile_id IN \
+         (SELECT id FROM brand_voice_profiles WHERE workspace_id = ?1)",
+        params![id],
+    )?;
+    tx.execute("DELETE FROM brand_voice_profiles WHERE workspace_id = ?1", params![id])?;
+    tx.execute("DELETE FROM workspace_settings WHERE workspace_id = ?1", params![id])?;
+    tx.execute("DELETE FROM workspaces WHERE id = ?1", params![id])?;
+
+    if was_active {
+        workspace::set_active_workspace(&tx, DEFAULT_WORKSPACE_ID)?;
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Workspace name cannot be empty".to_string(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn ensure_name_available(
+    conn: &rusqlite::Connection,
+    name: &str,
+    except_id: Option<&str>,
+) -> Result<(), AppError> {
+    let taken: bool = conn.query_row(
+        "SELECT COUNT(*) FROM workspaces WHERE name = ?1 COLLATE NOCASE AND id IS NOT ?2",
+        params![name, except_id],
+        |row| row.get::<_, u32>(0),
+    )? > 0;
+
+    if taken {
+        return Err(AppError::Validation(format!(
+            "A workspace named '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
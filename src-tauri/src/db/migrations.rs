@@ -76,5 +76,233 @@ pub fn get_migrations() -> Vec<&'static str> {
         INSERT OR IGNORE INTO app_settings (key, value) VALUES ('default_tone', 'professional');
         INSERT OR IGNORE INTO app_settings (key, value) VALUES ('default_length', 'medium');
         "#,
+
+        // Migration 5: URL fetch allowlist setting
+        r#"
+        INSERT OR IGNORE INTO app_settings (key, value) VALUES ('url_fetch_allowlist', '');
+        "#,
+
+        // Migration 6: url_cache + cache TTL setting
+        r#"
+        CREATE TABLE IF NOT EXISTS url_cache (
+            url TEXT PRIMARY KEY,
+            final_url TEXT NOT NULL,
+            body BLOB NOT NULL,
+            headers_json TEXT NOT NULL DEFAULT '{}',
+            etag TEXT,
+            last_modified TEXT,
+            fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        INSERT OR IGNORE INTO app_settings (key, value) VALUES ('url_cache_ttl_seconds', '3600');
+        "#,
+
+        // Migration 7: content fingerprints for deduplication
+        r#"
+        ALTER TABLE content_inputs ADD COLUMN content_hash TEXT;
+        ALTER TABLE content_inputs ADD COLUMN canonical_url TEXT;
+        ALTER TABLE content_inputs ADD COLUMN minhash_signature BLOB;
+
+        CREATE INDEX IF NOT EXISTS idx_content_inputs_content_hash
+            ON content_inputs(content_hash);
+        CREATE INDEX IF NOT EXISTS idx_content_inputs_canonical_url
+            ON content_inputs(canonical_url);
+        "#,
+
+        // Migration 8: brand_voice_versions + voice version on outputs
+        r#"
+        CREATE TABLE IF NOT EXISTS brand_voice_versions (
+            id TEXT PRIMARY KEY,
+            profile_id TEXT NOT NULL,
+            version_number INTEGER NOT NULL,
+            style_attributes_json TEXT NOT NULL,
+            change_source TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (profile_id) REFERENCES brand_voice_profiles(id) ON DELETE CASCADE,
+            UNIQUE (profile_id, version_number)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_brand_voice_versions_profile_id
+            ON brand_voice_versions(profile_id);
+
+        INSERT INTO brand_voice_versions (id, profile_id, version_number, style_attributes_json, change_source, created_at)
+            SELECT lower(hex(randomblob(16))), id, 1, style_attributes_json, 'initial', updated_at
+            FROM brand_voice_profiles;
+
+        ALTER TABLE repurposed_outputs ADD COLUMN voice_version_id TEXT
+            REFERENCES brand_voice_versions(id) ON DELETE SET NULL;
+        "#,
+
+        // Migration 9: voice consistency score on outputs
+        r#"
+        ALTER TABLE repurposed_outputs ADD COLUMN voice_score_json TEXT;
+
+        INSERT OR IGNORE INTO app_settings (key, value) VALUES ('voice_scoring_use_llm', 'false');
+        "#,
+
+        // Migration 10: locally computed stylometrics on brand voice profiles
+        r#"
+        ALTER TABLE brand_voice_profiles ADD COLUMN stylometrics_json TEXT;
+        "#,
+
+        // Migration 11: banned phrase enforcement
+        r#"
+        ALTER TABLE repurposed_outputs ADD COLUMN phrase_flags_json TEXT;
+
+        INSERT OR IGNORE INTO app_settings (key, value) VALUES ('banned_phrases', '[]');
+        INSERT OR IGNORE INTO app_settings (key, value) VALUES ('phrase_enforcement_mode', 'reask');
+        "#,
+
+        // Migration 12: sample provenance + approved outputs
+        r#"
+        ALTER TABLE brand_voice_samples ADD COLUMN source_kind TEXT NOT NULL DEFAULT 'pasted';
+        ALTER TABLE brand_voice_samples ADD COLUMN source_ref TEXT;
+
+        ALTER TABLE repurposed_outputs ADD COLUMN approved_at TEXT;
+        "#,
+
+        // Migration 13: workspaces, per-workspace settings and workspace scoping
+        r#"
+        CREATE TABLE IF NOT EXISTS workspaces (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS workspace_settings (
+            workspace_id TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (workspace_id, key),
+            FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+        );
+
+        INSERT OR IGNORE INTO workspaces (id, name) VALUES ('default', 'Default');
+
+        INSERT OR IGNORE INTO workspace_settings (workspace_id, key, value)
+            SELECT 'default', key, value FROM app_settings
+            WHERE key IN ('claude_api_key', 'monthly_usage_limit', 'default_tone', 'default_length');
+        UPDATE app_settings SET value = '' WHERE key = 'claude_api_key';
+
+        INSERT OR IGNORE INTO app_settings (key, value) VALUES ('active_workspace_id', 'default');
+
+        ALTER TABLE content_inputs ADD COLUMN workspace_id TEXT NOT NULL DEFAULT 'default';
+        ALTER TABLE brand_voice_profiles ADD COLUMN workspace_id TEXT NOT NULL DEFAULT 'default';
+        ALTER TABLE usage_records ADD COLUMN workspace_id TEXT NOT NULL DEFAULT 'default';
+
+        CREATE INDEX IF NOT EXISTS idx_content_inputs_workspace_id
+            ON content_inputs(workspace_id, created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_brand_voice_profiles_workspace_id
+            ON brand_voice_profiles(workspace_id);
+        CREATE INDEX IF NOT EXISTS idx_usage_records_workspace_id
+            ON usage_records(workspace_id, created_at);
+        "#,
+
+        // Migration 14: tone and length each output was generated with
+        r#"
+        ALTER TABLE repurposed_outputs ADD COLUMN tone TEXT;
+        ALTER TABLE repurposed_outputs ADD COLUMN length TEXT;
+        "#,
+
+        // Migration 15: key points extracted from each input
+        r#"
+        ALTER TABLE content_inputs ADD COLUMN key_points_json TEXT;
+        "#,
+
+        // Migration 16: content calendar slots for outputs or single tweets/emails
+        r#"
+        CREATE TABLE IF NOT EXISTS schedule_entries (
+            id TEXT PRIMARY KEY,
+            output_id TEXT NOT NULL REFERENCES repurposed_outputs(id) ON DELETE CASCADE,
+            part_number INTEGER,
+            scheduled_at TEXT NOT NULL,
+            channel TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_schedule_entries_output_part
+            ON schedule_entries(output_id, IFNULL(part_number, 0));
+        CREATE INDEX IF NOT EXISTS idx_schedule_entries_scheduled_at
+            ON schedule_entries(scheduled_at);
+        "#,
+
+        // Migration 17: publishing accounts and the posts made from outputs
+        r#"
+        CREATE TABLE IF NOT EXISTS publishing_accounts (
+            id TEXT PRIMARY KEY,
+            workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+            platform TEXT NOT NULL,
+            label TEXT NOT NULL,
+            base_url TEXT NOT NULL,
+            handle TEXT,
+            secret TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS published_posts (
+            id TEXT PRIMARY KEY,
+            output_id TEXT NOT NULL REFERENCES repurposed_outputs(id) ON DELETE CASCADE,
+            account_id TEXT REFERENCES publishing_accounts(id) ON DELETE SET NULL,
+            platform TEXT NOT NULL,
+            part_number INTEGER NOT NULL,
+            remote_id TEXT NOT NULL,
+            url TEXT NOT NULL,
+            published_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_publishing_accounts_workspace_id
+            ON publishing_accounts(workspace_id);
+        CREATE INDEX IF NOT EXISTS idx_published_posts_output_id
+            ON published_posts(output_id, part_number);
+        "#,
+
+        // Migration 18: outbound webhook endpoints and their delivery log
+        r#"
+        CREATE TABLE IF NOT EXISTS webhook_endpoints (
+            id TEXT PRIMARY KEY,
+            workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+            url TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            events_json TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id TEXT PRIMARY KEY,
+            endpoint_id TEXT NOT NULL REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+            event TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            status TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            response_status INTEGER,
+            error TEXT,
+            created_at TEXT NOT NULL,
+            completed_at TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_workspace_id
+            ON webhook_endpoints(workspace_id);
+        CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_endpoint_id
+            ON webhook_deliveries(endpoint_id, created_at DESC);
+        "#,
+
+        // Migration 19: track when cached pages were last used, for eviction
+        r#"
+        ALTER TABLE url_cache ADD COLUMN last_used_at TEXT;
+        UPDATE url_cache SET last_used_at = fetched_at;
+        "#,
+
+        // Migration 20: outputs being published, so the same output can't go
+        // out to an account twice at once
+        r#"
+        CREATE TABLE IF NOT EXISTS publishing_in_progress (
+            output_id TEXT NOT NULL REFERENCES repurposed_outputs(id) ON DELETE CASCADE,
+            account_id TEXT NOT NULL REFERENCES publishing_accounts(id) ON DELETE CASCADE,
+            started_at TEXT NOT NULL,
+            PRIMARY KEY (output_id, account_id)
+        );
+        "#,
     ]
 }
@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 use serde::Serialize;
 use thiserror::Error;
 
@@ -8,9 +10,13 @@ pub enum AppError {
     #[error("Claude API error: {0}")]
     ClaudeApi(String),
     #[error("URL fetch failed: {0}")]
-    UrlFetch(String),
+    UrlFetch(#[from] UrlFetchError),
     #[error("PDF export error: {0}")]
     PdfExport(String),
+    #[error("File error: {0}")]
+    FileIo(String),
+    #[error("Publishing failed: {0}")]
+    Publish(String),
     #[error("Usage limit reached: {used}/{limit} repurposings used this month")]
     UsageLimitExceeded { used: u32, limit: u32 },
     #[error("Validation error: {0}")]
@@ -21,6 +27,20 @@ pub enum AppError {
     ApiKeyMissing,
 }
 
+#[derive(Error, Debug, Clone)]
+pub enum UrlFetchError {
+    #[error("{0}")]
+    Request(String),
+    #[error("{host} resolves to {address}, which is in a blocked address range")]
+    BlockedAddress { host: String, address: IpAddr },
+    #[error("Too many redirects (limit is {0})")]
+    TooManyRedirects(usize),
+    #[error("Response body exceeds the {limit} byte limit")]
+    BodyTooLarge { limit: usize },
+    #[error("Unsupported content type '{0}'")]
+    UnsupportedContentType(String),
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -4,6 +4,7 @@ mod commands;
 mod db;
 mod errors;
 mod models;
+mod publishers;
 mod services;
 
 use db::DbState;
@@ -31,7 +32,24 @@ pub fn run() {
             db::run_migrations(&conn)
                 .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
 
+            // Fingerprint inputs saved before deduplication existed
+            services::dedup::backfill_fingerprints(&conn)
+                .map_err(|e| Box::new(std::io::Error::other(e.to_string())))?;
+
+            // Measure samples of voices analysed before stylometrics existed
+            services::stylometry::backfill(&conn)
+                .map_err(|e| Box::new(std::io::Error::other(e.to_string())))?;
+
+            // A publish the app quit during can be retried
+            commands::publishing::clear_interrupted_publishes(&conn)
+                .map_err(|e| Box::new(std::io::Error::other(e.to_string())))?;
+
+            // Resend webhooks still pending when the app last quit; like any
+            // delivery, a problem here never stops the app
+            let interrupted = services::webhooks::interrupted_deliveries(&conn).unwrap_or_default();
+
             let db_state = DbState::new(conn);
+            services::webhooks::deliver_in_background(db_state.conn.clone(), interrupted);
             app.manage(db_state);
 
             // Initialize Claude API client
@@ -42,19 +60,87 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::content::save_content,
+            commands::content::check_duplicate_content,
             commands::content::fetch_url,
+            commands::content::reextract_cached_url,
+            commands::content::crawl_site,
             commands::repurpose::repurpose_content,
             commands::brand_voice::get_brand_voices,
             commands::brand_voice::analyze_brand_voice,
             commands::brand_voice::delete_brand_voice,
             commands::brand_voice::set_default_voice,
+            commands::brand_voice::update_brand_voice,
+            commands::brand_voice::get_voice_samples,
+            commands::brand_voice::add_voice_samples,
+            commands::brand_voice::preview_voice_samples,
+            commands::brand_voice::remove_voice_sample,
+            commands::brand_voice::reanalyze_brand_voice,
+            commands::brand_voice::list_voice_versions,
+            commands::brand_voice::diff_voice_versions,
+            commands::brand_voice::restore_voice_version,
+            commands::brand_voice::set_voice_format_override,
+            commands::brand_voice::compare_brand_voices,
+            commands::brand_voice::blend_brand_voices,
+            commands::brand_voice::export_brand_voice,
+            commands::brand_voice::export_all_brand_voices,
+            commands::brand_voice::import_brand_voice,
+            commands::brand_voice::score_output_voice,
+            commands::brand_voice::analyze_voice_stylometrics,
+            commands::settings::get_voice_scoring_use_llm,
+            commands::settings::set_voice_scoring_use_llm,
+            commands::settings::get_banned_phrases,
+            commands::settings::set_banned_phrases,
+            commands::settings::get_phrase_enforcement_mode,
+            commands::settings::set_phrase_enforcement_mode,
+            commands::settings::get_pdf_template,
+            commands::settings::set_pdf_template,
+            commands::settings::get_email_template,
+            commands::settings::set_email_template,
             commands::history::get_history,
             commands::history::get_history_detail,
             commands::history::delete_history_item,
+            commands::history::set_output_approved,
+            commands::history::list_approved_outputs,
             commands::export::export_pdf,
+            commands::export::export_docx,
+            commands::export::export_markdown,
+            commands::export::export_history_markdown,
+            commands::export::export_json,
+            commands::export::export_csv,
+            commands::export::export_selection,
+            commands::export::render_email_html,
+            commands::export::export_email,
+            commands::export::export_ics,
+            commands::schedule::schedule_output,
+            commands::schedule::spread_schedule,
+            commands::schedule::move_schedule_entry,
+            commands::schedule::unschedule_entry,
+            commands::schedule::list_schedule,
+            commands::schedule::get_calendar_view,
+            commands::publishing::list_publishing_accounts,
+            commands::publishing::save_publishing_account,
+            commands::publishing::delete_publishing_account,
+            commands::publishing::publish_output,
+            commands::publishing::get_published_posts,
+            commands::webhooks::list_webhooks,
+            commands::webhooks::save_webhook,
+            commands::webhooks::delete_webhook,
+            commands::webhooks::list_webhook_deliveries,
+            commands::webhooks::send_test_webhook,
             commands::usage::get_usage_info,
+            commands::usage::get_usage_report,
+            commands::workspace::list_workspaces,
+            commands::workspace::get_active_workspace,
+            commands::workspace::create_workspace,
+            commands::workspace::switch_workspace,
+            commands::workspace::update_workspace,
+            commands::workspace::delete_workspace,
             commands::settings::get_api_key,
             commands::settings::set_api_key,
+            commands::settings::get_url_fetch_allowlist,
+            commands::settings::set_url_fetch_allowlist,
+            commands::settings::get_url_cache_ttl,
+            commands::settings::set_url_cache_ttl,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::platform::OutputFormat;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrandVoiceProfile {
     pub id: String,
@@ -9,9 +13,10 @@ pub struct BrandVoiceProfile {
     pub is_default: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub stylometrics: Option<Stylometrics>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StyleAttributes {
     pub tone: String,
     pub vocabulary_level: String,
@@ -19,6 +24,73 @@ pub struct StyleAttributes {
     pub personality_traits: Vec<String>,
     pub signature_phrases: Vec<String>,
     pub avoid_phrases: Vec<String>,
+    #[serde(default)]
+    pub format_overrides: BTreeMap<OutputFormat, FormatVoiceOverride>,
+}
+
+/// Adjustments to the base voice for one output format. Unset fields fall
+/// back to the base attributes; signature phrases are added to the base list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormatVoiceOverride {
+    pub tone: Option<String>,
+    pub vocabulary_level: Option<String>,
+    pub emoji_policy: Option<EmojiPolicy>,
+    #[serde(default)]
+    pub extra_signature_phrases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmojiPolicy {
+    #[serde(rename = "none")]
+    NoEmoji,
+    Sparing,
+    Liberal,
+}
+
+/// Measurable features of a voice's writing samples, computed locally.
+/// Rates are per 1000 words.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stylometrics {
+    pub sample_count: u32,
+    pub word_count: u32,
+    pub avg_sentence_length: f64,
+    pub sentence_length_sd: f64,
+    pub avg_paragraph_length: f64,
+    pub readability_grade: f64,
+    pub lexical_diversity: f64,
+    pub punctuation: PunctuationHabits,
+    pub emoji_per_1000_words: f64,
+    pub top_emojis: Vec<String>,
+    pub first_person_per_1000_words: f64,
+    pub second_person_per_1000_words: f64,
+    /// First-person pronouns as a share of all first- and second-person pronouns.
+    pub first_person_share: Option<f64>,
+    pub frequent_ngrams: Vec<NgramCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PunctuationHabits {
+    pub exclamations: f64,
+    pub questions: f64,
+    pub semicolons: f64,
+    pub colons: f64,
+    pub dashes: f64,
+    pub ellipses: f64,
+    pub parentheses: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NgramCount {
+    pub ngram: String,
+    pub count: u32,
+}
+
+/// Everything the refinement stage needs to know about the selected voice.
+#[derive(Debug, Clone)]
+pub struct VoiceGuide {
+    pub style: StyleAttributes,
+    pub stylometrics: Option<Stylometrics>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,4 +98,141 @@ pub struct AnalyzeVoiceRequest {
     pub name: String,
     pub description: Option<String>,
     pub samples: Vec<String>,
+    #[serde(default)]
+    pub sources: Vec<SampleSource>,
+}
+
+/// Where to gather writing samples from besides pasted text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SampleSource {
+    Url { url: String },
+    File { path: String },
+    Output { output_id: String },
+}
+
+/// A sample-sized piece of text and where it came from. `source_kind` is
+/// one of `pasted`, `url`, `file`, `output`, `import` or `blend`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleExcerpt {
+    pub text: String,
+    pub source_kind: String,
+    pub source_ref: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateVoiceRequest {
+    pub id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Base attributes. Any `format_overrides` inside are ignored; the stored
+    /// overrides are kept unless `format_overrides` below is given.
+    pub style_attributes: Option<StyleAttributes>,
+    #[serde(default)]
+    pub format_overrides: Option<BTreeMap<OutputFormat, FormatVoiceOverride>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrandVoiceSample {
+    pub id: String,
+    pub profile_id: String,
+    pub sample_text: String,
+    pub created_at: String,
+    pub source_kind: String,
+    pub source_ref: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrandVoiceVersion {
+    pub id: String,
+    pub profile_id: String,
+    pub version_number: u32,
+    pub style_attributes: StyleAttributes,
+    pub change_source: String,
+    pub created_at: String,
+}
+
+/// One changed `StyleAttributes` field between two versions. For list fields,
+/// `added` and `removed` hold the individual entries that changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StyleFieldDiff {
+    pub field: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Portable file format for moving brand voices between installations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrandVoiceExport {
+    pub format: String,
+    pub schema_version: u32,
+    pub exported_at: String,
+    pub profiles: Vec<ExportedBrandVoice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedBrandVoice {
+    pub name: String,
+    pub description: Option<String>,
+    pub style_attributes: StyleAttributes,
+    pub is_default: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub samples: Vec<String>,
+}
+
+/// How closely an output matches its brand voice, out of 100, with the
+/// individual checks that produced the overall score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceScore {
+    pub overall: f64,
+    pub criteria: Vec<VoiceCriterionScore>,
+    pub scored_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceCriterionScore {
+    pub criterion: String,
+    pub score: f64,
+    pub weight: f64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceJudgement {
+    pub score: f64,
+    pub rationale: String,
+}
+
+/// Two profiles side by side with the fields and measurements that differ.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceComparison {
+    pub left: BrandVoiceProfile,
+    pub right: BrandVoiceProfile,
+    pub style_differences: Vec<StyleFieldDiff>,
+    pub stylometric_differences: Vec<StylometricDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StylometricDiff {
+    pub metric: String,
+    pub left: f64,
+    pub right: f64,
+    pub difference: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlendVoicesRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub sources: Vec<BlendSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlendSource {
+    pub profile_id: String,
+    pub weight: f64,
 }
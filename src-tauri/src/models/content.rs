@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::brand_voice::VoiceScore;
 use super::platform::OutputFormat;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +30,67 @@ pub struct RepurposedOutput {
     pub format: String,
     pub output_text: String,
     pub created_at: String,
+    pub voice_version_id: Option<String>,
+    pub voice_score: Option<VoiceScore>,
+    pub phrase_flags: Vec<FlaggedPhrase>,
+    pub approved_at: Option<String>,
+    pub tone: Option<String>,
+    pub length: Option<String>,
+}
+
+/// A banned phrase left in an output. `start` and `end` are UTF-16 offsets so
+/// they can be used directly on JavaScript strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlaggedPhrase {
+    pub phrase: String,
+    pub matched_text: String,
+    pub start: u32,
+    pub end: u32,
+    pub source: String,
+}
+
+/// One email of an email sequence output, split on its "EMAIL n:" header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceEmail {
+    pub number: u32,
+    pub label: Option<String>,
+    pub subject: Option<String>,
+    pub preview: Option<String>,
+    pub send_timing: Option<String>,
+    pub body: String,
+}
+
+/// A newsletter output with its SUBJECT/PREVIEW lines split off the body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsletterParts {
+    pub subject: Option<String>,
+    pub preview: Option<String>,
+    pub body: String,
+}
+
+/// Versioned JSON export of content inputs with their key points and outputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonExport {
+    pub schema_version: u32,
+    pub exported_at: String,
+    pub items: Vec<JsonExportItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonExportItem {
+    pub input: ContentInput,
+    pub key_points: Option<KeyPoints>,
+    pub outputs: Vec<RepurposedOutput>,
+}
+
+/// Narrows the history list and bulk exports. `from` and `to` are inclusive
+/// "YYYY-MM-DD" dates; `format` keeps inputs with at least one output in it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryFilter {
+    pub search: Option<String>,
+    pub format: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +121,7 @@ pub struct FetchedContent {
     pub title: Option<String>,
     pub text: String,
     pub word_count: u32,
+    pub canonical_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,14 +130,72 @@ pub struct RepurposeRequest {
     pub source_url: Option<String>,
     pub title: Option<String>,
     pub formats: Vec<OutputFormat>,
-    pub tone: super::platform::TonePreset,
-    pub length: super::platform::LengthPreset,
+    /// Falls back to the workspace's default tone when not given.
+    #[serde(default)]
+    pub tone: Option<super::platform::TonePreset>,
+    /// Falls back to the workspace's default length when not given.
+    #[serde(default)]
+    pub length: Option<super::platform::LengthPreset>,
     pub voice_id: Option<String>,
     pub config: Option<super::platform::PlatformConfig>,
+    /// Adds the outputs to this earlier input instead of saving a new one.
+    pub existing_input_id: Option<String>,
+    /// Saves the input even when it duplicates an earlier one.
+    #[serde(default)]
+    pub save_anyway: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepurposeResponse {
-    pub content_input_id: String,
+    /// `None` when nothing was saved because the input is a duplicate.
+    pub content_input_id: Option<String>,
     pub outputs: Vec<RepurposedOutput>,
+    /// An earlier input the new one duplicates or is at least 90% similar to.
+    /// Nothing is generated until the request attaches to it or saves anyway.
+    pub duplicate_of: Option<DuplicateMatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlRequest {
+    pub sitemap_url: Option<String>,
+    pub index_url: Option<String>,
+    pub link_pattern: Option<String>,
+    pub delay_ms: Option<u64>,
+    pub max_pages: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlProgress {
+    pub url: String,
+    pub current: u32,
+    pub total: u32,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlPageIssue {
+    pub url: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlReport {
+    pub imported: Vec<ContentInput>,
+    pub skipped: Vec<CrawlPageIssue>,
+    pub failed: Vec<CrawlPageIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateMatch {
+    pub content_input_id: String,
+    pub title: Option<String>,
+    pub created_at: String,
+    pub similarity: f64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCheck {
+    pub exact: Option<DuplicateMatch>,
+    pub near: Vec<DuplicateMatch>,
 }
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+/// Branding applied to PDF exports. Stored per workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfTemplate {
+    /// Headings, rules and card borders, as "#rrggbb".
+    pub accent_color: String,
+    pub text_color: String,
+    /// Tried before the built-in Unicode and emoji fallback fonts.
+    pub font_family: Option<String>,
+    /// PNG or JPEG drawn at the top of the first page.
+    pub logo_path: Option<String>,
+    pub header_text: Option<String>,
+    pub footer_text: Option<String>,
+    pub show_page_numbers: bool,
+    pub cover_page: bool,
+}
+
+impl Default for PdfTemplate {
+    fn default() -> Self {
+        Self {
+            accent_color: "#1a5276".to_string(),
+            text_color: "#222222".to_string(),
+            font_family: None,
+            logo_path: None,
+            header_text: None,
+            footer_text: None,
+            show_page_numbers: true,
+            cover_page: true,
+        }
+    }
+}
+
+/// Styling for HTML email exports. Stored per workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailTemplate {
+    /// Shown in the header when there is no logo.
+    pub brand_name: Option<String>,
+    /// A hosted image; email clients don't load local files.
+    pub logo_url: Option<String>,
+    pub accent_color: String,
+    pub background_color: String,
+    pub text_color: String,
+    pub font_family: String,
+    /// Sign-off, address or unsubscribe text under every email.
+    pub footer_text: Option<String>,
+    /// The From header of .eml files.
+    pub from_address: Option<String>,
+}
+
+impl Default for EmailTemplate {
+    fn default() -> Self {
+        Self {
+            brand_name: None,
+            logo_url: None,
+            accent_color: "#1a5276".to_string(),
+            background_color: "#f4f6f8".to_string(),
+            text_color: "#222222".to_string(),
+            font_family: "Helvetica, Arial, sans-serif".to_string(),
+            footer_text: None,
+            from_address: None,
+        }
+    }
+}
+
+/// One email of a newsletter or sequence, ready to paste into an ESP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedEmail {
+    /// Position in a sequence; `None` for a newsletter.
+    pub number: Option<u32>,
+    pub subject: String,
+    pub preview: Option<String>,
+    pub send_timing: Option<String>,
+    pub html: String,
+    pub text: String,
+}
+
+/// A written PDF and anything printpdf reported while laying it out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PdfExport {
+    pub path: String,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFileType {
+    Pdf,
+    Docx,
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl ExportFileType {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFileType::Pdf => "pdf",
+            ExportFileType::Docx => "docx",
+            ExportFileType::Markdown => "md",
+            ExportFileType::Json => "json",
+            ExportFileType::Csv => "csv",
+        }
+    }
+}
+
+/// Selects what to export and where. Outputs are kept when they match both
+/// `output_ids` and `formats`, where given; with only `output_ids`, the
+/// content inputs are those the outputs belong to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportRequest {
+    #[serde(default)]
+    pub content_input_ids: Vec<String>,
+    pub output_ids: Option<Vec<String>>,
+    pub formats: Option<Vec<String>>,
+    pub file_type: ExportFileType,
+    /// One file per content input inside a ZIP archive, instead of one
+    /// document holding everything.
+    #[serde(default)]
+    pub zip: bool,
+    /// One CSV row per tweet or email.
+    #[serde(default)]
+    pub split_parts: bool,
+    /// The document or archive to write, usually from a save dialog.
+    pub target_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// Set when the files were bundled into a ZIP archive.
+    pub archive_path: Option<String>,
+    pub files: Vec<ExportedFile>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedFile {
+    /// A filesystem path, or the entry name inside the archive.
+    pub path: String,
+    pub content_input_ids: Vec<String>,
+    pub output_ids: Vec<String>,
+    pub size_bytes: u64,
+}
+
+/// Outputs to put on a calendar from a start time. Emails with a SEND TIMING
+/// day keep it; every other post follows the previous one after the cadence.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IcsExportRequest {
+    pub output_ids: Vec<String>,
+    /// RFC 3339, "YYYY-MM-DDTHH:MM" (UTC) or a bare date.
+    pub start_at: String,
+    /// Hours between posts; defaults to 24.
+    pub cadence_hours: Option<u32>,
+    /// Minutes each event lasts; defaults to 30.
+    pub duration_minutes: Option<u32>,
+    pub target_path: Option<String>,
+}
@@ -1,4 +1,9 @@
 pub mod brand_voice;
 pub mod content;
+pub mod export;
 pub mod platform;
+pub mod publishing;
+pub mod schedule;
 pub mod usage;
+pub mod webhook;
+pub mod workspace;
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishPlatform {
+    Mastodon,
+    Bluesky,
+}
+
+impl std::fmt::Display for PublishPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PublishPlatform::Mastodon => write!(f, "mastodon"),
+            PublishPlatform::Bluesky => write!(f, "bluesky"),
+        }
+    }
+}
+
+impl PublishPlatform {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "mastodon" => Some(PublishPlatform::Mastodon),
+            "bluesky" => Some(PublishPlatform::Bluesky),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PublishPlatform::Mastodon => "Mastodon",
+            PublishPlatform::Bluesky => "Bluesky",
+        }
+    }
+}
+
+/// An account outputs can be published to. The secret never leaves the
+/// backend; `masked_secret` is for display.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishingAccount {
+    pub id: String,
+    pub platform: PublishPlatform,
+    pub label: String,
+    /// The Mastodon instance or Bluesky PDS, e.g. "https://mastodon.social".
+    pub base_url: String,
+    /// The Bluesky handle or DID to sign in as; unused for Mastodon.
+    pub handle: Option<String>,
+    pub masked_secret: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SavePublishingAccountRequest {
+    /// Updates this account when set, otherwise creates one.
+    pub id: Option<String>,
+    pub platform: PublishPlatform,
+    pub label: String,
+    /// Defaults to https://bsky.social for Bluesky; required for Mastodon.
+    pub base_url: Option<String>,
+    pub handle: Option<String>,
+    /// A Mastodon access token or a Bluesky app password. Left unchanged
+    /// when updating without one.
+    pub secret: Option<String>,
+}
+
+/// One post made from an output: the whole output, or one tweet of a thread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedPost {
+    pub id: String,
+    pub output_id: String,
+    pub account_id: Option<String>,
+    pub platform: PublishPlatform,
+    /// 1-based position in the reply chain.
+    pub part_number: u32,
+    /// The Mastodon status id or Bluesky at:// URI.
+    pub remote_id: String,
+    pub url: String,
+    pub published_at: String,
+}
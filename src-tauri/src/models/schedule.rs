@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// A calendar slot for an output, or for one tweet of a thread or one email
+/// of a sequence when `part_number` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub id: String,
+    pub output_id: String,
+    pub content_input_id: String,
+    pub content_title: Option<String>,
+    pub format: String,
+    pub part_number: Option<u32>,
+    /// RFC 3339, UTC.
+    pub scheduled_at: String,
+    pub channel: String,
+    /// The start of the scheduled text, for calendar cells.
+    pub preview: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Schedules an output or one of its parts. Scheduling the same output and
+/// part again moves the existing slot.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduleRequest {
+    pub output_id: String,
+    pub part_number: Option<u32>,
+    pub scheduled_at: String,
+    /// Defaults to the channel the output's format is written for.
+    pub channel: Option<String>,
+}
+
+/// Schedules every tweet of a thread or email of a sequence from a start
+/// time. Emails follow their SEND TIMING lines where those give a day.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpreadScheduleRequest {
+    pub output_id: String,
+    pub start_at: String,
+    /// Gap between parts without a send timing; defaults to 24.
+    pub interval_hours: Option<u32>,
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarViewKind {
+    Week,
+    Month,
+}
+
+/// Days from Monday to Sunday covering a week, or every week touching a month.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarView {
+    pub kind: CalendarViewKind,
+    pub start_date: String,
+    pub end_date: String,
+    pub days: Vec<CalendarDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarDay {
+    /// "YYYY-MM-DD" in the requested UTC offset.
+    pub date: String,
+    /// False for leading and trailing days outside the month.
+    pub in_range: bool,
+    pub entries: Vec<ScheduleEntry>,
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,3 +8,15 @@ pub struct UsageInfo {
     pub limit: u32,
     pub resets_at: String,
 }
+
+/// One workspace's usage for a calendar month, for billing clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceUsage {
+    pub workspace_id: String,
+    pub workspace_name: String,
+    pub month: String,
+    pub repurpose_runs: u32,
+    pub formats_generated: u32,
+    pub limit: u32,
+    pub outputs_by_format: BTreeMap<String, u32>,
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    RepurposeCompleted,
+    ExportCompleted,
+    ContentDeleted,
+    Test,
+}
+
+impl std::fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookEvent::RepurposeCompleted => write!(f, "repurpose_completed"),
+            WebhookEvent::ExportCompleted => write!(f, "export_completed"),
+            WebhookEvent::ContentDeleted => write!(f, "content_deleted"),
+            WebhookEvent::Test => write!(f, "test"),
+        }
+    }
+}
+
+/// Events an endpoint gets when it doesn't list its own.
+pub const DEFAULT_WEBHOOK_EVENTS: [WebhookEvent; 3] = [
+    WebhookEvent::RepurposeCompleted,
+    WebhookEvent::ExportCompleted,
+    WebhookEvent::ContentDeleted,
+];
+
+/// A URL that receives signed JSON POSTs when content events happen. The
+/// secret is returned in full because receivers need it to check signatures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    pub id: String,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<WebhookEvent>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SaveWebhookRequest {
+    /// Updates this endpoint when set, otherwise creates one.
+    pub id: Option<String>,
+    pub url: String,
+    /// Defaults to every content event.
+    pub events: Option<Vec<WebhookEvent>>,
+    pub enabled: Option<bool>,
+    /// Generated for new endpoints when not given; kept when updating.
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl std::fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliveryStatus::Pending => write!(f, "pending"),
+            DeliveryStatus::Delivered => write!(f, "delivered"),
+            DeliveryStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// One event sent, or being sent, to one endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub endpoint_id: String,
+    pub event: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    /// The HTTP status of the last attempt, if the endpoint answered.
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use super::platform::{LengthPreset, TonePreset};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub is_active: bool,
+    pub default_voice_id: Option<String>,
+    pub default_tone: TonePreset,
+    pub default_length: LengthPreset,
+    pub monthly_usage_limit: u32,
+    pub has_api_key: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateWorkspaceRequest {
+    pub id: String,
+    pub name: Option<String>,
+    pub default_voice_id: Option<String>,
+    pub default_tone: Option<TonePreset>,
+    pub default_length: Option<LengthPreset>,
+    pub monthly_usage_limit: Option<u32>,
+    /// An empty string removes the workspace's key.
+    pub api_key: Option<String>,
+}
@@ -0,0 +1,327 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::errors::AppError;
+use crate::models::publishing::PublishPlatform;
+use crate::publishers::{link_spans, response_error, PostRef, Publisher, ReplyTo};
+
+pub const DEFAULT_PDS_URL: &str = "https://bsky.social";
+
+/// Bluesky limits posts to 300 graphemes, so an emoji counts once.
+const MAX_POST_GRAPHEMES: usize = 300;
+
+const POST_COLLECTION: &str = "app.bsky.feed.post";
+const WEB_URL: &str = "https://bsky.app";
+
+#[derive(Debug, Serialize)]
+struct CreateSessionRequest<'a> {
+    identifier: &'a str,
+    password: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    access_jwt: String,
+    did: String,
+    handle: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateRecordRequest<'a> {
+    repo: &'a str,
+    collection: &'a str,
+    record: PostRecord<'a>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PostRecord<'a> {
+    #[serde(rename = "$type")]
+    record_type: &'a str,
+    text: &'a str,
+    created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply: Option<ReplyRef<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    facets: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReplyRef<'a> {
+    root: StrongRef<'a>,
+    parent: StrongRef<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct StrongRef<'a> {
+    uri: &'a str,
+    cid: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateRecordResponse {
+    uri: String,
+    cid: String,
+}
+
+/// Posts to a Bluesky PDS over the AT Protocol, signed in with an app password.
+pub struct BlueskyPublisher {
+    client: reqwest::Client,
+    base_url: String,
+    session: Session,
+}
+
+impl BlueskyPublisher {
+    /// Creates a session for the handle (or DID) and app password.
+    pub async fn connect(
+        client: reqwest::Client,
+        base_url: &str,
+        identifier: &str,
+        app_password: &str,
+    ) -> Result<Self, AppError> {
+        let base_url = base_url.trim_end_matches('/').to_string();
+        let response = client
+            .post(format!("{}/xrpc/com.atproto.server.createSession", base_url))
+            .json(&CreateSessionRequest {
+                identifier: identifier.trim().trim_start_matches('@'),
+                password: app_password,
+            })
+            .send()
+            .await
+            .map_err(|e| AppError::Publish(format!("Request to Bluesky failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(response_error(PublishPlatform::Bluesky, response).await);
+        }
+
+        let session: Session = response
+            .json()
+            .await
+            .map_err(|e| AppError::Publish(format!("Failed to parse Bluesky session: {}", e)))?;
+
+        Ok(Self {
+            client,
+            base_url,
+            session,
+        })
+    }
+
+    fn strong_ref(post: &PostRef) -> Result<StrongRef<'_>, AppError> {
+        let cid = post.cid.as_deref().ok_or_else(|| {
+            AppError::Publish(format!("Cannot reply to '{}' without its CID", post.id))
+        })?;
+        Ok(StrongRef { uri: &post.id, cid })
+    }
+}
+
+#[async_trait]
+impl Publisher for BlueskyPublisher {
+    fn platform(&self) -> PublishPlatform {
+        PublishPlatform::Bluesky
+    }
+
+    fn max_post_length(&self) -> usize {
+        MAX_POST_GRAPHEMES
+    }
+
+    fn post_length(&self, text: &str) -> usize {
+        text.graphemes(true).count()
+    }
+
+    async fn post(&self, text: &str, reply_to: Option<ReplyTo<'_>>) -> Result<PostRef, AppError> {
+        let reply = match reply_to {
+            Some(reply_to) => Some(ReplyRef {
+                root: Self::strong_ref(reply_to.root)?,
+                parent: Self::strong_ref(reply_to.parent)?,
+            }),
+            None => None,
+        };
+
+        let request = CreateRecordRequest {
+            repo: &self.session.did,
+            collection: POST_COLLECTION,
+            record: PostRecord {
+                record_type: POST_COLLECTION,
+                text,
+                created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                reply,
+                facets: link_facets(text),
+            },
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/xrpc/com.atproto.repo.createRecord", self.base_url))
+            .bearer_auth(&self.session.access_jwt)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AppError::Publish(format!("Request to Bluesky failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(response_error(PublishPlatform::Bluesky, response).await);
+        }
+
+        let record: CreateRecordResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::Publish(format!("Failed to parse Bluesky response: {}", e)))?;
+
+        let rkey = record.uri.rsplit('/').next().unwrap_or_default();
+        Ok(PostRef {
+            url: format!("{}/profile/{}/post/{}", WEB_URL, self.session.handle, rkey),
+            id: record.uri,
+            cid: Some(record.cid),
+        })
+    }
+}
+
+/// Bluesky only links URLs that have a facet over their UTF-8 byte range.
+fn link_facets(text: &str) -> Vec<serde_json::Value> {
+    link_spans(text)
+        .into_iter()
+        .map(|(start, end)| {
+            serde_json::json!({
+                "index": { "byteStart": start, "byteEnd": end },
+                "features": [{
+                    "$type": "app.bsky.richtext.facet#link",
+                    "uri": &text[start..end],
+                }],
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::publishers::{publish_thread, validate_posts};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const DID: &str = "did:plc:abc123";
+
+    fn record(rkey: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "uri": format!("at://{}/app.bsky.feed.post/{}", DID, rkey),
+            "cid": format!("cid-{}", rkey),
+        }))
+    }
+
+    async fn mount_session(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/xrpc/com.atproto.server.createSession"))
+            .and(body_partial_json(serde_json::json!({
+                "identifier": "me.bsky.social",
+                "password": "app-password",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "accessJwt": "jwt",
+                "refreshJwt": "refresh",
+                "did": DID,
+                "handle": "me.bsky.social",
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn publishes_a_thread_as_a_reply_chain() {
+        let server = MockServer::start().await;
+        mount_session(&server).await;
+
+        let root = serde_json::json!({ "uri": format!("at://{}/app.bsky.feed.post/r1", DID), "cid": "cid-r1" });
+        Mock::given(method("POST"))
+            .and(path("/xrpc/com.atproto.repo.createRecord"))
+            .and(header("authorization", "Bearer jwt"))
+            .and(body_partial_json(serde_json::json!({
+                "repo": DID,
+                "collection": "app.bsky.feed.post",
+                "record": { "text": "First" },
+            })))
+            .respond_with(record("r1"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/xrpc/com.atproto.repo.createRecord"))
+            .and(body_partial_json(serde_json::json!({
+                "record": { "text": "Second", "reply": { "root": root, "parent": root } },
+            })))
+            .respond_with(record("r2"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/xrpc/com.atproto.repo.createRecord"))
+            .and(body_partial_json(serde_json::json!({
+                "record": {
+                    "text": "Third",
+                    "reply": {
+                        "root": root,
+                        "parent": { "uri": format!("at://{}/app.bsky.feed.post/r2", DID), "cid": "cid-r2" },
+                    },
+                },
+            })))
+            .respond_with(record("r3"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let publisher = BlueskyPublisher::connect(
+            reqwest::Client::new(),
+            &server.uri(),
+            "@me.bsky.social",
+            "app-password",
+        )
+        .await
+        .unwrap();
+        let posts = vec!["First".to_string(), "Second".to_string(), "Third".to_string()];
+        let (published, error) = publish_thread(&publisher, &posts).await;
+
+        assert!(error.is_none(), "{:?}", error);
+        assert_eq!(published.len(), 3);
+        assert_eq!(published[2].url, "https://bsky.app/profile/me.bsky.social/post/r3");
+    }
+
+    #[tokio::test]
+    async fn reports_a_rejected_sign_in() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/xrpc/com.atproto.server.createSession"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("AuthenticationRequired"))
+            .mount(&server)
+            .await;
+
+        let error = BlueskyPublisher::connect(reqwest::Client::new(), &server.uri(), "me", "wrong")
+            .await
+            .err()
+            .expect("sign-in should fail")
+            .to_string();
+        assert!(error.contains("401"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn counts_graphemes_and_links_urls() {
+        let server = MockServer::start().await;
+        mount_session(&server).await;
+        let publisher =
+            BlueskyPublisher::connect(reqwest::Client::new(), &server.uri(), "me.bsky.social", "app-password")
+                .await
+                .unwrap();
+
+        let family = "👨‍👩‍👧";
+        assert_eq!(publisher.post_length(&family.repeat(300)), 300);
+        assert!(validate_posts(&publisher, &[family.repeat(301)]).is_err());
+
+        let text = "Café notes: https://example.com/a.";
+        let facets = link_facets(text);
+        assert_eq!(facets.len(), 1);
+        assert_eq!(facets[0]["index"]["byteStart"], 13);
+        assert_eq!(facets[0]["index"]["byteEnd"], 34);
+        assert_eq!(facets[0]["features"][0]["uri"], "https://example.com/a");
+    }
+}
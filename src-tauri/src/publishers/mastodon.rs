@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+use crate::models::publishing::PublishPlatform;
+use crate::publishers::{link_spans, response_error, PostRef, Publisher, ReplyTo};
+
+/// The default status limit; instances can raise it, but rarely lower it.
+const MAX_STATUS_CHARS: usize = 500;
+
+/// Mastodon counts every link as this many characters, whatever its length.
+const LINK_CHARS: usize = 23;
+
+#[derive(Debug, Serialize)]
+struct StatusRequest<'a> {
+    status: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_reply_to_id: Option<&'a str>,
+    visibility: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusResponse {
+    id: String,
+    url: Option<String>,
+    uri: String,
+}
+
+/// Posts statuses through the Mastodon REST API with an access token that has
+/// the `write:statuses` scope.
+pub struct MastodonPublisher {
+    client: reqwest::Client,
+    base_url: String,
+    access_token: String,
+}
+
+impl MastodonPublisher {
+    pub fn new(client: reqwest::Client, base_url: &str, access_token: &str) -> Self {
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token: access_token.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Publisher for MastodonPublisher {
+    fn platform(&self) -> PublishPlatform {
+        PublishPlatform::Mastodon
+    }
+
+    fn max_post_length(&self) -> usize {
+        MAX_STATUS_CHARS
+    }
+
+    fn post_length(&self, text: &str) -> usize {
+        let spans = link_spans(text);
+        let link_chars: usize = spans.iter().map(|&(start, end)| text[start..end].chars().count()).sum();
+        text.chars().count() - link_chars + spans.len() * LINK_CHARS
+    }
+
+    async fn post(&self, text: &str, reply_to: Option<ReplyTo<'_>>) -> Result<PostRef, AppError> {
+        let request = StatusRequest {
+            status: text,
+            in_reply_to_id: reply_to.map(|r| r.parent.id.as_str()),
+            visibility: "public",
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/api/v1/statuses", self.base_url))
+            .bearer_auth(&self.access_token)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AppError::Publish(format!("Request to Mastodon failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(response_error(PublishPlatform::Mastodon, response).await);
+        }
+
+        let status: StatusResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::Publish(format!("Failed to parse Mastodon response: {}", e)))?;
+
+        Ok(PostRef {
+            url: status.url.unwrap_or(status.uri),
+            id: status.id,
+            cid: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::publishers::{publish_thread, validate_posts};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn status(id: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": id,
+            "url": format!("https://example.social/@me/{}", id),
+            "uri": format!("https://example.social/users/me/statuses/{}", id),
+        }))
+    }
+
+    #[tokio::test]
+    async fn publishes_a_thread_as_a_reply_chain() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/statuses"))
+            .and(header("authorization", "Bearer token"))
+            .and(body_partial_json(serde_json::json!({ "status": "First" })))
+            .respond_with(status("1"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/statuses"))
+            .and(body_partial_json(serde_json::json!({ "status": "Second", "in_reply_to_id": "1" })))
+            .respond_with(status("2"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/statuses"))
+            .and(body_partial_json(serde_json::json!({ "status": "Third", "in_reply_to_id": "2" })))
+            .respond_with(status("3"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let publisher = MastodonPublisher::new(reqwest::Client::new(), &server.uri(), "token");
+        let posts = vec!["First".to_string(), "Second".to_string(), "Third".to_string()];
+        let (published, error) = publish_thread(&publisher, &posts).await;
+
+        assert!(error.is_none());
+        let urls: Vec<&str> = published.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.social/@me/1",
+                "https://example.social/@me/2",
+                "https://example.social/@me/3"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_posts_made_before_a_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "status": "First" })))
+            .respond_with(status("1"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "status": "Second" })))
+            .respond_with(ResponseTemplate::new(422).set_body_string("Validation failed"))
+            .mount(&server)
+            .await;
+
+        let publisher = MastodonPublisher::new(reqwest::Client::new(), &server.uri(), "token");
+        let posts = vec!["First".to_string(), "Second".to_string(), "Third".to_string()];
+        let (published, error) = publish_thread(&publisher, &posts).await;
+
+        assert_eq!(published.len(), 1);
+        let error = error.expect("second post should fail").to_string();
+        assert!(error.contains("422"), "{}", error);
+        assert!(error.contains("Validation failed"), "{}", error);
+    }
+
+    #[test]
+    fn counts_links_as_fixed_length() {
+        let publisher = MastodonPublisher::new(reqwest::Client::new(), "https://example.social", "t");
+        let link = format!("https://example.com/{}", "a".repeat(200));
+        assert_eq!(publisher.post_length(&format!("Read {}.", link)), 5 + LINK_CHARS + 1);
+
+        let long = "x".repeat(MAX_STATUS_CHARS + 1);
+        let error = validate_posts(&publisher, &["ok".to_string(), long]).unwrap_err();
+        assert!(error.to_string().contains("post 2 is 501"), "{}", error);
+    }
+}
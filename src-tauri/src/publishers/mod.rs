@@ -0,0 +1,174 @@
+pub mod bluesky;
+pub mod mastodon;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::errors::AppError;
+use crate::models::publishing::PublishPlatform;
+
+use self::bluesky::BlueskyPublisher;
+use self::mastodon::MastodonPublisher;
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// A post as the platform identifies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostRef {
+    /// The Mastodon status id or Bluesky at:// URI.
+    pub id: String,
+    /// The Bluesky record CID, needed to reply to the post.
+    pub cid: Option<String>,
+    pub url: String,
+}
+
+/// Where a reply goes in a chain: the first post and the one directly above.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplyTo<'a> {
+    pub root: &'a PostRef,
+    pub parent: &'a PostRef,
+}
+
+#[async_trait]
+pub trait Publisher: Send + Sync {
+    fn platform(&self) -> PublishPlatform;
+
+    /// The longest post the platform accepts, in the units of `post_length`.
+    fn max_post_length(&self) -> usize;
+
+    /// Length of a post as the platform counts it.
+    fn post_length(&self, text: &str) -> usize {
+        text.chars().count()
+    }
+
+    async fn post(&self, text: &str, reply_to: Option<ReplyTo<'_>>) -> Result<PostRef, AppError>;
+}
+
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Signs in to an account and returns a publisher for it.
+pub async fn connect(
+    client: reqwest::Client,
+    platform: PublishPlatform,
+    base_url: &str,
+    handle: Option<&str>,
+    secret: &str,
+) -> Result<Box<dyn Publisher>, AppError> {
+    match platform {
+        PublishPlatform::Mastodon => Ok(Box::new(MastodonPublisher::new(client, base_url, secret))),
+        PublishPlatform::Bluesky => {
+            let handle = handle.filter(|h| !h.trim().is_empty()).ok_or_else(|| {
+                AppError::Validation("Bluesky accounts need a handle".to_string())
+            })?;
+            Ok(Box::new(
+                BlueskyPublisher::connect(client, base_url, handle, secret).await?,
+            ))
+        }
+    }
+}
+
+/// Checks every post of a thread before any goes out, so a long tweet in the
+/// middle doesn't leave half a thread published.
+pub fn validate_posts(publisher: &dyn Publisher, posts: &[String]) -> Result<(), AppError> {
+    if posts.iter().all(|post| post.trim().is_empty()) {
+        return Err(AppError::Validation("Nothing to publish".to_string()));
+    }
+
+    let max = publisher.max_post_length();
+    let too_long: Vec<String> = posts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, post)| {
+            let length = publisher.post_length(post);
+            (length > max).then(|| format!("post {} is {}", i + 1, length))
+        })
+        .collect();
+    if !too_long.is_empty() {
+        return Err(AppError::Validation(format!(
+            "{} allows {} characters per post, but {}",
+            publisher.platform().display_name(),
+            max,
+            too_long.join(", ")
+        )));
+    }
+
+    if posts.iter().any(|post| post.trim().is_empty()) {
+        return Err(AppError::Validation("Posts cannot be empty".to_string()));
+    }
+    Ok(())
+}
+
+/// Posts each item as a reply to the previous one. Returns what went out; a
+/// failure part-way stops the chain and comes back alongside the posts that
+/// were already made, so they can still be recorded.
+pub async fn publish_thread(
+    publisher: &dyn Publisher,
+    posts: &[String],
+) -> (Vec<PostRef>, Option<AppError>) {
+    let mut published: Vec<PostRef> = Vec::with_capacity(posts.len());
+    for text in posts {
+        let reply_to = match (published.first(), published.last()) {
+            (Some(root), Some(parent)) => Some(ReplyTo { root, parent }),
+            _ => None,
+        };
+        match publisher.post(text.trim(), reply_to).await {
+            Ok(post) => published.push(post),
+            Err(e) => return (published, Some(e)),
+        }
+    }
+    (published, None)
+}
+
+/// Trims a trailing slash and checks the scheme, so paths can be appended.
+pub fn normalize_base_url(url: &str) -> Result<String, AppError> {
+    let url = url.trim().trim_end_matches('/');
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| AppError::Validation(format!("Invalid server URL '{}'", url)))?;
+    if !matches!(parsed.scheme(), "https" | "http") || parsed.host_str().is_none() {
+        return Err(AppError::Validation(format!(
+            "Server URL '{}' must be an http(s) address",
+            url
+        )));
+    }
+    Ok(url.to_string())
+}
+
+/// Byte ranges of the http(s) links in a post, without trailing punctuation.
+pub(crate) fn link_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let trimmed = word.trim_end();
+        let start = offset + trimmed.len() - trimmed.trim_start_matches(['(', '<', '"']).len();
+        let link = &text[start..offset + trimmed.len()];
+        if link.starts_with("https://") || link.starts_with("http://") {
+            let link = link.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '>', '"']);
+            if link.len() > "https://".len() {
+                spans.push((start, start + link.len()));
+            }
+        }
+        offset += word.len();
+    }
+    spans
+}
+
+/// An error for a non-success response, with whatever the body says.
+pub(crate) async fn response_error(platform: PublishPlatform, response: reqwest::Response) -> AppError {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unable to read response body".to_string());
+    AppError::Publish(format!(
+        "{} returned status {}: {}",
+        platform.display_name(),
+        status,
+        body
+    ))
+}
@@ -1,6 +1,20 @@
 use crate::errors::AppError;
-use crate::models::brand_voice::StyleAttributes;
+use crate::models::brand_voice::{
+    BrandVoiceExport, EmojiPolicy, StyleAttributes, StyleFieldDiff, StylometricDiff, Stylometrics,
+};
+use crate::models::platform::OutputFormat;
 use crate::services::claude_api::ClaudeApiClient;
+use crate::services::{stylometry, url_fetcher};
+
+/// Rough token budget for the samples sent to Claude in one analysis. When
+/// the saved samples exceed it, a representative subset is sent instead.
+pub const SAMPLE_TOKEN_BUDGET: usize = 8000;
+pub const MIN_SAMPLE_CHARS: usize = 50;
+/// Long documents are cut into excerpts of at most this many characters.
+pub const EXCERPT_MAX_CHARS: usize = 2000;
+
+pub const VOICE_EXPORT_FORMAT: &str = "contentengine.brand_voices";
+pub const VOICE_EXPORT_SCHEMA_VERSION: u32 = 1;
 
 pub async fn analyze_voice_samples(
     client: &ClaudeApiClient,
@@ -13,12 +27,17 @@ pub async fn analyze_voice_samples(
         ));
     }
 
-    if samples.len() > 10 {
-        return Err(AppError::Validation(
-            "Maximum 10 writing samples allowed".to_string(),
-        ));
-    }
+    validate_samples(samples)?;
+
+    let selected: Vec<String> = select_representative(samples, SAMPLE_TOKEN_BUDGET)
+        .into_iter()
+        .map(|i| samples[i].clone())
+        .collect();
 
+    client.analyze_voice(api_key, &selected).await
+}
+
+pub fn validate_samples(samples: &[String]) -> Result<(), AppError> {
     for (i, sample) in samples.iter().enumerate() {
         if sample.trim().is_empty() {
             return Err(AppError::Validation(format!(
@@ -26,13 +45,417 @@ pub async fn analyze_voice_samples(
                 i + 1
             )));
         }
-        if sample.len() < 50 {
+        if sample.len() < MIN_SAMPLE_CHARS {
             return Err(AppError::Validation(format!(
-                "Writing sample {} is too short (minimum 50 characters)",
-                i + 1
+                "Writing sample {} is too short (minimum {} characters)",
+                i + 1,
+                MIN_SAMPLE_CHARS
             )));
         }
     }
 
-    client.analyze_voice(api_key, samples).await
+    Ok(())
+}
+
+/// Approximate token count (about four characters per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Cuts a document into excerpts of whole paragraphs, falling back to whole
+/// sentences for paragraphs longer than [`EXCERPT_MAX_CHARS`]. Excerpts too
+/// short to be useful samples are dropped.
+pub fn split_into_excerpts(text: &str) -> Vec<String> {
+    let mut pieces: Vec<String> = Vec::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        if paragraph.chars().count() <= EXCERPT_MAX_CHARS {
+            pieces.push(paragraph.to_string());
+        } else {
+            pieces.extend(stylometry::split_sentences(paragraph));
+        }
+    }
+
+    let mut excerpts = Vec::new();
+    let mut current = String::new();
+    for piece in pieces {
+        let separator = if current.is_empty() { "" } else { "\n\n" };
+        if !current.is_empty()
+            && current.chars().count() + separator.len() + piece.chars().count() > EXCERPT_MAX_CHARS
+        {
+            excerpts.push(std::mem::take(&mut current));
+            current.push_str(&piece);
+        } else {
+            current.push_str(separator);
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() {
+        excerpts.push(current);
+    }
+
+    excerpts.retain(|e| e.chars().count() >= MIN_SAMPLE_CHARS);
+    excerpts
+}
+
+/// Reads a plain text, Markdown or HTML file as sample text.
+pub fn read_sample_file(path: &str) -> Result<String, AppError> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    let bytes = std::fs::read(path)
+        .map_err(|e| AppError::FileIo(format!("Failed to read '{}': {}", path, e)))?;
+    let text = url_fetcher::decode_body(&bytes, None, None);
+
+    match extension.as_str() {
+        "txt" | "md" | "markdown" => Ok(text),
+        "html" | "htm" => Ok(url_fetcher::parse_page(&text)?.text),
+        other => Err(AppError::Validation(format!(
+            "Unsupported sample file type '.{}' (use .txt, .md or .html)",
+            other
+        ))),
+    }
+}
+
+/// Picks samples that fit in `token_budget` while covering the range of
+/// styles across all samples: each step adds the sample that most reduces
+/// the total stylometric distance from every sample to its nearest pick.
+/// Returns indices in their original order.
+pub fn select_representative(samples: &[String], token_budget: usize) -> Vec<usize> {
+    let tokens: Vec<usize> = samples.iter().map(|s| estimate_tokens(s)).collect();
+    if tokens.iter().sum::<usize>() <= token_budget {
+        return (0..samples.len()).collect();
+    }
+
+    let features = normalized_features(samples);
+    let distance = |a: usize, b: usize| -> f64 {
+        features[a]
+            .iter()
+            .zip(&features[b])
+            .map(|(x, y)| (x - y).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    };
+
+    let mut selected: Vec<usize> = Vec::new();
+    let mut nearest = vec![f64::INFINITY; samples.len()];
+    let mut used = 0;
+
+    loop {
+        let best = (0..samples.len())
+            .filter(|i| !selected.contains(i) && used + tokens[*i] <= token_budget)
+            .map(|candidate| {
+                let cost: f64 = (0..samples.len())
+                    .map(|j| nearest[j].min(distance(j, candidate)))
+                    .sum();
+                (candidate, cost)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        let Some((chosen, _)) = best else {
+            break;
+        };
+        used += tokens[chosen];
+        selected.push(chosen);
+        for (j, d) in nearest.iter_mut().enumerate() {
+            *d = d.min(distance(j, chosen));
+        }
+    }
+
+    selected.sort_unstable();
+    selected
+}
+
+/// Per-sample stylometric features scaled to unit variance, so no single
+/// measure dominates the distance.
+fn normalized_features(samples: &[String]) -> Vec<Vec<f64>> {
+    let raw: Vec<Vec<f64>> = samples
+        .iter()
+        .map(|sample| match stylometry::analyze(std::slice::from_ref(sample)) {
+            Some(m) => vec![
+                m.avg_sentence_length,
+                m.readability_grade,
+                m.lexical_diversity,
+                m.punctuation.exclamations,
+                m.punctuation.questions,
+                m.emoji_per_1000_words,
+                m.first_person_share.unwrap_or(0.5),
+            ],
+            None => vec![0.0; 7],
+        })
+        .collect();
+
+    let dims = raw.first().map(Vec::len).unwrap_or(0);
+    let n = raw.len().max(1) as f64;
+    let scales: Vec<f64> = (0..dims)
+        .map(|d| {
+            let mean = raw.iter().map(|f| f[d]).sum::<f64>() / n;
+            let variance = raw.iter().map(|f| (f[d] - mean).powi(2)).sum::<f64>() / n;
+            if variance > 0.0 {
+                variance.sqrt()
+            } else {
+                1.0
+            }
+        })
+        .collect();
+
+    raw.into_iter()
+        .map(|f| f.iter().zip(&scales).map(|(x, scale)| x / scale).collect())
+        .collect()
+}
+
+/// Compares two sets of style attributes field by field, returning only the
+/// fields that differ.
+pub fn diff_style_attributes(
+    before: &StyleAttributes,
+    after: &StyleAttributes,
+) -> Result<Vec<StyleFieldDiff>, AppError> {
+    let before = serde_json::to_value(before)?;
+    let after = serde_json::to_value(after)?;
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut diffs = Vec::new();
+    for (field, old_value) in before {
+        let new_value = after.get(field).cloned().unwrap_or(serde_json::Value::Null);
+        if *old_value == new_value {
+            continue;
+        }
+
+        let as_strings = |value: &serde_json::Value| -> Vec<String> {
+            value
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let old_items = as_strings(old_value);
+        let new_items = as_strings(&new_value);
+
+        diffs.push(StyleFieldDiff {
+            field: field.clone(),
+            added: new_items
+                .iter()
+                .filter(|item| !old_items.contains(item))
+                .cloned()
+                .collect(),
+            removed: old_items
+                .iter()
+                .filter(|item| !new_items.contains(item))
+                .cloned()
+                .collect(),
+            before: old_value.clone(),
+            after: new_value,
+        });
+    }
+
+    Ok(diffs)
+}
+
+/// The base attributes with the override for `format` applied, plus the
+/// format's emoji policy if it sets one.
+pub fn style_for_format(
+    style: &StyleAttributes,
+    format: &OutputFormat,
+) -> (StyleAttributes, Option<EmojiPolicy>) {
+    let mut merged = StyleAttributes {
+        format_overrides: Default::default(),
+        ..style.clone()
+    };
+
+    let Some(format_override) = style.format_overrides.get(format) else {
+        return (merged, None);
+    };
+
+    if let Some(tone) = format_override.tone.as_ref().filter(|t| !t.trim().is_empty()) {
+        merged.tone = tone.clone();
+    }
+    if let Some(level) = format_override
+        .vocabulary_level
+        .as_ref()
+        .filter(|v| !v.trim().is_empty())
+    {
+        merged.vocabulary_level = level.clone();
+    }
+    for phrase in &format_override.extra_signature_phrases {
+        if !phrase.trim().is_empty() && !merged.signature_phrases.contains(phrase) {
+            merged.signature_phrases.push(phrase.clone());
+        }
+    }
+
+    (merged, format_override.emoji_policy)
+}
+
+/// Measurements that differ between two stylometric profiles, largest
+/// relative difference first.
+pub fn diff_stylometrics(left: &Stylometrics, right: &Stylometrics) -> Vec<StylometricDiff> {
+    let metrics = |m: &Stylometrics| {
+        vec![
+            ("avg_sentence_length", m.avg_sentence_length),
+            ("sentence_length_sd", m.sentence_length_sd),
+            ("avg_paragraph_length", m.avg_paragraph_length),
+            ("readability_grade", m.readability_grade),
+            ("lexical_diversity", m.lexical_diversity),
+            ("exclamations", m.punctuation.exclamations),
+            ("questions", m.punctuation.questions),
+            ("semicolons", m.punctuation.semicolons),
+            ("colons", m.punctuation.colons),
+            ("dashes", m.punctuation.dashes),
+            ("ellipses", m.punctuation.ellipses),
+            ("parentheses", m.punctuation.parentheses),
+            ("emoji_per_1000_words", m.emoji_per_1000_words),
+            ("first_person_per_1000_words", m.first_person_per_1000_words),
+            ("second_person_per_1000_words", m.second_person_per_1000_words),
+        ]
+    };
+
+    let mut diffs: Vec<StylometricDiff> = metrics(left)
+        .into_iter()
+        .zip(metrics(right))
+        .filter(|((_, l), (_, r))| l != r)
+        .map(|((metric, l), (_, r))| StylometricDiff {
+            metric: metric.to_string(),
+            left: l,
+            right: r,
+            difference: ((r - l) * 100.0).round() / 100.0,
+        })
+        .collect();
+
+    let relative = |d: &StylometricDiff| (d.right - d.left).abs() / d.left.abs().max(d.right.abs());
+    diffs.sort_by(|a, b| relative(b).total_cmp(&relative(a)));
+    diffs
+}
+
+/// Combines several voices' attributes by weight. Descriptions are listed
+/// with their share, list entries are ranked by the total weight of the voices
+/// using them, and every voice's avoid-phrases are kept.
+pub fn blend_style_attributes(sources: &[(&StyleAttributes, f64)]) -> StyleAttributes {
+    let total: f64 = sources.iter().map(|(_, w)| w).sum();
+    let mut ordered: Vec<(&StyleAttributes, f64)> = sources
+        .iter()
+        .map(|(style, w)| (*style, w / total))
+        .collect();
+    ordered.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let describe = |field: fn(&StyleAttributes) -> &String| -> String {
+        ordered
+            .iter()
+            .filter(|(style, _)| !field(style).trim().is_empty())
+            .map(|(style, share)| format!("{:.0}% {}", share * 100.0, field(style)))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+
+    let rank = |field: fn(&StyleAttributes) -> &Vec<String>| -> Vec<String> {
+        let mut scored: Vec<(String, f64)> = Vec::new();
+        for (style, share) in &ordered {
+            for item in field(style) {
+                match scored.iter_mut().find(|(s, _)| s.eq_ignore_ascii_case(item)) {
+                    Some((_, score)) => *score += share,
+                    None => scored.push((item.clone(), *share)),
+                }
+            }
+        }
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let keep = ordered.iter().map(|(style, _)| field(style).len()).max().unwrap_or(0);
+        scored.into_iter().take(keep).map(|(item, _)| item).collect()
+    };
+
+    let mut avoid_phrases: Vec<String> = Vec::new();
+    let mut format_overrides = std::collections::BTreeMap::new();
+    for (style, _) in &ordered {
+        for phrase in &style.avoid_phrases {
+            if !avoid_phrases.iter().any(|p| p.eq_ignore_ascii_case(phrase)) {
+                avoid_phrases.push(phrase.clone());
+            }
+        }
+        // The heaviest voice's override wins for each format
+        for (format, format_override) in &style.format_overrides {
+            format_overrides
+                .entry(format.clone())
+                .or_insert_with(|| format_override.clone());
+        }
+    }
+
+    StyleAttributes {
+        tone: describe(|s| &s.tone),
+        vocabulary_level: describe(|s| &s.vocabulary_level),
+        sentence_style: describe(|s| &s.sentence_style),
+        personality_traits: rank(|s| &s.personality_traits),
+        signature_phrases: rank(|s| &s.signature_phrases),
+        avoid_phrases,
+        format_overrides,
+    }
+}
+
+/// Parses an exported brand voice file, checking the format marker and schema
+/// version before reading the profiles.
+pub fn parse_voice_export(json: &str) -> Result<BrandVoiceExport, AppError> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| AppError::Validation(format!("Brand voice file is not valid JSON: {}", e)))?;
+
+    if value.get("format").and_then(|f| f.as_str()) != Some(VOICE_EXPORT_FORMAT) {
+        return Err(AppError::Validation(
+            "File is not a ContentEngine brand voice export".to_string(),
+        ));
+    }
+
+    let schema_version = value
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if schema_version == 0 || schema_version > VOICE_EXPORT_SCHEMA_VERSION as u64 {
+        return Err(AppError::Validation(format!(
+            "Unsupported brand voice file version {} (supported: {})",
+            schema_version, VOICE_EXPORT_SCHEMA_VERSION
+        )));
+    }
+
+    let export: BrandVoiceExport = serde_json::from_value(value)
+        .map_err(|e| AppError::Validation(format!("Invalid brand voice file: {}", e)))?;
+
+    if export.profiles.is_empty() {
+        return Err(AppError::Validation(
+            "Brand voice file contains no profiles".to_string(),
+        ));
+    }
+
+    for profile in &export.profiles {
+        if profile.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Brand voice file contains a profile without a name".to_string(),
+            ));
+        }
+        validate_samples(&profile.samples)?;
+    }
+
+    Ok(export)
+}
+
+/// Returns `name` if unused, otherwise the first free "name (imported N)" variant.
+pub fn unique_voice_name(name: &str, existing: &[String]) -> String {
+    let taken = |candidate: &str| existing.iter().any(|n| n.eq_ignore_ascii_case(candidate));
+    if !taken(name) {
+        return name.to_string();
+    }
+
+    let mut n = 1;
+    loop {
+        let candidate = if n == 1 {
+            format!("{} (imported)", name)
+        } else {
+            format!("{} (imported {})", name, n)
+        };
+        if !taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
 }
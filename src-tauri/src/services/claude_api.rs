@@ -2,9 +2,12 @@ use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 
 use crate::errors::AppError;
-use crate::models::brand_voice::StyleAttributes;
+use crate::models::brand_voice::{
+    EmojiPolicy, StyleAttributes, Stylometrics, VoiceGuide, VoiceJudgement,
+};
 use crate::models::content::KeyPoints;
 use crate::models::platform::{LengthPreset, OutputFormat, PlatformConfig, TonePreset};
+use crate::services::brand_voice;
 
 #[derive(Debug, Serialize)]
 struct ClaudeRequest {
@@ -156,10 +159,11 @@ Be thorough but concise. Extract 3-7 key arguments and any supporting data point
         &self,
         api_key: &str,
         draft: &str,
-        style: &StyleAttributes,
+        voice: &VoiceGuide,
         format: &OutputFormat,
     ) -> Result<String, AppError> {
-        let system = format!(
+        let (style, emoji_policy) = brand_voice::style_for_format(&voice.style, format);
+        let mut system = format!(
             r#"You are a brand voice specialist. Your task is to refine the provided draft content to match a specific brand voice while preserving the content's message and format.
 
 Brand Voice Profile:
@@ -188,9 +192,88 @@ Rules:
             format
         );
 
+        if let Some(policy) = emoji_policy {
+            system.push_str(match policy {
+                EmojiPolicy::NoEmoji => "\n\nEmoji: use no emojis in this format, and remove any in the draft.",
+                EmojiPolicy::Sparing => "\n\nEmoji: at most one or two emojis in this format, only where they add meaning.",
+                EmojiPolicy::Liberal => "\n\nEmoji: use emojis freely in this format to add energy.",
+            });
+        }
+
+        if let Some(ref stylometrics) = voice.stylometrics {
+            system.push_str(&stylometric_targets(stylometrics));
+        }
+
         self.call_claude(api_key, &system, draft, 2048, 0.7).await
     }
 
+    pub async fn remove_banned_phrases(
+        &self,
+        api_key: &str,
+        text: &str,
+        phrases: &[String],
+        format: &OutputFormat,
+    ) -> Result<String, AppError> {
+        let system = format!(
+            r#"You are a copy editor. The provided content contains words or phrases that are not allowed. Rewrite only the sentences that contain them so that none of these phrases (or any form of them, such as plurals or other tenses) remain.
+
+Banned phrases: {}
+
+Content Format: {}
+
+Rules:
+1. Keep everything else exactly as it is, including the format structure
+2. Do not introduce any of the banned phrases in another form
+3. Return ONLY the corrected content, no explanations or meta-commentary"#,
+            phrases.join(", "),
+            format
+        );
+
+        self.call_claude(api_key, &system, text, 2048, 0.3).await
+    }
+
+    pub async fn judge_voice(
+        &self,
+        api_key: &str,
+        text: &str,
+        style: &StyleAttributes,
+    ) -> Result<VoiceJudgement, AppError> {
+        let system = format!(
+            r#"You are a brand voice editor. Rate how well the provided content matches the brand voice below.
+
+Brand Voice Profile:
+- Tone: {}
+- Vocabulary Level: {}
+- Sentence Style: {}
+- Personality Traits: {}
+- Signature Phrases: {}
+- Phrases to avoid: {}
+
+Return ONLY valid JSON with no additional text, markdown formatting, or code blocks. The JSON must match this exact structure:
+{{
+    "score": 0-100 (100 means indistinguishable from the brand voice),
+    "rationale": "One or two sentences explaining the score"
+}}"#,
+            style.tone,
+            style.vocabulary_level,
+            style.sentence_style,
+            style.personality_traits.join(", "),
+            style.signature_phrases.join(", "),
+            style.avoid_phrases.join(", "),
+        );
+
+        let response = self.call_claude(api_key, &system, text, 512, 0.0).await?;
+
+        let judgement: VoiceJudgement = serde_json::from_str(&response).map_err(|e| {
+            AppError::ClaudeApi(format!(
+                "Failed to parse voice judgement JSON: {}. Raw response: {}",
+                e, response
+            ))
+        })?;
+
+        Ok(judgement)
+    }
+
     pub async fn repurpose(
         &self,
         api_key: &str,
@@ -198,9 +281,9 @@ Rules:
         formats: &[OutputFormat],
         tone: &TonePreset,
         length: &LengthPreset,
-        voice: Option<&StyleAttributes>,
+        voice: Option<&VoiceGuide>,
         config: &PlatformConfig,
-    ) -> Result<Vec<(OutputFormat, String)>, AppError> {
+    ) -> Result<(KeyPoints, Vec<(OutputFormat, String)>), AppError> {
         // Stage 1: Extract key points
         let key_points = self.extract_key_points(api_key, content).await?;
         let key_points_json = serde_json::to_string(&key_points)?;
@@ -234,15 +317,15 @@ Rules:
         }
 
         // Stage 3: Refine with brand voice if provided
-        if let Some(style) = voice {
+        if let Some(voice) = voice {
             let refinement_futures: Vec<_> = drafts
                 .into_iter()
                 .map(|(fmt, draft)| {
-                    let s = style.clone();
+                    let v = voice.clone();
                     let f = fmt.clone();
                     let key = api_key.to_string();
                     async move {
-                        let result = self.refine_with_voice(&key, &draft, &s, &f).await;
+                        let result = self.refine_with_voice(&key, &draft, &v, &f).await;
                         (fmt, result)
                     }
                 })
@@ -255,9 +338,9 @@ Rules:
                 let text = result?;
                 final_outputs.push((fmt, text));
             }
-            Ok(final_outputs)
+            Ok((key_points, final_outputs))
         } else {
-            Ok(drafts)
+            Ok((key_points, drafts))
         }
     }
 
@@ -539,3 +622,46 @@ Rules:
         )
     }
 }
+
+/// Measured habits from the voice's samples, phrased as concrete targets for
+/// the refinement prompt.
+fn stylometric_targets(stylometrics: &Stylometrics) -> String {
+    let mut targets = format!(
+        "\n\nMeasured targets from the brand's own writing:\n\
+         - Average sentence length: about {:.0} words (typical spread ±{:.0})\n\
+         - Average paragraph length: about {:.0} words\n\
+         - Reading grade level: about {:.1}\n\
+         - Exclamation marks: {:.1} per 1000 words; questions: {:.1} per 1000 words\n\
+         - Emoji: {:.1} per 1000 words",
+        stylometrics.avg_sentence_length,
+        stylometrics.sentence_length_sd,
+        stylometrics.avg_paragraph_length,
+        stylometrics.readability_grade,
+        stylometrics.punctuation.exclamations,
+        stylometrics.punctuation.questions,
+        stylometrics.emoji_per_1000_words,
+    );
+
+    if !stylometrics.top_emojis.is_empty() {
+        targets.push_str(&format!(" (favourites: {})", stylometrics.top_emojis.join(" ")));
+    }
+
+    if let Some(share) = stylometrics.first_person_share {
+        targets.push_str(&format!(
+            "\n- Point of view: {:.0}% of personal pronouns are first person (I/we), the rest second person (you)",
+            share * 100.0
+        ));
+    }
+
+    if !stylometrics.frequent_ngrams.is_empty() {
+        let ngrams: Vec<&str> = stylometrics
+            .frequent_ngrams
+            .iter()
+            .map(|n| n.ngram.as_str())
+            .collect();
+        targets.push_str(&format!("\n- Recurring expressions: {}", ngrams.join(", ")));
+    }
+
+    targets.push_str("\nStay close to these numbers where the format allows.");
+    targets
+}
@@ -0,0 +1,364 @@
+use reqwest::Url;
+use scraper::{Html, Selector};
+
+use crate::errors::{AppError, UrlFetchError};
+use crate::models::content::CrawlPageIssue;
+use crate::services::url_fetcher::{self, FetchPolicy};
+
+const MAX_NESTED_SITEMAPS: usize = 20;
+const ROBOTS_CONTENT_TYPES: &[&str] = &["text/plain"];
+const SITEMAP_CONTENT_TYPES: &[&str] = &["application/xml", "text/xml", "text/plain"];
+
+/// Disallow/Allow rules from robots.txt that apply to our user agent.
+#[derive(Debug, Clone, Default)]
+pub struct RobotsRules {
+    allow: Vec<String>,
+    disallow: Vec<String>,
+    pub crawl_delay_ms: Option<u64>,
+}
+
+impl RobotsRules {
+    /// Parses robots.txt, using the group for `ContentEngine` if present,
+    /// otherwise the `*` group.
+    pub fn parse(robots_txt: &str) -> Self {
+        let mut specific = RobotsRules::default();
+        let mut wildcard = RobotsRules::default();
+        let mut has_specific = false;
+
+        // Agents named by the current group; a new group starts when a
+        // User-agent line follows a rule line.
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in robots_txt.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let field = field.trim().to_lowercase();
+            let value = value.trim();
+
+            if field == "user-agent" {
+                if in_rules {
+                    group_agents.clear();
+                    in_rules = false;
+                }
+                group_agents.push(value.to_lowercase());
+                continue;
+            }
+
+            in_rules = true;
+            let applies_specific = group_agents.iter().any(|a| a == "contentengine");
+            let applies_wildcard = group_agents.iter().any(|a| a == "*");
+            if applies_specific {
+                has_specific = true;
+            }
+
+            for (applies, rules) in [
+                (applies_specific, &mut specific),
+                (applies_wildcard, &mut wildcard),
+            ] {
+                if !applies {
+                    continue;
+                }
+                match field.as_str() {
+                    "allow" if !value.is_empty() => rules.allow.push(value.to_string()),
+                    "disallow" if !value.is_empty() => rules.disallow.push(value.to_string()),
+                    "crawl-delay" => {
+                        if let Ok(secs) = value.parse::<f64>() {
+                            rules.crawl_delay_ms = Some((secs * 1000.0) as u64);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if has_specific {
+            specific
+        } else {
+            wildcard
+        }
+    }
+
+    /// Longest matching rule wins; Allow wins ties.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        let longest = |rules: &[String]| {
+            rules
+                .iter()
+                .filter(|rule| robots_rule_matches(rule, path))
+                .map(|rule| rule.len())
+                .max()
+        };
+
+        match (longest(&self.allow), longest(&self.disallow)) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(allow), Some(disallow)) => allow >= disallow,
+        }
+    }
+}
+
+fn robots_rule_matches(rule: &str, path: &str) -> bool {
+    match rule.strip_suffix('$') {
+        Some(exact) => glob_matches(exact, path),
+        None => glob_matches(&format!("{}*", rule), path),
+    }
+}
+
+/// Matches `text` against a pattern where `*` stands for any run of characters.
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let first = parts[0];
+    let last = parts[parts.len() - 1];
+    if !text.starts_with(first) || text.len() < first.len() + last.len() {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+/// Fetches robots.txt for the URL's origin. A missing or unreadable file
+/// means everything is allowed.
+pub async fn fetch_robots(url: &Url, policy: &FetchPolicy) -> RobotsRules {
+    let Ok(robots_url) = url.join("/robots.txt") else {
+        return RobotsRules::default();
+    };
+
+    match url_fetcher::fetch_text(robots_url.as_str(), policy, ROBOTS_CONTENT_TYPES).await {
+        Ok(body) => RobotsRules::parse(&body),
+        Err(_) => RobotsRules::default(),
+    }
+}
+
+/// Collects page URLs from a sitemap, following one level of sitemap index.
+/// Nested sitemaps that cannot be fetched are returned as failures instead of
+/// stopping the crawl.
+pub async fn urls_from_sitemap(
+    sitemap_url: &str,
+    policy: &FetchPolicy,
+) -> Result<(Vec<Url>, Vec<CrawlPageIssue>), AppError> {
+    let xml = url_fetcher::fetch_text(sitemap_url, policy, SITEMAP_CONTENT_TYPES).await?;
+    let (nested, mut pages) = parse_sitemap(&xml);
+    let mut failed = Vec::new();
+
+    for nested_url in nested.into_iter().take(MAX_NESTED_SITEMAPS) {
+        match url_fetcher::fetch_text(nested_url.as_str(), policy, SITEMAP_CONTENT_TYPES).await {
+            Ok(nested_xml) => pages.extend(parse_sitemap(&nested_xml).1),
+            Err(e) => failed.push(CrawlPageIssue {
+                url: nested_url.to_string(),
+                reason: format!("Nested sitemap could not be read: {}", e),
+            }),
+        }
+    }
+
+    dedup_urls(&mut pages);
+
+    if pages.is_empty() {
+        let reason = match failed.first() {
+            Some(issue) => format!("No page URLs found in sitemap ({})", issue.reason),
+            None => "No page URLs found in sitemap".to_string(),
+        };
+        return Err(UrlFetchError::Request(reason).into());
+    }
+
+    Ok((pages, failed))
+}
+
+/// Returns `(nested sitemaps, page urls)` found in a sitemap document.
+fn parse_sitemap(xml: &str) -> (Vec<Url>, Vec<Url>) {
+    let document = Html::parse_document(xml);
+
+    let collect = |selector: &str| -> Vec<Url> {
+        let Ok(selector) = Selector::parse(selector) else {
+            return Vec::new();
+        };
+        document
+            .select(&selector)
+            .filter_map(|el| Url::parse(el.text().collect::<String>().trim()).ok())
+            .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+            .collect()
+    };
+
+    (collect("sitemap > loc"), collect("url > loc"))
+}
+
+/// Collects same-domain links from an index page whose path matches `pattern`.
+pub async fn urls_from_index(
+    index_url: &str,
+    pattern: &str,
+    policy: &FetchPolicy,
+) -> Result<Vec<Url>, AppError> {
+    let base = Url::parse(index_url)
+        .map_err(|e| AppError::Validation(format!("Invalid index URL: {}", e)))?;
+    let html = url_fetcher::fetch_text(index_url, policy, url_fetcher::PAGE_CONTENT_TYPES).await?;
+
+    let pages = matching_links(&html, &base, pattern);
+    if pages.is_empty() {
+        return Err(UrlFetchError::Request(format!(
+            "No links matching '{}' found on index page",
+            pattern
+        ))
+        .into());
+    }
+
+    Ok(pages)
+}
+
+/// Links on the same host as `base`, other than `base` itself, whose path
+/// matches `pattern`.
+fn matching_links(html: &str, base: &Url, pattern: &str) -> Vec<Url> {
+    let mut pages = extract_links(html, base)
+        .into_iter()
+        .filter(|url| url.host_str() == base.host_str())
+        .filter(|url| glob_matches(pattern, url.path()))
+        .filter(|url| url.as_str() != base.as_str())
+        .collect::<Vec<_>>();
+
+    dedup_urls(&mut pages);
+    pages
+}
+
+fn extract_links(html: &str, base: &Url) -> Vec<Url> {
+    let document = Html::parse_document(html);
+    let Ok(selector) = Selector::parse("a[href]") else {
+        return Vec::new();
+    };
+
+    document
+        .select(&selector)
+        .filter_map(|el| el.value().attr("href"))
+        .filter_map(|href| base.join(href.trim()).ok())
+        .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+        .map(|mut url| {
+            url.set_fragment(None);
+            url
+        })
+        .collect()
+}
+
+fn dedup_urls(urls: &mut Vec<Url>) {
+    let mut seen = std::collections::HashSet::new();
+    urls.retain(|url| seen.insert(url.as_str().to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn prefers_the_contentengine_group_over_the_wildcard() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\n\n\
+             User-agent: Googlebot\nUser-agent: ContentEngine\nDisallow: /private # team only\nCrawl-delay: 1.5\n",
+        );
+        assert!(rules.is_allowed("/blog/post"));
+        assert!(!rules.is_allowed("/private/notes"));
+        assert_eq!(rules.crawl_delay_ms, Some(1500));
+
+        let wildcard = RobotsRules::parse("User-agent: *\nDisallow: /admin\n");
+        assert!(!wildcard.is_allowed("/admin/users"));
+        assert!(wildcard.is_allowed("/blog"));
+        assert!(RobotsRules::parse("").is_allowed("/anything"));
+    }
+
+    #[test]
+    fn longest_robots_rule_wins_and_allow_wins_ties() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /blog\nAllow: /blog/public\nDisallow: /*.pdf$\nAllow: /same\nDisallow: /same\n",
+        );
+        assert!(!rules.is_allowed("/blog/draft"));
+        assert!(rules.is_allowed("/blog/public/post"));
+        assert!(!rules.is_allowed("/files/report.pdf"));
+        assert!(rules.is_allowed("/files/report.pdf?download=1"));
+        assert!(rules.is_allowed("/same"));
+    }
+
+    #[test]
+    fn matches_globs() {
+        assert!(glob_matches("/blog/*", "/blog/post-1"));
+        assert!(glob_matches("/*/2024/*", "/news/2024/launch"));
+        assert!(!glob_matches("/blog/*", "/about"));
+        assert!(!glob_matches("/a*a", "/a"));
+        assert!(glob_matches("/exact", "/exact"));
+        assert!(!glob_matches("/exact", "/exact/more"));
+    }
+
+    #[test]
+    fn keeps_same_domain_links_matching_the_pattern() {
+        let base = Url::parse("https://example.com/blog/").unwrap();
+        let html = r#"
+            <a href="/blog/first-post">First</a>
+            <a href="second-post#comments">Second</a>
+            <a href="https://example.com/blog/first-post">First again</a>
+            <a href="https://other.com/blog/elsewhere">Other site</a>
+            <a href="/about">About</a>
+            <a href="/blog/">Index itself</a>
+            <a href="mailto:hi@example.com">Mail</a>
+        "#;
+
+        let links: Vec<String> = matching_links(html, &base, "/blog/*")
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            links,
+            [
+                "https://example.com/blog/first-post",
+                "https://example.com/blog/second-post"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_a_broken_nested_sitemap_and_keeps_the_rest() {
+        let server = MockServer::start().await;
+        let index = format!(
+            "<sitemapindex><sitemap><loc>{0}/good.xml</loc></sitemap>\
+             <sitemap><loc>{0}/broken.xml</loc></sitemap></sitemapindex>",
+            server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path("/sitemap.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(index, "application/xml"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/good.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<urlset><url><loc>https://example.com/a</loc></url></urlset>",
+                "application/xml",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/broken.xml"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let policy = FetchPolicy::from_allowlist(&["127.0.0.1".to_string()]).unwrap();
+        let (pages, failed) = urls_from_sitemap(&format!("{}/sitemap.xml", server.uri()), &policy)
+            .await
+            .unwrap();
+
+        assert_eq!(pages, [Url::parse("https://example.com/a").unwrap()]);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].url, format!("{}/broken.xml", server.uri()));
+        assert!(failed[0].reason.contains("500"), "{}", failed[0].reason);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::errors::AppError;
+use crate::models::content::{JsonExport, JsonExportItem, RepurposedOutput};
+use crate::services::output_text;
+use crate::services::schedule::ScheduledTimes;
+
+/// Bumped whenever the shape of `JsonExport` changes incompatibly.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+const CSV_COLUMNS: [&str; 10] = [
+    "content_input_id",
+    "title",
+    "output_id",
+    "format",
+    "part",
+    "subject",
+    "send_timing",
+    "text",
+    "char_count",
+    "scheduled_at",
+];
+
+pub fn build_json(items: Vec<JsonExportItem>) -> Result<String, AppError> {
+    let export = JsonExport {
+        schema_version: JSON_SCHEMA_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        items,
+    };
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+/// One row per output, or with `split_parts` one row per tweet of a thread and
+/// per email of a sequence. A part without its own slot takes the whole
+/// output's scheduled time. Rows end in CRLF as RFC 4180 expects.
+pub fn build_csv(items: &[JsonExportItem], split_parts: bool, scheduled: &ScheduledTimes) -> String {
+    let mut csv = csv_row(CSV_COLUMNS.iter().map(|c| c.to_string()));
+
+    for item in items {
+        let title = item.input.title.clone().unwrap_or_default();
+        for output in &item.outputs {
+            for part in output_parts(output, split_parts) {
+                let scheduled_at = part
+                    .number
+                    .and_then(|n| scheduled.get(&(output.id.clone(), Some(n))))
+                    .or_else(|| scheduled.get(&(output.id.clone(), None)))
+                    .cloned()
+                    .unwrap_or_default();
+                csv.push_str(&csv_row([
+                    item.input.id.clone(),
+                    title.clone(),
+                    output.id.clone(),
+                    output.format.clone(),
+                    part.number.map(|n| n.to_string()).unwrap_or_default(),
+                    part.subject.unwrap_or_default(),
+                    part.send_timing.unwrap_or_default(),
+                    part.text.clone(),
+                    part.text.chars().count().to_string(),
+                    scheduled_at,
+                ]));
+            }
+        }
+    }
+
+    csv
+}
+
+struct Part {
+    number: Option<u32>,
+    subject: Option<String>,
+    send_timing: Option<String>,
+    text: String,
+}
+
+fn output_parts(output: &RepurposedOutput, split_parts: bool) -> Vec<Part> {
+    match output.format.as_str() {
+        "twitter_thread" if split_parts => output_text::split_tweets(&output.output_text)
+            .into_iter()
+            .enumerate()
+            .map(|(i, tweet)| Part {
+                number: Some(i as u32 + 1),
+                subject: None,
+                send_timing: None,
+                text: tweet,
+            })
+            .collect(),
+        "email_sequence" if split_parts => output_text::parse_email_sequence(&output.output_text)
+            .into_iter()
+            .map(|email| Part {
+                number: Some(email.number),
+                subject: email.subject,
+                send_timing: email.send_timing,
+                text: email.body,
+            })
+            .collect(),
+        "newsletter" => {
+            let newsletter = output_text::parse_newsletter(&output.output_text);
+            vec![Part {
+                number: None,
+                subject: newsletter.subject,
+                send_timing: None,
+                text: output.output_text.trim().to_string(),
+            }]
+        }
+        _ => vec![Part {
+            number: None,
+            subject: None,
+            send_timing: None,
+            text: output.output_text.trim().to_string(),
+        }],
+    }
+}
+
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    let mut row = fields
+        .into_iter()
+        .map(|field| csv_field(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// Quotes a field if it contains a delimiter, quote or line break. A field
+/// a spreadsheet would read as a formula gets a leading `'` so it stays text.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quotes_delimiters() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn csv_field_neutralizes_formulas() {
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("+1 555"), "'+1 555");
+        assert_eq!(csv_field("-2"), "'-2");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+}
@@ -0,0 +1,309 @@
+use reqwest::Url;
+use rusqlite::params;
+use sha2::{Digest, Sha256};
+
+use crate::errors::AppError;
+use crate::models::content::{DuplicateCheck, DuplicateMatch};
+use crate::services::workspace;
+
+/// Inputs at or above this estimated Jaccard similarity are reported as near-duplicates.
+pub const NEAR_DUPLICATE_THRESHOLD: f64 = 0.9;
+
+const SHINGLE_SIZE: usize = 5;
+const MINHASH_PERMUTATIONS: usize = 128;
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "mc_cid", "mc_eid", "ref"];
+
+/// SHA-256 of the text with case folded and whitespace collapsed.
+pub fn content_hash(text: &str) -> String {
+    let normalized = text
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Normalises a URL so trivially different links to the same page compare equal:
+/// lowercase host, no fragment, default port, trailing slash or tracking parameters.
+pub fn canonicalize_url(url: &str) -> Option<String> {
+    let mut parsed = Url::parse(url.trim()).ok()?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return None;
+    }
+
+    parsed.set_fragment(None);
+    if parsed.port() == parsed.port_or_known_default() {
+        let _ = parsed.set_port(None);
+    }
+
+    let query: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if query.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(if path.is_empty() { "/" } else { &path });
+
+    // http and https variants of the same page share a key
+    let canonical = parsed.as_str().to_string();
+    Some(
+        canonical
+            .strip_prefix("https://")
+            .or_else(|| canonical.strip_prefix("http://"))
+            .unwrap_or(&canonical)
+            .to_string(),
+    )
+}
+
+/// MinHash signature over word shingles, serialised as little-endian u64s.
+pub fn minhash_signature(text: &str) -> Vec<u8> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let shingles: Vec<u64> = if words.len() <= SHINGLE_SIZE {
+        vec![fnv1a(&words.join(" "))]
+    } else {
+        words
+            .windows(SHINGLE_SIZE)
+            .map(|window| fnv1a(&window.join(" ")))
+            .collect()
+    };
+
+    (0..MINHASH_PERMUTATIONS as u64)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|shingle| splitmix64(shingle ^ seed.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+/// Estimated Jaccard similarity of two signatures from [`minhash_signature`].
+pub fn signature_similarity(a: &[u8], b: &[u8]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let slots = a.len() / 8;
+    let equal = a
+        .chunks_exact(8)
+        .zip(b.chunks_exact(8))
+        .filter(|(x, y)| x == y)
+        .count();
+    equal as f64 / slots as f64
+}
+
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Looks for inputs in the active workspace with the same content hash or
+/// canonical URL, and for near-duplicates by MinHash similarity.
+pub fn find_duplicates(
+    conn: &rusqlite::Connection,
+    text: &str,
+    source_url: Option<&str>,
+) -> Result<DuplicateCheck, AppError> {
+    let hash = content_hash(text);
+    let canonical = source_url.and_then(canonicalize_url);
+    let signature = minhash_signature(text);
+
+    let mut exact: Option<DuplicateMatch> = None;
+    let mut near: Vec<DuplicateMatch> = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, created_at, content_hash, canonical_url, minhash_signature \
+         FROM content_inputs WHERE workspace_id = ?1 ORDER BY created_at DESC",
+    )?;
+    let rows = stmt.query_map(params![workspace::active_workspace_id(conn)], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, Option<Vec<u8>>>(5)?,
+        ))
+    })?;
+
+    for (id, title, created_at, row_hash, row_canonical, row_signature) in
+        rows.filter_map(|r| r.ok())
+    {
+        let reason = if row_hash.as_deref() == Some(hash.as_str()) {
+            Some(("content_hash", 1.0))
+        } else if canonical.is_some() && row_canonical == canonical {
+            Some(("canonical_url", 1.0))
+        } else {
+            let similarity = row_signature
+                .map(|sig| signature_similarity(&signature, &sig))
+                .unwrap_or(0.0);
+            (similarity >= NEAR_DUPLICATE_THRESHOLD).then_some(("similar_text", similarity))
+        };
+
+        let Some((reason, similarity)) = reason else {
+            continue;
+        };
+
+        let found = DuplicateMatch {
+            content_input_id: id,
+            title,
+            created_at,
+            similarity,
+            reason: reason.to_string(),
+        };
+
+        if reason == "similar_text" || exact.is_some() {
+            near.push(found);
+        } else {
+            exact = Some(found);
+        }
+    }
+
+    near.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    Ok(DuplicateCheck { exact, near })
+}
+
+/// The strongest existing match for new input, if any: an exact match first,
+/// otherwise the most similar near-duplicate.
+pub fn closest_match(
+    conn: &rusqlite::Connection,
+    text: &str,
+    source_url: Option<&str>,
+) -> Result<Option<DuplicateMatch>, AppError> {
+    let check = find_duplicates(conn, text, source_url)?;
+    Ok(check.exact.or_else(|| check.near.into_iter().next()))
+}
+
+/// Fills in hash, canonical URL and signature for inputs stored before these
+/// columns existed.
+pub fn backfill_fingerprints(conn: &rusqlite::Connection) -> Result<(), AppError> {
+    let pending: Vec<(String, String, Option<String>)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, raw_text, source_url FROM content_inputs WHERE content_hash IS NULL",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    for (id, text, source_url) in pending {
+        conn.execute(
+            "UPDATE content_inputs SET content_hash = ?1, canonical_url = ?2, minhash_signature = ?3 WHERE id = ?4",
+            params![
+                content_hash(&text),
+                source_url.as_deref().and_then(canonicalize_url),
+                minhash_signature(&text),
+                id
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::content::insert_content_input;
+
+    /// A few hundred distinct words, so one edit changes only a handful of shingles.
+    fn article(words: usize) -> Vec<String> {
+        (0..words).map(|i| format!("word{}", i)).collect()
+    }
+
+    fn with_replaced(words: &[String], every: usize) -> String {
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i % every == 0 { format!("changed{}", i) } else { w.clone() })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn setup() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::db::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn folds_case_and_whitespace_in_hashes_and_urls() {
+        assert_eq!(content_hash("Hello   World\n"), content_hash("hello world"));
+        assert_eq!(
+            canonicalize_url("https://Example.com:443/post/?utm_source=x&id=2#top"),
+            canonicalize_url("http://example.com/post?id=2")
+        );
+    }
+
+    #[test]
+    fn warns_at_ninety_percent_similarity() {
+        let conn = setup();
+        let words = article(400);
+        let original = insert_content_input(&conn, "default", None, words.join(" "), None).unwrap();
+
+        // Two of 400 words changed: about 95% of shingles are shared
+        let close = with_replaced(&words, 200);
+        let found = closest_match(&conn, &close, None).unwrap().expect("near-duplicate");
+        assert_eq!(found.content_input_id, original.id);
+        assert_eq!(found.reason, "similar_text");
+        assert!(found.similarity >= NEAR_DUPLICATE_THRESHOLD, "{}", found.similarity);
+
+        // One word in 10 changed: only about a third of shingles are shared
+        let loose = with_replaced(&words, 10);
+        let similarity = signature_similarity(&minhash_signature(&loose), &minhash_signature(&words.join(" ")));
+        assert!(similarity < NEAR_DUPLICATE_THRESHOLD, "{}", similarity);
+        assert!(closest_match(&conn, &loose, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn prefers_an_exact_match() {
+        let conn = setup();
+        let words = article(100);
+        insert_content_input(&conn, "default", None, with_replaced(&words, 50), None).unwrap();
+        let exact = insert_content_input(
+            &conn,
+            "default",
+            Some("https://example.com/a".to_string()),
+            words.join(" "),
+            None,
+        )
+        .unwrap();
+
+        let found = closest_match(&conn, &words.join("  ").to_uppercase(), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.content_input_id, exact.id);
+        assert_eq!(found.reason, "content_hash");
+
+        let by_url = closest_match(&conn, "different text", Some("https://example.com/a/?utm_medium=x"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_url.reason, "canonical_url");
+    }
+}
@@ -0,0 +1,444 @@
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::errors::AppError;
+use crate::models::content::{HistoryDetail, RepurposedOutput};
+use crate::services::output_text::{self, format_display_name};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+<Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
+<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+<Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
+</Types>"#;
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
+</Relationships>"#;
+
+const DOCUMENT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>
+</Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:eastAsia="Calibri" w:cs="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="160" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults>
+<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>
+<w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:before="2400" w:after="480"/></w:pPr><w:rPr><w:b/><w:sz w:val="56"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Subtitle"><w:name w:val="Subtitle"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:rPr><w:color w:val="595959"/><w:sz w:val="24"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="240"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="28"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="ListNumber"><w:name w:val="List Number"/><w:basedOn w:val="Normal"/><w:qFormat/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:style>
+</w:styles>"#;
+
+/// Builds a .docx package: for each content input a title page with the
+/// source metadata, then one section per output starting on a new page.
+pub fn build_docx(details: &[HistoryDetail]) -> Result<Vec<u8>, AppError> {
+    let mut body = String::new();
+    let mut thread_count = 0;
+
+    for (i, detail) in details.iter().enumerate() {
+        if i > 0 {
+            body.push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#);
+        }
+        body.push_str(&title_page(detail));
+
+        for output in &detail.outputs {
+            // Heading1 paragraphs carry a page break, so each format starts a page
+            body.push_str(&paragraph(
+                Some("Heading1"),
+                &[Run::plain(&format_display_name(&output.format))],
+            ));
+            if output.format == "twitter_thread" {
+                thread_count += 1;
+            }
+            body.push_str(&render_output(output, thread_count));
+        }
+    }
+
+    let document = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body
+    );
+
+    let files: [(&str, String); 8] = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", PACKAGE_RELS.to_string()),
+        ("word/document.xml", document),
+        ("word/_rels/document.xml.rels", DOCUMENT_RELS.to_string()),
+        ("word/styles.xml", STYLES.to_string()),
+        ("word/numbering.xml", numbering(thread_count)),
+        ("docProps/core.xml", core_properties(details)),
+        ("docProps/app.xml", app_properties()),
+    ];
+
+    let zip_error = |e: zip::result::ZipError| AppError::FileIo(format!("Failed to build DOCX: {}", e));
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(name, options).map_err(zip_error)?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| AppError::FileIo(format!("Failed to build DOCX: {}", e)))?;
+    }
+
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+pub fn export_to_docx(details: &[HistoryDetail], output_path: &str) -> Result<String, AppError> {
+    let bytes = build_docx(details)?;
+    std::fs::write(output_path, bytes)
+        .map_err(|e| AppError::FileIo(format!("Failed to write DOCX file: {}", e)))?;
+    Ok(output_path.to_string())
+}
+
+fn title_page(detail: &HistoryDetail) -> String {
+    let mut xml = paragraph(Some("Title"), &[Run::plain(document_title(detail))]);
+    if let Some(ref url) = detail.input.source_url {
+        xml.push_str(&paragraph(Some("Subtitle"), &[Run::plain(&format!("Source: {}", url))]));
+    }
+    xml.push_str(&paragraph(
+        Some("Subtitle"),
+        &[Run::plain(&format!("Created: {}", detail.input.created_at))],
+    ));
+    xml.push_str(&paragraph(
+        Some("Subtitle"),
+        &[Run::plain(&format!("Word count: {}", detail.input.word_count))],
+    ));
+    let formats: Vec<String> = detail
+        .outputs
+        .iter()
+        .map(|o| format_display_name(&o.format))
+        .collect();
+    xml.push_str(&paragraph(
+        Some("Subtitle"),
+        &[Run::plain(&format!("Formats: {}", formats.join(", ")))],
+    ));
+    xml
+}
+
+fn document_title(detail: &HistoryDetail) -> &str {
+    detail.input.title.as_deref().unwrap_or("Untitled Content")
+}
+
+struct Run<'a> {
+    text: &'a str,
+    bold: bool,
+    italic: bool,
+}
+
+impl<'a> Run<'a> {
+    fn plain(text: &'a str) -> Self {
+        Self { text, bold: false, italic: false }
+    }
+
+    fn bold(text: &'a str) -> Self {
+        Self { text, bold: true, italic: false }
+    }
+
+    fn italic(text: &'a str) -> Self {
+        Self { text, bold: false, italic: true }
+    }
+}
+
+/// `thread_number` picks the numbering instance, so each thread restarts at 1.
+fn render_output(output: &RepurposedOutput, thread_number: usize) -> String {
+    let mut xml = String::new();
+
+    match output.format.as_str() {
+        "twitter_thread" => {
+            for tweet in output_text::split_tweets(&output.output_text) {
+                xml.push_str(&format!(
+                    r#"<w:p><w:pPr><w:pStyle w:val="ListNumber"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="{}"/></w:numPr></w:pPr>{}</w:p>"#,
+                    thread_number,
+                    run(&Run::plain(&tweet))
+                ));
+            }
+        }
+        "email_sequence" => {
+            for email in output_text::parse_email_sequence(&output.output_text) {
+                let heading = match email.label {
+                    Some(ref label) => format!("Email {}: {}", email.number, label),
+                    None => format!("Email {}", email.number),
+                };
+                xml.push_str(&paragraph(Some("Heading2"), &[Run::plain(&heading)]));
+                if let Some(ref subject) = email.subject {
+                    xml.push_str(&paragraph(None, &[Run::bold(&format!("Subject: {}", subject))]));
+                }
+                if let Some(ref preview) = email.preview {
+                    xml.push_str(&paragraph(None, &[Run::italic(&format!("Preview: {}", preview))]));
+                }
+                if let Some(ref timing) = email.send_timing {
+                    xml.push_str(&paragraph(None, &[Run::italic(&format!("Send timing: {}", timing))]));
+                }
+                xml.push_str(&text_paragraphs(&email.body));
+            }
+        }
+        "newsletter" => {
+            let newsletter = output_text::parse_newsletter(&output.output_text);
+            if let Some(ref subject) = newsletter.subject {
+                xml.push_str(&paragraph(None, &[Run::bold(&format!("Subject: {}", subject))]));
+            }
+            if let Some(ref preview) = newsletter.preview {
+                xml.push_str(&paragraph(None, &[Run::italic(&format!("Preview: {}", preview))]));
+            }
+            xml.push_str(&text_paragraphs(&newsletter.body));
+        }
+        _ => xml.push_str(&text_paragraphs(&output.output_text)),
+    }
+
+    xml
+}
+
+/// One paragraph per blank-line separated block, with line breaks inside.
+fn text_paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| paragraph(None, &[Run::plain(block)]))
+        .collect()
+}
+
+fn paragraph(style: Option<&str>, runs: &[Run]) -> String {
+    let properties = match style {
+        Some("Heading1") => r#"<w:pPr><w:pStyle w:val="Heading1"/><w:pageBreakBefore/></w:pPr>"#.to_string(),
+        Some(style) => format!(r#"<w:pPr><w:pStyle w:val="{}"/></w:pPr>"#, style),
+        None => String::new(),
+    };
+    let runs: String = runs.iter().map(run).collect();
+    format!("<w:p>{}{}</w:p>", properties, runs)
+}
+
+/// A run, with newlines turned into line breaks.
+fn run(run: &Run) -> String {
+    let mut properties = String::new();
+    if run.bold {
+        properties.push_str("<w:b/>");
+    }
+    if run.italic {
+        properties.push_str("<w:i/>");
+    }
+    let properties = if properties.is_empty() {
+        properties
+    } else {
+        format!("<w:rPr>{}</w:rPr>", properties)
+    };
+
+    let text = run
+        .text
+        .lines()
+        .map(|line| format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape_xml(line)))
+        .collect::<Vec<_>>()
+        .join("<w:br/>");
+    format!("<w:r>{}{}</w:r>", properties, text)
+}
+
+/// A decimal list definition with one numbering instance per thread.
+fn numbering(thread_count: usize) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="singleLevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum>"#,
+    );
+    for num_id in 1..=thread_count {
+        xml.push_str(&format!(
+            r#"<w:num w:numId="{}"><w:abstractNumId w:val="0"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="1"/></w:lvlOverride></w:num>"#,
+            num_id
+        ));
+    }
+    xml.push_str("</w:numbering>");
+    xml
+}
+
+/// A single input's title and source; for several inputs a count as the
+/// title and the earliest creation date.
+fn core_properties(details: &[HistoryDetail]) -> String {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let created = details
+        .iter()
+        .filter_map(|d| w3c_datetime(&d.input.created_at))
+        .min()
+        .unwrap_or_else(|| now.clone());
+
+    let mut keywords: Vec<&str> = Vec::new();
+    for output in details.iter().flat_map(|d| &d.outputs) {
+        if !keywords.contains(&output.format.as_str()) {
+            keywords.push(&output.format);
+        }
+    }
+
+    let (title, source) = match details {
+        [detail] => (
+            document_title(detail).to_string(),
+            detail.input.source_url.as_deref().unwrap_or_default(),
+        ),
+        _ => (format!("{} content items", details.len()), ""),
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:dcmitype="http://purl.org/dc/dcmitype/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dc:subject>Repurposed content</dc:subject><dc:creator>Content Engine</dc:creator><cp:keywords>{}</cp:keywords><dc:description>{}</dc:description><dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created><dcterms:modified xsi:type="dcterms:W3CDTF">{}</dcterms:modified></cp:coreProperties>"#,
+        escape_xml(&title),
+        escape_xml(&keywords.join(", ")),
+        escape_xml(source),
+        created,
+        now
+    )
+}
+
+fn app_properties() -> String {
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties"><Application>Content Engine</Application></Properties>"#
+        .to_string()
+}
+
+/// RFC 3339 or SQLite datetimes as the W3CDTF form core properties expect.
+fn w3c_datetime(value: &str) -> Option<String> {
+    let parsed = chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.to_utc().naive_utc())
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .ok()?;
+    Some(parsed.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// Escapes markup characters and drops characters XML 1.0 does not allow.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::models::content::ContentInput;
+
+    fn output(format: &str, text: &str) -> RepurposedOutput {
+        RepurposedOutput {
+            id: format!("{}-id", format),
+            content_input_id: "input-id".to_string(),
+            format: format.to_string(),
+            output_text: text.to_string(),
+            created_at: "2026-01-02T03:04:05+00:00".to_string(),
+            voice_version_id: None,
+            voice_score: None,
+            phrase_flags: Vec::new(),
+            approved_at: None,
+            tone: Some("professional".to_string()),
+            length: Some("medium".to_string()),
+        }
+    }
+
+    fn detail() -> HistoryDetail {
+        HistoryDetail {
+            input: ContentInput {
+                id: "input-id".to_string(),
+                source_url: Some("https://example.com/post?a=1&b=2".to_string()),
+                raw_text: "Original text".to_string(),
+                title: Some("Shipping <fast> & safe".to_string()),
+                word_count: 1200,
+                created_at: "2026-01-02 03:04:05".to_string(),
+            },
+            outputs: vec![
+                output("twitter_thread", "1/ First tweet\n\n2/ Second tweet\nwith a second line\n\n3/ Last one #rust"),
+                output(
+                    "email_sequence",
+                    "EMAIL 1: The Hook\nSUBJECT: Open me\nSEND TIMING: Day 1\n\nHello there.\n\nEMAIL 2: The Close\nSUBJECT: Last chance\nSEND TIMING: Day 3\n\nGoodbye.",
+                ),
+                output("linkedin", "A post.\n\nWith two paragraphs."),
+            ],
+        }
+    }
+
+    fn read_entry(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+        let mut contents = String::new();
+        archive
+            .by_name(name)
+            .unwrap_or_else(|_| panic!("missing {}", name))
+            .read_to_string(&mut contents)
+            .unwrap();
+        contents
+    }
+
+    #[test]
+    fn produces_a_readable_package_with_all_parts() {
+        let bytes = build_docx(&[detail()]).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+
+        let names: Vec<&str> = archive.file_names().collect();
+        for part in [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "word/document.xml",
+            "word/_rels/document.xml.rels",
+            "word/styles.xml",
+            "word/numbering.xml",
+            "docProps/core.xml",
+            "docProps/app.xml",
+        ] {
+            assert!(names.contains(&part), "missing {}", part);
+        }
+
+        let content_types = read_entry(&mut archive, "[Content_Types].xml");
+        assert!(content_types.contains("wordprocessingml.document.main+xml"));
+    }
+
+    #[test]
+    fn renders_sections_tweets_and_subjects() {
+        let bytes = build_docx(&[detail()]).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let document = read_entry(&mut archive, "word/document.xml");
+
+        assert!(document.contains("Shipping &lt;fast&gt; &amp; safe"));
+        assert!(document.contains("Source: https://example.com/post?a=1&amp;b=2"));
+
+        // One page break per format section
+        assert_eq!(document.matches("<w:pageBreakBefore/>").count(), 3);
+
+        // Tweets are numbered list items without their "1/" prefixes
+        assert_eq!(document.matches(r#"<w:numId w:val="1"/>"#).count(), 3);
+        assert!(document.contains(">First tweet<"));
+        assert!(document.contains("Second tweet</w:t><w:br/>"));
+
+        // Email subjects are bold
+        assert!(document.contains(r#"<w:rPr><w:b/></w:rPr><w:t xml:space="preserve">Subject: Open me</w:t>"#));
+        assert!(document.contains("Subject: Last chance"));
+        assert!(document.contains(">Email 2: The Close<"));
+
+        let numbering = read_entry(&mut archive, "word/numbering.xml");
+        assert!(numbering.contains(r#"<w:num w:numId="1">"#));
+    }
+
+    #[test]
+    fn sets_core_properties() {
+        let bytes = build_docx(&[detail()]).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let core = read_entry(&mut archive, "docProps/core.xml");
+
+        assert!(core.contains("<dc:title>Shipping &lt;fast&gt; &amp; safe</dc:title>"));
+        assert!(core.contains("<cp:keywords>twitter_thread, email_sequence, linkedin</cp:keywords>"));
+        assert!(core.contains(r#"<dcterms:created xsi:type="dcterms:W3CDTF">2026-01-02T03:04:05Z</dcterms:created>"#));
+    }
+}
@@ -0,0 +1,497 @@
+use crate::errors::AppError;
+use crate::models::content::RepurposedOutput;
+use crate::models::export::{EmailTemplate, RenderedEmail};
+use crate::services::pdf_export::{self, escape_html};
+use crate::services::{output_text, workspace};
+
+/// The active workspace's email template, or the default one.
+pub fn load_template(conn: &rusqlite::Connection) -> EmailTemplate {
+    workspace::get_setting(conn, &workspace::active_workspace_id(conn), "email_template")
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn validate_template(mut template: EmailTemplate) -> Result<EmailTemplate, AppError> {
+    template.accent_color = pdf_export::normalize_color(&template.accent_color)?;
+    template.background_color = pdf_export::normalize_color(&template.background_color)?;
+    template.text_color = pdf_export::normalize_color(&template.text_color)?;
+
+    template.logo_url = template.logo_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(ref url) = template.logo_url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(AppError::Validation(
+                "The logo must be a hosted http(s) URL".to_string(),
+            ));
+        }
+    }
+
+    template.from_address = template
+        .from_address
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    if let Some(ref address) = template.from_address {
+        if !address.contains('@') || address.contains(['\r', '\n']) {
+            return Err(AppError::Validation(format!(
+                "Invalid from address '{}'",
+                address
+            )));
+        }
+    }
+
+    if template.font_family.trim().is_empty() {
+        template.font_family = EmailTemplate::default().font_family;
+    }
+
+    Ok(template)
+}
+
+/// Renders a newsletter as one email, or an email sequence as one per email.
+pub fn render_output(
+    output: &RepurposedOutput,
+    template: &EmailTemplate,
+) -> Result<Vec<RenderedEmail>, AppError> {
+    match output.format.as_str() {
+        "newsletter" => {
+            let newsletter = output_text::parse_newsletter(&output.output_text);
+            let subject = newsletter.subject.unwrap_or_else(|| "Newsletter".to_string());
+            Ok(vec![render_email(
+                None,
+                subject,
+                newsletter.preview,
+                None,
+                &newsletter.body,
+                template,
+            )])
+        }
+        "email_sequence" => Ok(output_text::parse_email_sequence(&output.output_text)
+            .into_iter()
+            .map(|email| {
+                let subject = email
+                    .subject
+                    .unwrap_or_else(|| format!("Email {}", email.number));
+                render_email(
+                    Some(email.number),
+                    subject,
+                    email.preview,
+                    email.send_timing,
+                    &email.body,
+                    template,
+                )
+            })
+            .collect()),
+        other => Err(AppError::Validation(format!(
+            "HTML emails can only be rendered from newsletters and email sequences, not '{}'",
+            other
+        ))),
+    }
+}
+
+fn render_email(
+    number: Option<u32>,
+    subject: String,
+    preview: Option<String>,
+    send_timing: Option<String>,
+    body: &str,
+    template: &EmailTemplate,
+) -> RenderedEmail {
+    RenderedEmail {
+        number,
+        html: render_html(&subject, preview.as_deref(), body, template),
+        text: render_text(body, template),
+        subject,
+        preview,
+        send_timing,
+    }
+}
+
+/// A table-based layout with inline styles, since most email clients ignore
+/// stylesheets. The one media query only narrows the container on phones.
+fn render_html(subject: &str, preview: Option<&str>, body: &str, template: &EmailTemplate) -> String {
+    let font = escape_html(&template.font_family);
+    let text_style = format!(
+        "font-family:{};font-size:16px;line-height:1.6;color:{};",
+        font, template.text_color
+    );
+
+    let header = match (&template.logo_url, &template.brand_name) {
+        (Some(url), name) => format!(
+            r#"<img src="{}" alt="{}" height="40" style="display:block;height:40px;width:auto;border:0;">"#,
+            escape_html(url),
+            escape_html(name.as_deref().unwrap_or_default())
+        ),
+        (None, Some(name)) => format!(
+            r#"<span style="font-family:{};font-size:20px;font-weight:bold;color:{};">{}</span>"#,
+            font,
+            template.accent_color,
+            escape_html(name)
+        ),
+        (None, None) => String::new(),
+    };
+
+    let footer = template
+        .footer_text
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| {
+            format!(
+                r#"<tr><td style="padding:20px 32px;font-family:{};font-size:12px;line-height:1.5;color:#888888;">{}</td></tr>"#,
+                font,
+                inline_html(text.trim(), template).replace('\n', "<br>")
+            )
+        })
+        .unwrap_or_default();
+
+    // Hidden preheader text shows next to the subject in the inbox
+    let preheader = preview
+        .map(|text| {
+            format!(
+                r#"<div style="display:none;max-height:0;overflow:hidden;mso-hide:all;">{}</div>"#,
+                escape_html(text)
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="x-apple-disable-message-reformatting">
+<title>{title}</title>
+<style>
+@media only screen and (max-width: 620px) {{
+  .container {{ width: 100% !important; }}
+  .content {{ padding: 20px !important; }}
+}}
+</style>
+</head>
+<body style="margin:0;padding:0;background-color:{background};">
+{preheader}
+<table role="presentation" width="100%" cellpadding="0" cellspacing="0" border="0" style="background-color:{background};">
+<tr><td align="center" style="padding:24px 12px;">
+<table role="presentation" class="container" width="600" cellpadding="0" cellspacing="0" border="0" style="width:600px;max-width:600px;background-color:#ffffff;border-radius:6px;">
+<tr><td style="padding:24px 32px;border-bottom:3px solid {accent};">{header}</td></tr>
+<tr><td class="content" style="padding:32px;{text_style}">
+{blocks}
+</td></tr>
+{footer}
+</table>
+</td></tr>
+</table>
+</body>
+</html>
+"#,
+        title = escape_html(subject),
+        background = template.background_color,
+        accent = template.accent_color,
+        preheader = preheader,
+        header = header,
+        text_style = text_style,
+        blocks = body_blocks(body, template),
+        footer = footer,
+    )
+}
+
+/// Paragraphs, "#" headings and "-"/"1." lists from the plain body text.
+fn body_blocks(body: &str, template: &EmailTemplate) -> String {
+    let mut html = Vec::new();
+
+    for block in body.trim().split("\n\n").map(str::trim).filter(|b| !b.is_empty()) {
+        let mut lines: Vec<&str> = block.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+
+        if let Some(heading) = lines[0].strip_prefix('#') {
+            html.push(format!(
+                r#"<h2 style="margin:24px 0 12px;font-size:20px;line-height:1.3;color:{};">{}</h2>"#,
+                template.accent_color,
+                inline_html(heading.trim_start_matches('#').trim(), template)
+            ));
+            lines.remove(0);
+            if lines.is_empty() {
+                continue;
+            }
+        }
+
+        if lines.iter().all(|line| bullet_item(line).is_some()) {
+            html.push(list_html("ul", lines.iter().filter_map(|l| bullet_item(l)), template));
+        } else if lines.iter().all(|line| numbered_item(line).is_some()) {
+            html.push(list_html("ol", lines.iter().filter_map(|l| numbered_item(l)), template));
+        } else {
+            let text: Vec<String> = lines.iter().map(|line| inline_html(line, template)).collect();
+            html.push(format!(
+                r#"<p style="margin:0 0 16px;">{}</p>"#,
+                text.join("<br>")
+            ));
+        }
+    }
+
+    html.join("\n")
+}
+
+fn list_html<'a>(
+    tag: &str,
+    items: impl Iterator<Item = &'a str>,
+    template: &EmailTemplate,
+) -> String {
+    let items: String = items
+        .map(|item| format!(r#"<li style="margin:0 0 8px;">{}</li>"#, inline_html(item, template)))
+        .collect();
+    format!(r#"<{tag} style="margin:0 0 16px;padding-left:24px;">{items}</{tag}>"#)
+}
+
+fn bullet_item(line: &str) -> Option<&str> {
+    line.strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("• "))
+}
+
+fn numbered_item(line: &str) -> Option<&str> {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))
+}
+
+/// Escapes a line, turns **bold** into <strong> and bare URLs into links.
+fn inline_html(text: &str, template: &EmailTemplate) -> String {
+    let escaped = escape_html(text);
+
+    let mut bolded = String::new();
+    for (i, part) in escaped.split("**").enumerate() {
+        if i % 2 == 1 {
+            bolded.push_str("<strong>");
+            bolded.push_str(part);
+            bolded.push_str("</strong>");
+        } else {
+            bolded.push_str(part);
+        }
+    }
+    // An unmatched "**" leaves an unclosed tag
+    if escaped.matches("**").count() % 2 == 1 {
+        bolded = escaped;
+    }
+
+    bolded
+        .split(' ')
+        .map(|word| {
+            let end = word.trim_end_matches(['.', ',', ')', '!', '?', ';', ':']).len();
+            let (url, rest) = word.split_at(end);
+            if url.starts_with("https://") || url.starts_with("http://") {
+                format!(
+                    r#"<a href="{url}" style="color:{};text-decoration:underline;">{url}</a>{rest}"#,
+                    template.accent_color
+                )
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The plain-text alternative: the body without markdown emphasis or heading
+/// markers, then the footer.
+fn render_text(body: &str, template: &EmailTemplate) -> String {
+    let mut text = body
+        .trim()
+        .replace("**", "")
+        .lines()
+        .map(|line| line.trim_start_matches('#').trim_start())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Some(footer) = template.footer_text.as_deref().filter(|f| !f.trim().is_empty()) {
+        text.push_str("\n\n--\n");
+        text.push_str(footer.trim());
+    }
+    text.push('\n');
+    text
+}
+
+/// A multipart/alternative message with the plain-text and HTML versions,
+/// marked as unsent so mail clients open it as a draft.
+pub fn build_eml(email: &RenderedEmail, template: &EmailTemplate) -> String {
+    let boundary = format!("----=_Part_{}", uuid::Uuid::new_v4().simple());
+
+    let mut headers = Vec::new();
+    if let Some(ref from) = template.from_address {
+        headers.push(format!("From: {}", from));
+    }
+    headers.push(format!("Subject: {}", encode_header(&email.subject)));
+    headers.push(format!("Date: {}", chrono::Utc::now().to_rfc2822()));
+    headers.push("MIME-Version: 1.0".to_string());
+    headers.push("X-Unsent: 1".to_string());
+    headers.push(format!(
+        "Content-Type: multipart/alternative; boundary=\"{}\"",
+        boundary
+    ));
+
+    let mut eml = headers.join("\r\n");
+    eml.push_str("\r\n\r\n");
+    for (content_type, body) in [("text/plain", &email.text), ("text/html", &email.html)] {
+        eml.push_str(&format!("--{}\r\n", boundary));
+        eml.push_str(&format!("Content-Type: {}; charset=utf-8\r\n", content_type));
+        eml.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
+        eml.push_str(&quoted_printable(body));
+        eml.push_str("\r\n");
+    }
+    eml.push_str(&format!("--{}--\r\n", boundary));
+    eml
+}
+
+/// RFC 2047 "Q" encoded-words for non-ASCII header values, each kept under
+/// the 75 character limit.
+fn encode_header(value: &str) -> String {
+    if value.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        return value.to_string();
+    }
+
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in value.chars() {
+        let mut encoded = String::new();
+        if c == ' ' {
+            encoded.push('_');
+        } else if c.is_ascii_alphanumeric() || "!*+-/".contains(c) {
+            encoded.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("={:02X}", byte));
+            }
+        }
+        // "=?UTF-8?Q?" plus "?=" is 12 characters
+        if current.len() + encoded.len() > 75 - 12 {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push_str(&encoded);
+    }
+    words.push(current);
+
+    words
+        .iter()
+        .map(|word| format!("=?UTF-8?Q?{}?=", word))
+        .collect::<Vec<_>>()
+        .join("\r\n ")
+}
+
+/// RFC 2045 quoted-printable with CRLF line endings and soft breaks keeping
+/// lines within 76 characters.
+fn quoted_printable(text: &str) -> String {
+    let mut lines = Vec::new();
+
+    for line in text.lines() {
+        let bytes = line.as_bytes();
+        let mut encoded = String::new();
+        let mut width = 0;
+
+        for (i, &byte) in bytes.iter().enumerate() {
+            let is_last = i + 1 == bytes.len();
+            let token = match byte {
+                b' ' | b'\t' if is_last => format!("={:02X}", byte),
+                b' ' | b'\t' => (byte as char).to_string(),
+                33..=60 | 62..=126 => (byte as char).to_string(),
+                _ => format!("={:02X}", byte),
+            };
+            // Leave room for the "=" of a soft line break
+            if width + token.len() > 75 {
+                encoded.push_str("=\r\n");
+                width = 0;
+            }
+            width += token.len();
+            encoded.push_str(&token);
+        }
+        lines.push(encoded);
+    }
+
+    lines.join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_header_leaves_ascii_alone() {
+        assert_eq!(encode_header("Five tips for launch day"), "Five tips for launch day");
+    }
+
+    #[test]
+    fn encode_header_q_encodes_non_ascii() {
+        assert_eq!(encode_header("Café ☕"), "=?UTF-8?Q?Caf=C3=A9_=E2=98=95?=");
+        assert_eq!(encode_header("a=b?"), "a=b?");
+        assert_eq!(encode_header("é=?"), "=?UTF-8?Q?=C3=A9=3D=3F?=");
+    }
+
+    #[test]
+    fn encode_header_splits_long_values_between_characters() {
+        let encoded = encode_header(&"ü".repeat(40));
+        let words: Vec<&str> = encoded.split("\r\n ").collect();
+        assert!(words.len() > 1);
+        for word in words {
+            assert!(word.len() <= 75, "{} is too long", word);
+            let inner = word.strip_prefix("=?UTF-8?Q?").unwrap().strip_suffix("?=").unwrap();
+            // Every word holds whole characters: "=C3=BC" repeated
+            assert_eq!(inner.len() % 6, 0);
+        }
+    }
+
+    #[test]
+    fn quoted_printable_soft_breaks_at_76() {
+        let line = "a".repeat(200);
+        let encoded = quoted_printable(&line);
+        for part in encoded.split("\r\n") {
+            assert!(part.len() <= 76, "{} is too long", part);
+        }
+        assert!(encoded.starts_with(&format!("{}=\r\n", "a".repeat(75))));
+        assert_eq!(encoded.replace("=\r\n", ""), line);
+    }
+
+    #[test]
+    fn quoted_printable_never_splits_an_escape() {
+        let encoded = quoted_printable(&format!("{}é", "a".repeat(74)));
+        assert_eq!(encoded, format!("{}=\r\n=C3=A9", "a".repeat(74)));
+    }
+
+    #[test]
+    fn quoted_printable_escapes_specials() {
+        assert_eq!(quoted_printable("1 = 1 "), "1 =3D 1=20");
+        assert_eq!(quoted_printable("one\ntwo"), "one\r\ntwo");
+        assert_eq!(quoted_printable("naïve"), "na=C3=AFve");
+    }
+
+    #[test]
+    fn build_eml_has_headers_and_both_parts() {
+        let email = RenderedEmail {
+            number: Some(1),
+            subject: "Grüße".to_string(),
+            preview: None,
+            send_timing: None,
+            html: "<p>Hello</p>".to_string(),
+            text: "Hello".to_string(),
+        };
+        let template = EmailTemplate {
+            from_address: Some("News <news@example.com>".to_string()),
+            ..EmailTemplate::default()
+        };
+        let eml = build_eml(&email, &template);
+
+        let (headers, body) = eml.split_once("\r\n\r\n").unwrap();
+        assert!(headers.contains("From: News <news@example.com>\r\n"));
+        assert!(headers.contains("Subject: =?UTF-8?Q?Gr=C3=BC=C3=9Fe?=\r\n"));
+        assert!(headers.contains("MIME-Version: 1.0\r\n"));
+        assert!(headers.contains("X-Unsent: 1\r\n"));
+
+        let boundary = headers
+            .split("boundary=\"")
+            .nth(1)
+            .and_then(|rest| rest.strip_suffix('"'))
+            .unwrap();
+        assert_eq!(body.matches(&format!("--{}\r\n", boundary)).count(), 2);
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)));
+        assert!(body.contains("Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: quoted-printable\r\n\r\nHello\r\n"));
+        assert!(body.contains("Content-Type: text/html; charset=utf-8\r\nContent-Transfer-Encoding: quoted-printable\r\n\r\n<p>Hello</p>\r\n"));
+    }
+}
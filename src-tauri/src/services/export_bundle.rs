@@ -0,0 +1,107 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::errors::AppError;
+use crate::models::content::{HistoryDetail, JsonExportItem, KeyPoints};
+use crate::models::export::{ExportFileType, PdfTemplate};
+use crate::services::schedule::ScheduledTimes;
+use crate::services::{data_export, docx_export, markdown_export, pdf_export};
+
+/// What the individual renderers need beyond the content itself.
+pub struct RenderContext<'a> {
+    pub template: &'a PdfTemplate,
+    /// Voice version ids to profile names, for Markdown front matter.
+    pub voices: &'a HashMap<String, String>,
+    /// Key points by content input id, for JSON.
+    pub key_points: &'a HashMap<String, KeyPoints>,
+    /// Calendar slots, for the CSV scheduled_at column.
+    pub scheduled: &'a ScheduledTimes,
+    pub split_parts: bool,
+}
+
+/// Renders content inputs as one document of the given type. Returns the bytes
+/// and any warnings from rendering.
+pub fn render(
+    file_type: ExportFileType,
+    details: &[HistoryDetail],
+    context: &RenderContext,
+) -> Result<(Vec<u8>, Vec<String>), AppError> {
+    match file_type {
+        ExportFileType::Pdf => pdf_export::build_pdf(details, context.template),
+        ExportFileType::Docx => Ok((docx_export::build_docx(details)?, Vec::new())),
+        ExportFileType::Markdown => match details {
+            [detail] => Ok((
+                markdown_export::render_input(detail, context.voices).into_bytes(),
+                Vec::new(),
+            )),
+            _ => Err(AppError::Validation(
+                "Markdown exports of several items need a ZIP archive".to_string(),
+            )),
+        },
+        ExportFileType::Json => {
+            let json = data_export::build_json(json_items(details, context))?;
+            Ok((json.into_bytes(), Vec::new()))
+        }
+        ExportFileType::Csv => {
+            let csv = data_export::build_csv(
+                &json_items(details, context),
+                context.split_parts,
+                context.scheduled,
+            );
+            Ok((csv.into_bytes(), Vec::new()))
+        }
+    }
+}
+
+/// Packs named files into a ZIP archive, suffixing repeated names with "-2",
+/// "-3" and so on. Returns the archive and the entry names used.
+pub fn build_zip(files: Vec<(String, Vec<u8>)>) -> Result<(Vec<u8>, Vec<String>), AppError> {
+    let zip_error = |e: zip::result::ZipError| AppError::FileIo(format!("Failed to build ZIP: {}", e));
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut used: HashSet<String> = HashSet::new();
+    let mut names = Vec::new();
+    for (name, contents) in files {
+        let name = unique_entry_name(&used, &name);
+        used.insert(name.clone());
+
+        zip.start_file(name.as_str(), options).map_err(zip_error)?;
+        zip.write_all(&contents)
+            .map_err(|e| AppError::FileIo(format!("Failed to build ZIP: {}", e)))?;
+        names.push(name);
+    }
+
+    Ok((zip.finish().map_err(zip_error)?.into_inner(), names))
+}
+
+fn unique_entry_name(used: &HashSet<String>, name: &str) -> String {
+    if !used.contains(name) {
+        return name.to_string();
+    }
+    let (stem, extension) = name.rsplit_once('.').unwrap_or((name, ""));
+    (2..)
+        .map(|n| {
+            if extension.is_empty() {
+                format!("{}-{}", stem, n)
+            } else {
+                format!("{}-{}.{}", stem, n, extension)
+            }
+        })
+        .find(|candidate| !used.contains(candidate))
+        .unwrap_or_default()
+}
+
+fn json_items(details: &[HistoryDetail], context: &RenderContext) -> Vec<JsonExportItem> {
+    details
+        .iter()
+        .map(|detail| JsonExportItem {
+            input: detail.input.clone(),
+            key_points: context.key_points.get(&detail.input.id).cloned(),
+            outputs: detail.outputs.clone(),
+        })
+        .collect()
+}
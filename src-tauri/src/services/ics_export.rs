@@ -0,0 +1,304 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::errors::AppError;
+use crate::models::content::RepurposedOutput;
+use crate::services::output_text;
+use crate::services::schedule::{self, ScheduledTimes};
+
+const PRODID: &str = "-//ContentEngine//Content Calendar//EN";
+const UID_DOMAIN: &str = "contentengine.app";
+
+/// RFC 5545 caps content lines at 75 octets, excluding the CRLF.
+const MAX_LINE_OCTETS: usize = 75;
+
+pub struct IcsEvent {
+    /// Derived from the output id and part, so re-importing a calendar
+    /// updates its events instead of duplicating them.
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub summary: String,
+    pub description: String,
+    pub category: String,
+}
+
+/// One event per single-piece output, and one per tweet of a thread or email
+/// of a sequence. Outputs go out in order from `start`, `cadence` apart, with
+/// email SEND TIMING days taking precedence. Anything already on the content
+/// calendar keeps its slot.
+pub fn plan_events(
+    outputs: &[(RepurposedOutput, Option<String>)],
+    start: DateTime<Utc>,
+    cadence: Duration,
+    scheduled: &ScheduledTimes,
+) -> Result<Vec<IcsEvent>, AppError> {
+    let slot = |output_id: &str, part: Option<u32>| {
+        scheduled
+            .get(&(output_id.to_string(), part))
+            .and_then(|at| schedule::parse_datetime(at).ok())
+    };
+
+    let mut events = Vec::new();
+    let mut next = start;
+    for (output, title) in outputs {
+        let title = title.as_deref().unwrap_or("Untitled");
+        let category = output_text::format_display_name(&output.format);
+        let output_slot = slot(&output.id, None);
+        let output_start = output_slot.unwrap_or(next);
+
+        match part_events(output, title) {
+            Some(parts) => {
+                let times = schedule::spread_times(output, output_start, cadence)?;
+                for ((number, at), (summary, description)) in times.into_iter().zip(parts) {
+                    if output_slot.is_none() {
+                        next = next.max(at + cadence);
+                    }
+                    let start = slot(&output.id, Some(number)).unwrap_or(at);
+                    events.push(IcsEvent {
+                        uid: format!("{}-{}@{}", output.id, number, UID_DOMAIN),
+                        start,
+                        summary,
+                        description,
+                        category: category.clone(),
+                    });
+                }
+            }
+            None => {
+                if output_slot.is_none() {
+                    next = output_start + cadence;
+                }
+                events.push(IcsEvent {
+                    uid: format!("{}@{}", output.id, UID_DOMAIN),
+                    start: output_start,
+                    summary: format!("{}: {}", category, title),
+                    description: output.output_text.trim().to_string(),
+                    category,
+                });
+            }
+        }
+    }
+
+    events.sort_by_key(|event| event.start);
+    Ok(events)
+}
+
+/// Summaries and descriptions for each tweet or email; `None` for formats
+/// that go out in one piece.
+fn part_events(output: &RepurposedOutput, title: &str) -> Option<Vec<(String, String)>> {
+    match output.format.as_str() {
+        "twitter_thread" => {
+            let tweets = output_text::split_tweets(&output.output_text);
+            let count = tweets.len();
+            Some(
+                tweets
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, tweet)| (format!("Tweet {}/{}: {}", i + 1, count, title), tweet))
+                    .collect(),
+            )
+        }
+        "email_sequence" => Some(
+            output_text::parse_email_sequence(&output.output_text)
+                .into_iter()
+                .map(|email| {
+                    let summary = match email.subject.as_deref() {
+                        Some(subject) => format!("Email {}: {}", email.number, subject),
+                        None => format!("Email {}: {}", email.number, title),
+                    };
+                    let mut description = String::new();
+                    if let Some(subject) = email.subject {
+                        description.push_str(&format!("Subject: {}\n", subject));
+                    }
+                    if let Some(preview) = email.preview {
+                        description.push_str(&format!("Preview: {}\n", preview));
+                    }
+                    if !description.is_empty() {
+                        description.push('\n');
+                    }
+                    description.push_str(&email.body);
+                    (summary, description)
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// A VCALENDAR with one VEVENT per event, CRLF line endings and folded lines.
+pub fn build_ics(events: &[IcsEvent], duration: Duration) -> String {
+    let stamp = ics_datetime(&Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+
+    for event in events {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", event.uid),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART:{}", ics_datetime(&event.start)),
+            format!("DTEND:{}", ics_datetime(&(event.start + duration))),
+            format!("SUMMARY:{}", escape_text(&event.summary)),
+            format!("DESCRIPTION:{}", escape_text(&event.description)),
+            format!("CATEGORIES:{}", escape_text(&event.category)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+fn ics_datetime(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value (RFC 5545 section 3.3.11).
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.trim().chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits a content line into 75-octet pieces without breaking a UTF-8
+/// character, continuing each with a leading space. Ends with CRLF.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn output(id: &str, format: &str, text: &str) -> RepurposedOutput {
+        RepurposedOutput {
+            id: id.to_string(),
+            content_input_id: "input-id".to_string(),
+            format: format.to_string(),
+            output_text: text.to_string(),
+            created_at: "2026-01-02T03:04:05+00:00".to_string(),
+            voice_version_id: None,
+            voice_score: None,
+            phrase_flags: Vec::new(),
+            approved_at: None,
+            tone: None,
+            length: None,
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn folds_at_75_octets_without_splitting_characters() {
+        // 73 ASCII octets leave room for one 2-octet "é" but not the 4-octet emoji after it
+        let line = format!("{}é🚀{}", "a".repeat(73), "日本語".repeat(40));
+        let folded = fold_line(&line);
+
+        assert!(folded.ends_with("\r\n"));
+        let physical: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(physical.len() > 2);
+        assert_eq!(physical[0], format!("{}é", "a".repeat(73)));
+        assert!(physical[1].starts_with(" 🚀"));
+        for piece in &physical {
+            assert!(piece.len() <= MAX_LINE_OCTETS, "{} octets: {:?}", piece.len(), piece);
+        }
+        for piece in &physical[1..] {
+            assert!(piece.starts_with(' '));
+        }
+
+        assert_eq!(folded.trim_end_matches("\r\n").replace("\r\n ", ""), line);
+        assert_eq!(fold_line("short"), "short\r\n");
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape_text("a;b,c\\d\ne"), r"a\;b\,c\\d\ne");
+        assert_eq!(escape_text("  line one\r\nline two\n"), r"line one\nline two");
+    }
+
+    #[test]
+    fn uids_are_stable_across_exports() {
+        let outputs = vec![
+            (output("thread", "twitter_thread", "1/ First\n\n2/ Second"), Some("Post".to_string())),
+            (output("post", "linkedin", "A post."), None),
+        ];
+        let uids = |start| {
+            plan_events(&outputs, start, Duration::days(1), &ScheduledTimes::new())
+                .unwrap()
+                .into_iter()
+                .map(|event| event.uid)
+                .collect::<Vec<_>>()
+        };
+
+        let first = uids(start());
+        assert_eq!(
+            first,
+            vec![
+                "thread-1@contentengine.app",
+                "thread-2@contentengine.app",
+                "post@contentengine.app",
+            ]
+        );
+        assert_eq!(uids(start() + Duration::days(7)), first);
+    }
+
+    #[test]
+    fn send_timing_days_override_the_cadence() {
+        let sequence = output(
+            "seq",
+            "email_sequence",
+            "EMAIL 1: Hook\nSUBJECT: Open me\nSEND TIMING: Day 1\n\nHello.\n\n\
+             EMAIL 2: Story\nSUBJECT: A story\nSEND TIMING: Day 4\n\nOnce.\n\n\
+             EMAIL 3: Close\nSUBJECT: Last chance\n\nGoodbye.",
+        );
+        let outputs = vec![
+            (sequence, Some("Launch".to_string())),
+            (output("post", "linkedin", "A post."), None),
+        ];
+
+        let events = plan_events(&outputs, start(), Duration::days(1), &ScheduledTimes::new()).unwrap();
+        let starts: Vec<(&str, DateTime<Utc>)> =
+            events.iter().map(|event| (event.uid.as_str(), event.start)).collect();
+        assert_eq!(
+            starts,
+            vec![
+                ("seq-1@contentengine.app", start()),
+                ("seq-2@contentengine.app", start() + Duration::days(3)),
+                // No SEND TIMING: one cadence after the previous email
+                ("seq-3@contentengine.app", start() + Duration::days(4)),
+                // The next output waits for the whole sequence
+                ("post@contentengine.app", start() + Duration::days(5)),
+            ]
+        );
+        assert_eq!(events[1].summary, "Email 2: A story");
+    }
+}
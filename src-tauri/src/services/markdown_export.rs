@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use crate::models::content::{ContentInput, HistoryDetail, RepurposedOutput};
+use crate::services::output_text::{self, format_display_name};
+
+/// Renders a content input and all of its outputs as one Markdown document.
+/// `voices` maps voice version ids to profile names.
+pub fn render_input(detail: &HistoryDetail, voices: &HashMap<String, String>) -> String {
+    let formats: Vec<&str> = detail.outputs.iter().map(|o| o.format.as_str()).collect();
+    let tones: Vec<&str> = detail.outputs.iter().filter_map(|o| o.tone.as_deref()).collect();
+    let voice_names: Vec<&str> = detail
+        .outputs
+        .iter()
+        .filter_map(|o| voice_name(o, voices))
+        .collect();
+
+    let mut doc = front_matter(&detail.input, None, &formats, &tones, &voice_names);
+    for output in &detail.outputs {
+        doc.push('\n');
+        doc.push_str(&render_section(output));
+    }
+    doc
+}
+
+/// Renders a single output as its own Markdown document.
+pub fn render_output(
+    input: &ContentInput,
+    output: &RepurposedOutput,
+    voices: &HashMap<String, String>,
+) -> String {
+    let tones: Vec<&str> = output.tone.as_deref().into_iter().collect();
+    let voice_names: Vec<&str> = voice_name(output, voices).into_iter().collect();
+
+    let mut doc = front_matter(input, Some(&output.id), &[&output.format], &tones, &voice_names);
+    doc.push('\n');
+    doc.push_str(&render_section(output));
+    doc
+}
+
+fn voice_name<'a>(output: &RepurposedOutput, voices: &'a HashMap<String, String>) -> Option<&'a str> {
+    output
+        .voice_version_id
+        .as_ref()
+        .and_then(|id| voices.get(id))
+        .map(String::as_str)
+}
+
+fn front_matter(
+    input: &ContentInput,
+    output_id: Option<&str>,
+    formats: &[&str],
+    tones: &[&str],
+    voices: &[&str],
+) -> String {
+    let mut yaml = String::from("---\n");
+    yaml.push_str(&format!(
+        "title: {}\n",
+        yaml_string(input.title.as_deref().unwrap_or("Untitled Content"))
+    ));
+    yaml.push_str(&format!("id: {}\n", yaml_string(output_id.unwrap_or(&input.id))));
+    if let Some(ref url) = input.source_url {
+        yaml.push_str(&format!("source_url: {}\n", yaml_string(url)));
+    }
+    yaml.push_str(&format!("created_at: {}\n", yaml_string(&input.created_at)));
+    yaml.push_str(&yaml_field("format", formats));
+    yaml.push_str(&yaml_field("tone", tones));
+    yaml.push_str(&yaml_field("voice", voices));
+    yaml.push_str(&format!("word_count: {}\n", input.word_count));
+    yaml.push_str("---\n");
+    yaml
+}
+
+/// A scalar for one distinct value, a flow list for several, nothing for none.
+fn yaml_field(key: &str, values: &[&str]) -> String {
+    let mut distinct: Vec<&str> = Vec::new();
+    for value in values {
+        if !distinct.contains(value) {
+            distinct.push(value);
+        }
+    }
+
+    match distinct.as_slice() {
+        [] => String::new(),
+        [value] => format!("{}: {}\n", key, yaml_string(value)),
+        values => format!(
+            "{}: [{}]\n",
+            key,
+            values.iter().map(|v| yaml_string(v)).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn yaml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn render_section(output: &RepurposedOutput) -> String {
+    let mut section = format!("## {}\n\n", format_display_name(&output.format));
+
+    match output.format.as_str() {
+        "twitter_thread" => {
+            for (i, tweet) in output_text::split_tweets(&output.output_text).iter().enumerate() {
+                let marker = format!("{}. ", i + 1);
+                section.push_str(&marker);
+                section.push_str(&indent_continuation(tweet, marker.len()));
+                section.push('\n');
+            }
+        }
+        "email_sequence" => {
+            for (i, email) in output_text::parse_email_sequence(&output.output_text)
+                .iter()
+                .enumerate()
+            {
+                let marker = format!("{}. ", i + 1);
+                let indent = " ".repeat(marker.len());
+                let heading = match email.label {
+                    Some(ref label) => format!("Email {}: {}", email.number, label),
+                    None => format!("Email {}", email.number),
+                };
+                section.push_str(&format!("{}**{}**\n", marker, heading));
+
+                let fields = [
+                    ("Subject", &email.subject),
+                    ("Preview", &email.preview),
+                    ("Send timing", &email.send_timing),
+                ];
+                for (label, value) in fields {
+                    if let Some(value) = value {
+                        section.push_str(&format!("{}- **{}:** {}\n", indent, label, value));
+                    }
+                }
+
+                if !email.body.is_empty() {
+                    section.push('\n');
+                    section.push_str(&indent);
+                    section.push_str(&indent_continuation(&email.body, marker.len()));
+                    section.push('\n');
+                }
+                section.push('\n');
+            }
+            section.truncate(section.trim_end().len());
+            section.push('\n');
+        }
+        "newsletter" => {
+            let newsletter = output_text::parse_newsletter(&output.output_text);
+            if let Some(ref subject) = newsletter.subject {
+                section.push_str(&format!("**Subject:** {}\n\n", subject));
+            }
+            if let Some(ref preview) = newsletter.preview {
+                section.push_str(&format!("**Preview:** {}\n\n", preview));
+            }
+            section.push_str(&newsletter.body);
+            section.push('\n');
+        }
+        _ => {
+            section.push_str(output.output_text.trim());
+            section.push('\n');
+        }
+    }
+
+    section
+}
+
+/// Indents every line after the first so multi-line text stays inside a list item.
+fn indent_continuation(text: &str, width: usize) -> String {
+    let indent = " ".repeat(width);
+    text.trim()
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.trim().is_empty() {
+                line.trim_end().to_string()
+            } else {
+                format!("{}{}", indent, line.trim_end())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(format: &str, text: &str) -> RepurposedOutput {
+        RepurposedOutput {
+            id: format!("{}-id", format),
+            content_input_id: "input-id".to_string(),
+            format: format.to_string(),
+            output_text: text.to_string(),
+            created_at: "2026-01-02T03:04:05+00:00".to_string(),
+            voice_version_id: None,
+            voice_score: None,
+            phrase_flags: Vec::new(),
+            approved_at: None,
+            tone: None,
+            length: None,
+        }
+    }
+
+    /// Decodes a double-quoted YAML scalar or flow list. Both use the same
+    /// escapes as JSON, so a value that parses as JSON is valid YAML too.
+    fn decode<T: serde::de::DeserializeOwned>(yaml: &str) -> T {
+        serde_json::from_str(yaml).unwrap_or_else(|e| panic!("{}: {}", yaml, e))
+    }
+
+    #[test]
+    fn quotes_strings_that_would_break_yaml() {
+        let tricky = "Say \"hi\": C:\\path\\to\r\nnext line\t# not a comment";
+        let quoted = yaml_string(tricky);
+        assert!(!quoted.contains('\n'), "{}", quoted);
+        assert_eq!(
+            decode::<String>(&quoted),
+            "Say \"hi\": C:\\path\\to\nnext line\t# not a comment"
+        );
+        assert_eq!(yaml_string("- [x], {y} & *z"), "\"- [x], {y} & *z\"");
+    }
+
+    #[test]
+    fn writes_a_scalar_for_one_value_and_a_flow_list_for_several() {
+        assert_eq!(yaml_field("tone", &[]), "");
+        assert_eq!(yaml_field("tone", &["casual", "casual"]), "tone: \"casual\"\n");
+
+        let field = yaml_field("voice", &["Ann \"A\"", "B\\C", "Ann \"A\"", "two\nlines"]);
+        let list = field.strip_prefix("voice: ").unwrap().strip_suffix('\n').unwrap();
+        assert_eq!(
+            decode::<Vec<String>>(list),
+            vec!["Ann \"A\"", "B\\C", "two\nlines"]
+        );
+    }
+
+    #[test]
+    fn keeps_front_matter_valid_for_awkward_titles() {
+        let input = ContentInput {
+            id: "input-id".to_string(),
+            source_url: None,
+            raw_text: String::new(),
+            title: Some("\"Quoted\" \\ title\nwith a break".to_string()),
+            word_count: 12,
+            created_at: "2026-01-02 03:04:05".to_string(),
+        };
+        let doc = render_output(&input, &output("linkedin", "A post."), &HashMap::new());
+
+        let front: Vec<&str> = doc.lines().take_while(|l| !l.is_empty()).collect();
+        assert_eq!(front.first(), Some(&"---"));
+        assert_eq!(front.iter().filter(|l| **l == "---").count(), 2);
+        let title = front[1].strip_prefix("title: ").unwrap();
+        assert_eq!(decode::<String>(title), "\"Quoted\" \\ title\nwith a break");
+        assert!(front.contains(&"id: \"linkedin-id\""));
+        assert!(front.contains(&"format: \"linkedin\""));
+    }
+
+    #[test]
+    fn renders_tweets_as_a_numbered_list() {
+        let section = render_section(&output(
+            "twitter_thread",
+            "1/ First tweet\n\n2/ Second tweet\nwith a second line\n\n3/ Last one #rust",
+        ));
+        assert_eq!(
+            section,
+            "## Twitter/X Thread\n\n\
+             1. First tweet\n\
+             2. Second tweet\n   with a second line\n\
+             3. Last one #rust\n"
+        );
+
+        // Continuation lines line up under two-digit markers too
+        let long: Vec<String> = (1..=10).map(|n| format!("{}/ Tweet {}\nmore", n, n)).collect();
+        let section = render_section(&output("twitter_thread", &long.join("\n\n")));
+        assert!(section.ends_with("10. Tweet 10\n    more\n"), "{}", section);
+    }
+
+    #[test]
+    fn renders_emails_with_their_fields() {
+        let section = render_section(&output(
+            "email_sequence",
+            "EMAIL 1: The Hook\nSUBJECT: Open me\nPREVIEW: A peek\nSEND TIMING: Day 1\n\n\
+             Hello there.\n\nSecond paragraph.\n\n\
+             EMAIL 2\nSUBJECT: Last chance\n\nGoodbye.",
+        ));
+        assert_eq!(
+            section,
+            "## Email Sequence\n\n\
+             1. **Email 1: The Hook**\n   \
+             - **Subject:** Open me\n   \
+             - **Preview:** A peek\n   \
+             - **Send timing:** Day 1\n\n   \
+             Hello there.\n\n   Second paragraph.\n\n\
+             2. **Email 2**\n   \
+             - **Subject:** Last chance\n\n   \
+             Goodbye.\n"
+        );
+    }
+}
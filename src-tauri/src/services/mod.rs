@@ -1,5 +1,21 @@
 pub mod brand_voice;
 pub mod claude_api;
+pub mod crawler;
+pub mod data_export;
+pub mod dedup;
+pub mod docx_export;
+pub mod email_render;
+pub mod export_bundle;
+pub mod ics_export;
+pub mod markdown_export;
 pub mod pdf_export;
+pub mod output_text;
+pub mod phrase_guard;
+pub mod schedule;
+pub mod stylometry;
+pub mod url_cache;
 pub mod url_fetcher;
 pub mod usage_tracker;
+pub mod voice_scoring;
+pub mod webhooks;
+pub mod workspace;
@@ -0,0 +1,295 @@
+use serde::Deserialize;
+
+use crate::models::content::{NewsletterParts, SequenceEmail};
+
+pub fn format_display_name(format: &str) -> String {
+    match format {
+        "twitter_thread" => "Twitter/X Thread".to_string(),
+        "linkedin" => "LinkedIn Post".to_string(),
+        "instagram" => "Instagram Caption".to_string(),
+        "newsletter" => "Newsletter".to_string(),
+        "email_sequence" => "Email Sequence".to_string(),
+        "summary" => "Summary".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Splits a thread into its tweets, without the "1/" numbering. Falls back to
+/// "---" separators, then blank-line paragraphs, for unnumbered threads. A
+/// JSON array of strings is also accepted.
+pub fn split_tweets(text: &str) -> Vec<String> {
+    if let Ok(tweets) = serde_json::from_str::<Vec<String>>(text) {
+        return tweets;
+    }
+
+    let mut tweets: Vec<String> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut next_number = 1;
+
+    for line in text.lines() {
+        match tweet_marker(line) {
+            Some((number, rest)) if number == next_number => {
+                push_joined(&mut tweets, &current);
+                current = vec![rest];
+                next_number += 1;
+            }
+            _ => current.push(line),
+        }
+    }
+    push_joined(&mut tweets, &current);
+
+    if next_number > 1 {
+        return tweets;
+    }
+
+    let separated: Vec<&str> = if text.lines().any(|line| line.trim() == "---") {
+        text.split("\n---").collect()
+    } else {
+        text.split("\n\n").collect()
+    };
+    separated
+        .iter()
+        .map(|part| part.trim().trim_start_matches("---").trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Splits an email sequence on its "EMAIL n:" headers and pulls out the
+/// SUBJECT, PREVIEW and SEND TIMING lines. Text without headers is one email.
+pub fn parse_email_sequence(text: &str) -> Vec<SequenceEmail> {
+    if let Ok(sequence) = serde_json::from_str::<JsonEmailSequence>(text) {
+        return sequence
+            .emails
+            .into_iter()
+            .map(|email| SequenceEmail {
+                number: email.email_number,
+                label: email.label,
+                subject: email.subject_line,
+                preview: email.preview_text,
+                send_timing: None,
+                body: match email.cta_text {
+                    Some(cta) if !cta.trim().is_empty() => format!("{}\n\n{}", email.body, cta),
+                    _ => email.body,
+                },
+            })
+            .collect();
+    }
+
+    let mut emails: Vec<SequenceEmail> = Vec::new();
+    let mut body: Vec<&str> = Vec::new();
+    let mut current = SequenceEmail {
+        number: 1,
+        label: None,
+        subject: None,
+        preview: None,
+        send_timing: None,
+        body: String::new(),
+    };
+    let mut has_header = false;
+
+    for line in text.lines() {
+        if let Some((number, label)) = email_header(line) {
+            if has_header || !is_blank(&body) {
+                current.body = trim_separators(&body.join("\n"));
+                emails.push(current);
+            }
+            current = SequenceEmail {
+                number,
+                label,
+                subject: None,
+                preview: None,
+                send_timing: None,
+                body: String::new(),
+            };
+            body.clear();
+            has_header = true;
+        } else if let Some(value) = field_value(line, "SUBJECT").filter(|_| current.subject.is_none()) {
+            current.subject = Some(value.to_string());
+        } else if let Some(value) = field_value(line, "PREVIEW").filter(|_| current.preview.is_none()) {
+            current.preview = Some(value.to_string());
+        } else if let Some(value) =
+            field_value(line, "SEND TIMING").filter(|_| current.send_timing.is_none())
+        {
+            current.send_timing = Some(value.to_string());
+        } else {
+            body.push(line);
+        }
+    }
+
+    current.body = trim_separators(&body.join("\n"));
+    if has_header || !current.body.is_empty() || current.subject.is_some() {
+        emails.push(current);
+    }
+
+    emails
+}
+
+/// Days after the first send that a SEND TIMING line names: "Day 3" is two
+/// days in, "3 days after signup" three, "Immediately" none. `None` when the
+/// timing gives no day.
+pub fn send_timing_day(timing: &str) -> Option<u32> {
+    let lower = timing.trim().to_lowercase();
+    if lower.starts_with("immediately") || lower.starts_with("right away") {
+        return Some(0);
+    }
+
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    for (i, word) in words.iter().enumerate() {
+        if *word == "day" {
+            if let Some(n) = words.get(i + 1).and_then(|w| w.parse::<u32>().ok()) {
+                return Some(n.saturating_sub(1));
+            }
+        }
+        if let Ok(n) = word.parse::<u32>() {
+            match words.get(i + 1) {
+                Some(&"day") | Some(&"days") => return Some(n),
+                Some(&"week") | Some(&"weeks") => return Some(n * 7),
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Splits the leading SUBJECT and PREVIEW lines off a newsletter.
+pub fn parse_newsletter(text: &str) -> NewsletterParts {
+    if let Ok(data) = serde_json::from_str::<JsonNewsletter>(text) {
+        return NewsletterParts {
+            subject: data.subject_line,
+            preview: data.preview_text,
+            body: data.body,
+        };
+    }
+
+    let mut subject = None;
+    let mut preview = None;
+    let mut body_start = 0;
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(value) = field_value(line, "SUBJECT").filter(|_| subject.is_none()) {
+            subject = Some(value.to_string());
+        } else if let Some(value) = field_value(line, "PREVIEW").filter(|_| preview.is_none()) {
+            preview = Some(value.to_string());
+        } else {
+            body_start = i;
+            break;
+        }
+        body_start = i + 1;
+    }
+
+    NewsletterParts {
+        subject,
+        preview,
+        body: text.lines().skip(body_start).collect::<Vec<_>>().join("\n").trim().to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonEmailSequence {
+    emails: Vec<JsonEmail>,
+}
+
+#[derive(Deserialize)]
+struct JsonEmail {
+    email_number: u32,
+    label: Option<String>,
+    subject_line: Option<String>,
+    preview_text: Option<String>,
+    body: String,
+    cta_text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonNewsletter {
+    subject_line: Option<String>,
+    preview_text: Option<String>,
+    body: String,
+}
+
+/// Parses a "3/" or "3/7" tweet number, returning it and the rest of the line.
+fn tweet_marker(line: &str) -> Option<(u32, &str)> {
+    let trimmed = line.trim_start();
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let number: u32 = trimmed[..digits].parse().ok()?;
+    let rest = trimmed[digits..].strip_prefix('/')?;
+    let rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    Some((number, rest.trim_start()))
+}
+
+/// Parses an "EMAIL 2:" header line (markdown emphasis allowed), returning the
+/// email number and any label after the colon.
+fn email_header(line: &str) -> Option<(u32, Option<String>)> {
+    let trimmed = strip_emphasis(line);
+    let rest = strip_prefix_ignore_case(trimmed, "EMAIL")?;
+    let rest = rest.trim_start();
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let number: u32 = rest[..digits].parse().ok()?;
+    let rest = rest[digits..].trim_start_matches('*').trim_start();
+
+    // "Email 2 goes out on Friday" in a body is not a header
+    let label = if rest.is_empty() {
+        rest
+    } else {
+        rest.strip_prefix([':', '—', '–', '-'])?
+            .trim_matches(|c: char| c == '*' || c.is_whitespace())
+    };
+
+    Some((number, (!label.is_empty()).then(|| label.to_string())))
+}
+
+/// The value of a "KEY: value" line, tolerating markdown emphasis around the key.
+fn field_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = strip_prefix_ignore_case(strip_emphasis(line), key)?;
+    let rest = rest.trim_start();
+    // "SUBJECT LINE:" and "PREVIEW TEXT:" are common variants
+    let rest = strip_prefix_ignore_case(rest, "LINE")
+        .or_else(|| strip_prefix_ignore_case(rest, "TEXT"))
+        .unwrap_or(rest);
+    let rest = rest.trim_start_matches('*').trim_start();
+    let rest = rest.strip_prefix(':')?;
+    Some(rest.trim_matches(|c: char| c == '*' || c.is_whitespace()))
+}
+
+fn strip_emphasis(line: &str) -> &str {
+    line.trim().trim_start_matches(['*', '#', '_']).trim_start()
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &text[prefix.len()..])
+}
+
+fn is_blank(lines: &[&str]) -> bool {
+    lines.iter().all(|line| line.trim().is_empty())
+}
+
+fn push_joined(parts: &mut Vec<String>, lines: &[&str]) {
+    let joined = trim_separators(&lines.join("\n"));
+    if !joined.is_empty() {
+        parts.push(joined);
+    }
+}
+
+/// Trims whitespace and "---" separator lines from both ends.
+fn trim_separators(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let is_filler = |line: &&str| {
+        let line = line.trim();
+        line.is_empty() || line.chars().all(|c| c == '-' || c == '—')
+    };
+    let start = lines.iter().position(|l| !is_filler(l)).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !is_filler(l)).map_or(start, |i| i + 1);
+    lines[start..end].join("\n")
+}
@@ -2,91 +2,549 @@ use printpdf::*;
 use std::collections::BTreeMap;
 
 use crate::errors::AppError;
-use crate::models::content::{ContentInput, RepurposedOutput};
+use crate::models::content::{ContentInput, HistoryDetail, RepurposedOutput};
+use crate::models::export::{PdfExport, PdfTemplate};
+use crate::services::output_text::{self, format_display_name};
+use crate::services::workspace;
 
-pub fn export_to_pdf(
-    input: &ContentInput,
-    outputs: &[RepurposedOutput],
-    output_path: &str,
-) -> Result<String, AppError> {
-    let title = input.title.as_deref().unwrap_or("Untitled Content");
-
-    let mut html = String::new();
-    html.push_str("<html><body>");
-    html.push_str(&format!("<h1>{}</h1>", escape_html(title)));
-    html.push_str(&format!(
-        "<p><small>Word Count: {} | Created: {}</small></p>",
-        input.word_count, input.created_at
-    ));
-
-    if let Some(ref url) = input.source_url {
-        html.push_str(&format!("<p><small>Source: {}</small></p>", escape_html(url)));
-    }
+const PAGE_WIDTH_MM: f32 = 210.0;
+const PAGE_HEIGHT_MM: f32 = 297.0;
+const MARGIN_TOP_MM: f32 = 20.0;
+const MARGIN_SIDE_MM: f32 = 15.0;
+const LOGO_MAX_WIDTH_MM: f32 = 40.0;
+const LOGO_MAX_HEIGHT_MM: f32 = 18.0;
 
-    html.push_str("<hr/>");
+/// Fonts shipped with the app so exports don't depend on what is installed:
+/// DejaVu Sans covers Latin, Greek, Cyrillic, Hebrew, Arabic and most
+/// symbols, Noto Sans JP covers CJK. Their licences are next to the files.
+const BUNDLED_FONTS: [(&str, &[u8]); 2] = [
+    ("DejaVu Sans", include_bytes!("../../fonts/DejaVuSans.ttf")),
+    ("Noto Sans JP", include_bytes!("../../fonts/NotoSansJP-Regular.otf")),
+];
 
-    for output in outputs {
-        let format_label = format_display_name(&output.format);
-        html.push_str(&format!("<h2>{}</h2>", escape_html(&format_label)));
-
-        let text = &output.output_text;
-        for line in text.split('\n') {
-            if line.trim().is_empty() {
-                html.push_str("<br/>");
-            } else {
-                html.push_str(&format!("<p>{}</p>", escape_html(line)));
-            }
-        }
+/// System fonts tried after the bundled ones. printpdf falls back per
+/// character along the CSS family list; outline emoji fonts are listed
+/// before colour ones, which embed as bitmaps poorly or not at all.
+const FALLBACK_FONTS: [&str; 12] = [
+    "Noto Sans",
+    "Liberation Sans",
+    "Segoe UI",
+    "Helvetica Neue",
+    "Arial",
+    "Arial Unicode MS",
+    "Noto Sans Symbols 2",
+    "Noto Emoji",
+    "Noto Color Emoji",
+    "Apple Color Emoji",
+    "Segoe UI Emoji",
+    "Segoe UI Symbol",
+];
 
-        html.push_str("<hr/>");
-    }
+/// Lays out one or more content inputs as a single PDF. Returns the bytes and
+/// any warnings printpdf reported.
+pub fn build_pdf(
+    details: &[HistoryDetail],
+    template: &PdfTemplate,
+) -> Result<(Vec<u8>, Vec<String>), AppError> {
+    let logo = match template.logo_path {
+        Some(ref path) => Some(load_logo(path)?),
+        None => None,
+    };
 
-    html.push_str("</body></html>");
+    let html = build_html(details, template, logo.is_some());
 
     let options = GeneratePdfOptions {
-        page_width: Some(210.0),
-        page_height: Some(297.0),
-        margin_top: Some(20.0),
-        margin_right: Some(15.0),
-        margin_bottom: Some(20.0),
-        margin_left: Some(15.0),
+        page_width: Some(PAGE_WIDTH_MM),
+        page_height: Some(PAGE_HEIGHT_MM),
+        margin_top: Some(MARGIN_TOP_MM),
+        margin_right: Some(MARGIN_SIDE_MM),
+        margin_bottom: Some(MARGIN_TOP_MM),
+        margin_left: Some(MARGIN_SIDE_MM),
+        show_page_numbers: Some(template.show_page_numbers),
+        header_text: template.header_text.clone().filter(|t| !t.trim().is_empty()),
+        footer_text: template.footer_text.clone().filter(|t| !t.trim().is_empty()),
+        skip_first_page: Some(template.cover_page),
         ..Default::default()
     };
 
     let mut warnings = Vec::new();
-    let doc = PdfDocument::from_html(
+    let fonts: BTreeMap<String, Base64OrRaw> = BUNDLED_FONTS
+        .iter()
+        .map(|(family, bytes)| (family.to_string(), Base64OrRaw::Raw(bytes.to_vec())))
+        .collect();
+    let mut doc = PdfDocument::from_html(
         &html,
         &BTreeMap::new(),
-        &BTreeMap::new(),
+        &fonts,
         &options,
         &mut warnings,
     )
     .map_err(|e| AppError::PdfExport(format!("Failed to generate PDF: {}", e)))?;
 
+    if doc.pages.is_empty() {
+        return Err(AppError::PdfExport(format!(
+            "Failed to lay out PDF: {}",
+            summarize_warnings(&warnings).join("; ")
+        )));
+    }
+
+    if let Some(ref logo) = logo {
+        place_logo(&mut doc, logo);
+    }
+
     let save_opts = PdfSaveOptions::default();
     let bytes = doc.save(&save_opts, &mut warnings);
 
+    Ok((bytes, summarize_warnings(&warnings)))
+}
+
+pub fn export_to_pdf(
+    details: &[HistoryDetail],
+    template: &PdfTemplate,
+    output_path: &str,
+) -> Result<PdfExport, AppError> {
+    let (bytes, warnings) = build_pdf(details, template)?;
+
     std::fs::write(output_path, &bytes)
         .map_err(|e| AppError::PdfExport(format!("Failed to write PDF file: {}", e)))?;
 
-    Ok(output_path.to_string())
+    Ok(PdfExport {
+        path: output_path.to_string(),
+        warnings,
+    })
+}
+
+/// The active workspace's PDF template, or the default one.
+pub fn load_template(conn: &rusqlite::Connection) -> PdfTemplate {
+    workspace::get_setting(conn, &workspace::active_workspace_id(conn), "pdf_template")
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Normalizes colours to "#rrggbb" and rejects logos that can't be drawn.
+pub fn validate_template(mut template: PdfTemplate) -> Result<PdfTemplate, AppError> {
+    template.accent_color = normalize_color(&template.accent_color)?;
+    template.text_color = normalize_color(&template.text_color)?;
+
+    template.logo_path = template.logo_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(ref path) = template.logo_path {
+        load_logo(path)?;
+    }
+
+    Ok(template)
+}
+
+/// "#rgb" or "#rrggbb", in either case, as lowercase "#rrggbb".
+pub fn normalize_color(color: &str) -> Result<String, AppError> {
+    let hex = color.trim().trim_start_matches('#');
+    let valid = matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(AppError::Validation(format!(
+            "Invalid colour '{}', expected #rgb or #rrggbb",
+            color
+        )));
+    }
+
+    let hex = if hex.len() == 3 {
+        hex.chars().flat_map(|c| [c, c]).collect()
+    } else {
+        hex.to_string()
+    };
+    Ok(format!("#{}", hex.to_ascii_lowercase()))
+}
+
+/// Checks that a logo file exists and is an image printpdf can decode.
+pub fn load_logo(path: &str) -> Result<RawImage, AppError> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| AppError::FileIo(format!("Failed to read logo '{}': {}", path, e)))?;
+    RawImage::decode_from_bytes(&bytes, &mut Vec::new())
+        .map_err(|e| AppError::Validation(format!("Unsupported logo image '{}': {}", path, e)))
 }
 
-fn format_display_name(format: &str) -> String {
-    match format {
-        "twitter_thread" => "Twitter/X Thread".to_string(),
-        "linkedin" => "LinkedIn Post".to_string(),
-        "instagram" => "Instagram Caption".to_string(),
-        "newsletter" => "Newsletter".to_string(),
-        "email_sequence" => "Email Sequence".to_string(),
-        "summary" => "Summary".to_string(),
-        other => other.to_string(),
+fn build_html(details: &[HistoryDetail], template: &PdfTemplate, has_logo: bool) -> String {
+    let mut html = String::new();
+    html.push_str("<html><head><style>");
+    html.push_str(&stylesheet(template));
+    html.push_str("</style></head><body>");
+
+    if has_logo {
+        // Reserves the space the logo is drawn into after layout
+        html.push_str("<div class=\"logo-space\"></div>");
+    }
+
+    for (i, detail) in details.iter().enumerate() {
+        // Every input after the first starts on a new page
+        if i == 0 {
+            html.push_str("<div>");
+        } else {
+            html.push_str("<div class=\"item\">");
+        }
+        html.push_str(&render_item(&detail.input, &detail.outputs, template));
+        html.push_str("</div>");
     }
+
+    html.push_str("</body></html>");
+    html
 }
 
-fn escape_html(s: &str) -> String {
+fn render_item(input: &ContentInput, outputs: &[RepurposedOutput], template: &PdfTemplate) -> String {
+    let title = input.title.as_deref().unwrap_or("Untitled Content");
+    let mut html = String::new();
+
+    let mut meta = format!(
+        "Word Count: {} | Created: {}",
+        input.word_count,
+        input.created_at.get(..10).unwrap_or(&input.created_at)
+    );
+    if let Some(ref url) = input.source_url {
+        meta.push_str(&format!(" | Source: {}", url));
+    }
+
+    if template.cover_page {
+        html.push_str("<div class=\"cover\">");
+        html.push_str(&format!("<h1 class=\"cover-title\">{}</h1>", escape_html(title)));
+        html.push_str("<p class=\"cover-subtitle\">Content Package</p>");
+        html.push_str(&format!("<p class=\"meta\">{}</p>", escape_html(&meta)));
+        for output in outputs {
+            html.push_str(&format!(
+                "<p class=\"cover-format\">{}</p>",
+                escape_html(&format_display_name(&output.format))
+            ));
+        }
+        html.push_str("</div>");
+    } else {
+        html.push_str(&format!("<h1>{}</h1>", escape_html(title)));
+        html.push_str(&format!("<p class=\"meta\">{}</p>", escape_html(&meta)));
+        html.push_str("<hr/>");
+    }
+
+    for output in outputs {
+        html.push_str("<div class=\"section\">");
+        html.push_str(&format!(
+            "<h2>{}</h2>",
+            escape_html(&format_display_name(&output.format))
+        ));
+        html.push_str(&render_output(output));
+        html.push_str("</div>");
+    }
+
+    html
+}
+
+fn stylesheet(template: &PdfTemplate) -> String {
+    let mut families: Vec<String> = Vec::new();
+    if let Some(ref family) = template.font_family {
+        if !family.trim().is_empty() {
+            families.push(format!("'{}'", family.trim().replace('\'', "")));
+        }
+    }
+    families.extend(
+        BUNDLED_FONTS
+            .iter()
+            .map(|(family, _)| *family)
+            .chain(FALLBACK_FONTS)
+            .map(|f| format!("'{}'", f)),
+    );
+    families.push("sans-serif".to_string());
+
+    let accent = &template.accent_color;
+    let text = &template.text_color;
+
+    format!(
+        "body {{ font-family: {fonts}; color: {text}; font-size: 11pt; line-height: 1.5; }}
+        h1 {{ color: {accent}; font-size: 22pt; margin: 0 0 4mm 0; }}
+        h2 {{ color: {accent}; font-size: 15pt; border-bottom: 1px solid {accent}; padding-bottom: 2mm; margin: 8mm 0 4mm 0; }}
+        p {{ margin: 0 0 2.5mm 0; }}
+        hr {{ border-top: 1px solid {accent}; margin: 4mm 0; }}
+        .meta {{ color: #666666; font-size: 9pt; }}
+        .logo-space {{ height: {logo}mm; }}
+        .cover {{ padding-top: 50mm; page-break-after: always; }}
+        .cover-title {{ font-size: 30pt; }}
+        .cover-subtitle {{ color: {accent}; font-size: 14pt; margin-bottom: 10mm; }}
+        .cover-format {{ font-size: 11pt; border-left: 3px solid {accent}; padding-left: 3mm; }}
+        .item {{ page-break-before: always; }}
+        .section {{ margin-bottom: 6mm; }}
+        .tweet {{ border: 1px solid #d0d7de; border-radius: 6px; padding: 3mm 4mm; margin-bottom: 3mm; }}
+        .tweet-count {{ color: #888888; font-size: 8pt; }}
+        .email {{ margin-bottom: 6mm; }}
+        .subject-block {{ background-color: #f4f6f8; border-left: 4px solid {accent}; padding: 3mm 4mm; margin-bottom: 3mm; }}
+        .subject-block p {{ margin: 0 0 1mm 0; }}
+        .email-heading {{ color: {accent}; font-weight: bold; }}
+        .subject {{ font-weight: bold; }}
+        .detail {{ color: #555555; font-size: 9pt; }}
+        .post {{ border-left: 3px solid {accent}; padding-left: 4mm; }}",
+        fonts = families.join(", "),
+        text = text,
+        accent = accent,
+        logo = LOGO_MAX_HEIGHT_MM + 4.0,
+    )
+}
+
+/// Threads render as one card per tweet and email sequences with a subject
+/// block per email; social posts get a rule down the side.
+fn render_output(output: &RepurposedOutput) -> String {
+    let mut html = String::new();
+
+    match output.format.as_str() {
+        "twitter_thread" => {
+            let tweets = output_text::split_tweets(&output.output_text);
+            for (i, tweet) in tweets.iter().enumerate() {
+                html.push_str("<div class=\"tweet\">");
+                html.push_str(&paragraphs(tweet));
+                html.push_str(&format!(
+                    "<p class=\"tweet-count\">{}/{} · {} characters</p>",
+                    i + 1,
+                    tweets.len(),
+                    tweet.chars().count()
+                ));
+                html.push_str("</div>");
+            }
+        }
+        "email_sequence" => {
+            for email in output_text::parse_email_sequence(&output.output_text) {
+                let heading = match email.label {
+                    Some(ref label) => format!("Email {}: {}", email.number, label),
+                    None => format!("Email {}", email.number),
+                };
+                html.push_str("<div class=\"email\"><div class=\"subject-block\">");
+                html.push_str(&format!("<p class=\"email-heading\">{}</p>", escape_html(&heading)));
+                html.push_str(&subject_lines(
+                    email.subject.as_deref(),
+                    email.preview.as_deref(),
+                    email.send_timing.as_deref(),
+                ));
+                html.push_str("</div>");
+                html.push_str(&paragraphs(&email.body));
+                html.push_str("</div>");
+            }
+        }
+        "newsletter" => {
+            let newsletter = output_text::parse_newsletter(&output.output_text);
+            if newsletter.subject.is_some() || newsletter.preview.is_some() {
+                html.push_str("<div class=\"subject-block\">");
+                html.push_str(&subject_lines(
+                    newsletter.subject.as_deref(),
+                    newsletter.preview.as_deref(),
+                    None,
+                ));
+                html.push_str("</div>");
+            }
+            html.push_str(&paragraphs(&newsletter.body));
+        }
+        "linkedin" | "instagram" => {
+            html.push_str("<div class=\"post\">");
+            html.push_str(&paragraphs(&output.output_text));
+            html.push_str("</div>");
+        }
+        _ => html.push_str(&paragraphs(&output.output_text)),
+    }
+
+    html
+}
+
+fn subject_lines(subject: Option<&str>, preview: Option<&str>, send_timing: Option<&str>) -> String {
+    let mut html = String::new();
+    if let Some(subject) = subject {
+        html.push_str(&format!(
+            "<p class=\"subject\">Subject: {}</p>",
+            escape_html(subject)
+        ));
+    }
+    if let Some(preview) = preview {
+        html.push_str(&format!(
+            "<p class=\"detail\">Preview: {}</p>",
+            escape_html(preview)
+        ));
+    }
+    if let Some(timing) = send_timing {
+        html.push_str(&format!(
+            "<p class=\"detail\">Send timing: {}</p>",
+            escape_html(timing)
+        ));
+    }
+    html
+}
+
+/// One paragraph per block of text, with single line breaks kept.
+fn paragraphs(text: &str) -> String {
+    text.trim()
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| {
+            let lines: Vec<String> = block.lines().map(|line| escape_html(line.trim_end())).collect();
+            format!("<p>{}</p>", lines.join("<br/>"))
+        })
+        .collect()
+}
+
+/// Draws the logo into the space reserved at the top of the first page,
+/// scaled to fit within the maximum logo size.
+fn place_logo(doc: &mut PdfDocument, logo: &RawImage) {
+    if logo.width == 0 || logo.height == 0 {
+        return;
+    }
+
+    let max_width = Pt::from(Mm(LOGO_MAX_WIDTH_MM)).0;
+    let max_height = Pt::from(Mm(LOGO_MAX_HEIGHT_MM)).0;
+    let scale = (max_width / logo.width as f32).min(max_height / logo.height as f32);
+    let height = logo.height as f32 * scale;
+    // At 72 dpi one pixel is one point, so the dpi sets the scale directly
+    let dpi = 72.0 / scale;
+
+    let image_id = doc.add_image(logo);
+    let x = Pt::from(Mm(MARGIN_SIDE_MM));
+    let y = Pt(Pt::from(Mm(PAGE_HEIGHT_MM - MARGIN_TOP_MM)).0 - height);
+
+    doc.pages[0].ops.push(Op::UseXobject {
+        id: image_id,
+        transform: XObjectTransform {
+            translate_x: Some(x),
+            translate_y: Some(y),
+            dpi: Some(dpi),
+            ..Default::default()
+        },
+    });
+}
+
+/// Warnings and errors from layout and saving, deduplicated. Informational
+/// messages are dropped.
+fn summarize_warnings(warnings: &[PdfWarnMsg]) -> Vec<String> {
+    let mut messages: Vec<String> = Vec::new();
+    for warning in warnings {
+        if warning.severity == PdfParseErrorSeverity::Info {
+            continue;
+        }
+        let message = if warning.page > 0 {
+            format!("Page {}: {}", warning.page, warning.msg)
+        } else {
+            warning.msg.clone()
+        };
+        if !messages.contains(&message) {
+            messages.push(message);
+        }
+    }
+    messages
+}
+
+/// Escapes text for HTML element content and double-quoted attributes.
+pub fn escape_html(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
         .replace('"', "&quot;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detail(title: &str, output_text: &str) -> HistoryDetail {
+        HistoryDetail {
+            input: ContentInput {
+                id: "input-1".to_string(),
+                source_url: None,
+                raw_text: output_text.to_string(),
+                title: Some(title.to_string()),
+                word_count: 3,
+                created_at: "2026-01-02T03:04:05Z".to_string(),
+            },
+            outputs: vec![RepurposedOutput {
+                id: "output-1".to_string(),
+                content_input_id: "input-1".to_string(),
+                format: "short_summary".to_string(),
+                output_text: output_text.to_string(),
+                created_at: "2026-01-02T03:04:05Z".to_string(),
+                voice_version_id: None,
+                voice_score: None,
+                phrase_flags: Vec::new(),
+                approved_at: None,
+                tone: None,
+                length: None,
+            }],
+        }
+    }
+
+    fn logo(width: usize, height: usize) -> RawImage {
+        RawImage {
+            pixels: RawImageData::U8(vec![0; width * height * 3]),
+            width,
+            height,
+            data_format: RawImageFormat::RGB8,
+            tag: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn normalize_color_expands_and_lowercases() {
+        assert_eq!(normalize_color("#ABC").unwrap(), "#aabbcc");
+        assert_eq!(normalize_color(" 1A5276 ").unwrap(), "#1a5276");
+        assert!(normalize_color("#12345").is_err());
+        assert!(normalize_color("#ggg").is_err());
+        assert!(normalize_color("").is_err());
+    }
+
+    #[test]
+    fn place_logo_scales_into_reserved_space() {
+        let mut doc = PdfDocument::new("test");
+        doc.pages.push(PdfPage::new(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), Vec::new()));
+
+        // Twice as wide as tall, so the height limit decides the scale
+        place_logo(&mut doc, &logo(200, 100));
+
+        let max_height = Pt::from(Mm(LOGO_MAX_HEIGHT_MM)).0;
+        let scale = max_height / 100.0;
+        match doc.pages[0].ops.last() {
+            Some(Op::UseXobject { transform, .. }) => {
+                let dpi = transform.dpi.unwrap();
+                assert!((dpi - 72.0 / scale).abs() < 0.01);
+                assert_eq!(transform.translate_x, Some(Pt::from(Mm(MARGIN_SIDE_MM))));
+                let top = Pt::from(Mm(PAGE_HEIGHT_MM - MARGIN_TOP_MM)).0;
+                assert!((transform.translate_y.unwrap().0 - (top - max_height)).abs() < 0.01);
+            }
+            other => panic!("expected the logo to be drawn, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn place_logo_skips_empty_images() {
+        let mut doc = PdfDocument::new("test");
+        doc.pages.push(PdfPage::new(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), Vec::new()));
+        place_logo(&mut doc, &logo(0, 10));
+        assert!(doc.pages[0].ops.is_empty());
+    }
+
+    #[test]
+    fn summarize_warnings_drops_info_and_duplicates() {
+        let warnings = vec![
+            PdfWarnMsg::info(0, 0, "parsed".to_string()),
+            PdfWarnMsg::warning(2, 0, "missing glyph".to_string()),
+            PdfWarnMsg::warning(2, 5, "missing glyph".to_string()),
+            PdfWarnMsg::error(0, 0, "bad font".to_string()),
+        ];
+        assert_eq!(
+            summarize_warnings(&warnings),
+            vec!["Page 2: missing glyph".to_string(), "bad font".to_string()]
+        );
+    }
+
+    #[test]
+    fn non_latin_text_round_trips() {
+        let template = PdfTemplate {
+            cover_page: false,
+            ..PdfTemplate::default()
+        };
+        let (bytes, _) =
+            build_pdf(&[detail("Привет, мир", "Καλημέρα κόσμε")], &template).unwrap();
+
+        let doc = PdfDocument::parse(&bytes, &PdfParseOptions::default(), &mut Vec::new()).unwrap();
+        // Extracted text has a line break after every glyph
+        let text: String = doc
+            .extract_text()
+            .concat()
+            .concat()
+            .replace("\r\n", "");
+        assert!(text.contains("Привет, мир"), "{}", text);
+        assert!(text.contains("Καλημέρα κόσμε"), "{}", text);
+    }
+}
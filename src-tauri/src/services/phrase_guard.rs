@@ -0,0 +1,340 @@
+use crate::errors::AppError;
+use crate::models::content::FlaggedPhrase;
+
+/// How many times an output is sent back to the model to remove banned phrases
+/// before the remaining matches are flagged instead.
+pub const MAX_FIX_ATTEMPTS: usize = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnforcementMode {
+    /// Ask the model to rewrite the output, then flag whatever remains.
+    Reask,
+    /// Only flag matches.
+    Flag,
+}
+
+impl EnforcementMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnforcementMode::Reask => "reask",
+            EnforcementMode::Flag => "flag",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value {
+            "reask" => Ok(EnforcementMode::Reask),
+            "flag" => Ok(EnforcementMode::Flag),
+            other => Err(AppError::Validation(format!(
+                "Unknown phrase enforcement mode '{}' (expected 'reask' or 'flag')",
+                other
+            ))),
+        }
+    }
+}
+
+/// A phrase the output must not contain, and whether it came from the voice
+/// or the global banned list.
+#[derive(Debug, Clone)]
+pub struct BannedPhrase {
+    pub phrase: String,
+    pub source: &'static str,
+}
+
+/// Avoid-phrases from the voice followed by the global banned list, without
+/// blanks or case-insensitive repeats.
+pub fn collect_banned(avoid_phrases: &[String], global: &[String]) -> Vec<BannedPhrase> {
+    let mut banned: Vec<BannedPhrase> = Vec::new();
+    let sources = avoid_phrases
+        .iter()
+        .map(|p| (p, "voice"))
+        .chain(global.iter().map(|p| (p, "global")));
+
+    for (phrase, source) in sources {
+        let phrase = phrase.trim();
+        if phrase.is_empty() || banned.iter().any(|b| b.phrase.eq_ignore_ascii_case(phrase)) {
+            continue;
+        }
+        banned.push(BannedPhrase {
+            phrase: phrase.to_string(),
+            source,
+        });
+    }
+
+    banned
+}
+
+pub fn load_global_banned(conn: &rusqlite::Connection) -> Vec<String> {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'banned_phrases'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+pub fn load_mode(conn: &rusqlite::Connection) -> EnforcementMode {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'phrase_enforcement_mode'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|value| EnforcementMode::parse(&value).ok())
+    .unwrap_or(EnforcementMode::Reask)
+}
+
+/// Finds every occurrence of the banned phrases, matching case-insensitively
+/// and allowing simple inflections of each word ("leverage" matches
+/// "leveraged", "leveraging", "leverages").
+pub fn find_banned(text: &str, banned: &[BannedPhrase]) -> Vec<FlaggedPhrase> {
+    let tokens = tokenize(text);
+    let mut flags = Vec::new();
+
+    for banned_phrase in banned {
+        let phrase_words: Vec<String> = tokenize(&banned_phrase.phrase)
+            .into_iter()
+            .map(|t| t.word)
+            .collect();
+        if phrase_words.is_empty() || phrase_words.len() > tokens.len() {
+            continue;
+        }
+
+        for window in tokens.windows(phrase_words.len()) {
+            let matches = window
+                .iter()
+                .zip(&phrase_words)
+                .all(|(token, word)| inflection_matches(word, &token.word));
+            if !matches {
+                continue;
+            }
+
+            let start = window[0].start;
+            let end = window[window.len() - 1].end;
+            flags.push(FlaggedPhrase {
+                phrase: banned_phrase.phrase.clone(),
+                matched_text: text[start..end].to_string(),
+                start: utf16_offset(text, start),
+                end: utf16_offset(text, end),
+                source: banned_phrase.source.to_string(),
+            });
+        }
+    }
+
+    flags.sort_by_key(|f| f.start);
+    flags
+}
+
+/// The distinct banned phrases behind a set of flags, in order of appearance.
+pub fn distinct_phrases(flags: &[FlaggedPhrase]) -> Vec<String> {
+    let mut phrases: Vec<String> = Vec::new();
+    for flag in flags {
+        if !phrases.contains(&flag.phrase) {
+            phrases.push(flag.phrase.clone());
+        }
+    }
+    phrases
+}
+
+struct Token {
+    word: String,
+    start: usize,
+    end: usize,
+}
+
+/// Splits text into lowercased words (letters, digits and inner apostrophes)
+/// with their byte ranges.
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '\'' || c == '’';
+    for (i, c) in text.char_indices() {
+        match (is_word_char(c), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                push_token(&mut tokens, text, s, i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        push_token(&mut tokens, text, s, text.len());
+    }
+
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<Token>, text: &str, start: usize, end: usize) {
+    let raw = &text[start..end];
+    let trimmed = raw.trim_matches(|c| c == '\'' || c == '’');
+    if trimmed.is_empty() {
+        return;
+    }
+    let offset = raw.find(trimmed).unwrap_or(0);
+    tokens.push(Token {
+        word: trimmed.replace('’', "'").to_lowercase(),
+        start: start + offset,
+        end: start + offset + trimmed.len(),
+    });
+}
+
+/// True if `candidate` is `base` or a regular inflection of it. Very short
+/// words only match exactly.
+fn inflection_matches(base: &str, candidate: &str) -> bool {
+    if base == candidate {
+        return true;
+    }
+    base.chars().count() >= 3 && inflections(base).iter().any(|form| form == candidate)
+}
+
+fn inflections(base: &str) -> Vec<String> {
+    let mut forms = vec![
+        format!("{}s", base),
+        format!("{}es", base),
+        format!("{}ed", base),
+        format!("{}ing", base),
+        format!("{}'s", base),
+    ];
+
+    if let Some(stem) = base.strip_suffix('e') {
+        forms.push(format!("{}d", base));
+        forms.push(format!("{}ing", stem));
+    }
+
+    if let Some(stem) = base.strip_suffix('y') {
+        forms.push(format!("{}ies", stem));
+        forms.push(format!("{}ied", stem));
+    }
+
+    // Doubled final consonant: "ship" -> "shipped", "shipping"
+    let chars: Vec<char> = base.chars().collect();
+    if let [.., a, b, c] = chars[..] {
+        let vowel = |ch: char| "aeiou".contains(ch);
+        if !vowel(a) && vowel(b) && !vowel(c) && !"wxy".contains(c) {
+            forms.push(format!("{}{}ed", base, c));
+            forms.push(format!("{}{}ing", base, c));
+        }
+    }
+
+    forms
+}
+
+fn utf16_offset(text: &str, byte_offset: usize) -> u32 {
+    text[..byte_offset].encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn banned(phrases: &[&str]) -> Vec<BannedPhrase> {
+        phrases
+            .iter()
+            .map(|phrase| BannedPhrase {
+                phrase: phrase.to_string(),
+                source: "global",
+            })
+            .collect()
+    }
+
+    fn matched(text: &str, phrases: &[&str]) -> Vec<String> {
+        find_banned(text, &banned(phrases))
+            .into_iter()
+            .map(|flag| flag.matched_text)
+            .collect()
+    }
+
+    #[test]
+    fn matches_inflections_case_insensitively() {
+        assert_eq!(
+            matched(
+                "Leverage it. We leveraged, are leveraging and he leverages.",
+                &["leverage"]
+            ),
+            vec!["Leverage", "leveraged", "leveraging", "leverages"]
+        );
+        assert_eq!(
+            matched("We shipped fast and kept shipping; ships sail.", &["ship"]),
+            vec!["shipped", "shipping", "ships"]
+        );
+        assert_eq!(
+            matched("It simplifies things; we simplified it.", &["simplify"]),
+            vec!["simplifies", "simplified"]
+        );
+        assert_eq!(
+            matched("Let's Deep-Dive into a deep  dives session.", &["deep dive"]),
+            vec!["Deep-Dive", "deep  dives"]
+        );
+        // Curly apostrophes count as straight ones for possessives
+        assert_eq!(
+            matched("The game’s changer: a game changer.", &["game-changer"]),
+            vec!["game’s changer", "game changer"]
+        );
+    }
+
+    #[test]
+    fn leaves_other_words_alone() {
+        assert!(matched("A leverageable position.", &["leverage"]).is_empty());
+        assert!(matched("Shipment delayed.", &["ship"]).is_empty());
+        // Words under three letters only match exactly
+        assert!(matched("Gone to the ones.", &["go", "on"]).is_empty());
+        assert!(matched("Anything", &[" ", ""]).is_empty());
+    }
+
+    #[test]
+    fn reports_utf16_offsets() {
+        let text = "🚀 Café teams leverage synergy";
+        let flags = find_banned(text, &banned(&["leverage synergy", "café"]));
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+
+        let spans: Vec<(u32, u32, &str)> = flags
+            .iter()
+            .map(|flag| (flag.start, flag.end, flag.matched_text.as_str()))
+            .collect();
+        assert_eq!(spans, vec![(3, 7, "Café"), (14, 30, "leverage synergy")]);
+
+        // The offsets slice the same text out of a JavaScript string
+        for flag in &flags {
+            let slice = String::from_utf16(&utf16[flag.start as usize..flag.end as usize]).unwrap();
+            assert_eq!(slice, flag.matched_text);
+        }
+    }
+
+    #[test]
+    fn collects_voice_phrases_before_global_ones() {
+        let voice = vec![" Synergy ".to_string(), "".to_string(), "circle back".to_string()];
+        let global = vec![
+            "synergy".to_string(),
+            "Circle Back".to_string(),
+            "  ".to_string(),
+            "leverage".to_string(),
+        ];
+
+        let collected: Vec<(String, &str)> = collect_banned(&voice, &global)
+            .into_iter()
+            .map(|b| (b.phrase, b.source))
+            .collect();
+        assert_eq!(
+            collected,
+            vec![
+                ("Synergy".to_string(), "voice"),
+                ("circle back".to_string(), "voice"),
+                ("leverage".to_string(), "global"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_distinct_phrases_in_order() {
+        let flags = find_banned(
+            "Leverage synergy, then leverage more synergy.",
+            &banned(&["synergy", "leverage"]),
+        );
+        assert_eq!(flags.len(), 4);
+        assert_eq!(distinct_phrases(&flags), vec!["leverage", "synergy"]);
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rusqlite::params;
+
+use crate::errors::AppError;
+use crate::models::content::RepurposedOutput;
+use crate::models::schedule::ScheduleEntry;
+use crate::services::{output_text, workspace};
+
+const PREVIEW_CHARS: usize = 140;
+
+/// Scheduled times keyed by output id and part number, `None` standing for the
+/// whole output.
+pub type ScheduledTimes = HashMap<(String, Option<u32>), String>;
+
+/// A date alone is scheduled for this hour, UTC.
+const DEFAULT_HOUR: u32 = 9;
+
+const ENTRY_SELECT: &str = "SELECT se.id, se.output_id, ro.content_input_id, ci.title, ro.format, \
+     se.part_number, se.scheduled_at, se.channel, ro.output_text, se.created_at, se.updated_at \
+     FROM schedule_entries se \
+     JOIN repurposed_outputs ro ON ro.id = se.output_id \
+     JOIN content_inputs ci ON ci.id = ro.content_input_id";
+
+/// Parses RFC 3339, "YYYY-MM-DDTHH:MM[:SS]" (taken as UTC) or a bare date.
+pub fn parse_datetime(value: &str) -> Result<DateTime<Utc>, AppError> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.to_utc());
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(dt.and_utc());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let time = NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0).unwrap_or_default();
+        return Ok(date.and_time(time).and_utc());
+    }
+
+    Err(AppError::Validation(format!(
+        "Invalid date/time '{}', expected RFC 3339 or YYYY-MM-DD",
+        value
+    )))
+}
+
+/// The stored form, which sorts and compares correctly as text.
+pub fn format_datetime(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// The channel an output's format is written for.
+pub fn default_channel(format: &str) -> &'static str {
+    match format {
+        "twitter_thread" => "twitter",
+        "linkedin" => "linkedin",
+        "instagram" => "instagram",
+        "newsletter" | "email_sequence" => "email",
+        _ => "other",
+    }
+}
+
+pub fn normalize_channel(channel: Option<&str>, format: &str) -> Result<String, AppError> {
+    match channel.map(str::trim) {
+        None => Ok(default_channel(format).to_string()),
+        Some("") => Err(AppError::Validation("Channel cannot be empty".to_string())),
+        Some(channel) => Ok(channel.to_lowercase()),
+    }
+}
+
+/// Tweets of a thread or emails of a sequence; `None` for single-piece formats.
+pub fn parts(format: &str, text: &str) -> Option<Vec<String>> {
+    match format {
+        "twitter_thread" => Some(output_text::split_tweets(text)),
+        "email_sequence" => Some(
+            output_text::parse_email_sequence(text)
+                .into_iter()
+                .map(|email| match email.subject {
+                    Some(subject) => format!("{}\n\n{}", subject, email.body),
+                    None => email.body,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+pub fn validate_part(output: &RepurposedOutput, part_number: Option<u32>) -> Result<(), AppError> {
+    let Some(number) = part_number else {
+        return Ok(());
+    };
+    let count = parts(&output.format, &output.output_text).map(|p| p.len()).ok_or_else(|| {
+        AppError::Validation(format!(
+            "Only threads and email sequences can be scheduled in parts, not '{}'",
+            output.format
+        ))
+    })?;
+    if number == 0 || number as usize > count {
+        return Err(AppError::Validation(format!(
+            "Part {} does not exist; this output has {} parts",
+            number, count
+        )));
+    }
+    Ok(())
+}
+
+/// Times for every part of a thread or sequence, starting at `start`. Emails
+/// whose SEND TIMING names a day go out that many days after the start at the
+/// same time of day; other parts follow the previous one after `interval`.
+pub fn spread_times(
+    output: &RepurposedOutput,
+    start: DateTime<Utc>,
+    interval: Duration,
+) -> Result<Vec<(u32, DateTime<Utc>)>, AppError> {
+    let count = match parts(&output.format, &output.output_text).map(|p| p.len()) {
+        None => {
+            return Err(AppError::Validation(
+                "Only threads and email sequences can be spread across days".to_string(),
+            ))
+        }
+        Some(0) if output.format == "email_sequence" => {
+            return Err(AppError::Validation("No emails found in this sequence".to_string()))
+        }
+        Some(0) => return Err(AppError::Validation("No tweets found in this thread".to_string())),
+        Some(count) => count,
+    };
+
+    let day_offsets: Vec<Option<u32>> = if output.format == "email_sequence" {
+        output_text::parse_email_sequence(&output.output_text)
+            .iter()
+            .map(|email| email.send_timing.as_deref().and_then(output_text::send_timing_day))
+            .collect()
+    } else {
+        vec![None; count]
+    };
+
+    let mut times = Vec::with_capacity(count);
+    let mut previous: Option<DateTime<Utc>> = None;
+    for (i, offset) in day_offsets.into_iter().enumerate() {
+        let at = match (offset, previous) {
+            (Some(days), _) => start + Duration::days(days as i64),
+            (None, Some(previous)) => previous + interval,
+            (None, None) => start,
+        };
+        times.push((i as u32 + 1, at));
+        previous = Some(at);
+    }
+    Ok(times)
+}
+
+/// Creates or moves the slot for an output, or one part of it. Returns its id.
+pub fn upsert_entry(
+    conn: &rusqlite::Connection,
+    output_id: &str,
+    part_number: Option<u32>,
+    scheduled_at: &DateTime<Utc>,
+    channel: &str,
+) -> Result<String, AppError> {
+    let now = Utc::now().to_rfc3339();
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM schedule_entries WHERE output_id = ?1 AND part_number IS ?2",
+            params![output_id, part_number],
+            |row| row.get(0),
+        )
+        .ok();
+
+    match existing {
+        Some(id) => {
+            conn.execute(
+                "UPDATE schedule_entries SET scheduled_at = ?1, channel = ?2, updated_at = ?3 WHERE id = ?4",
+                params![format_datetime(scheduled_at), channel, now, id],
+            )?;
+            Ok(id)
+        }
+        None => {
+            let id = uuid::Uuid::new_v4().to_string();
+            conn.execute(
+                "INSERT INTO schedule_entries (id, output_id, part_number, scheduled_at, channel, created_at, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+                params![id, output_id, part_number, format_datetime(scheduled_at), channel, now],
+            )?;
+            Ok(id)
+        }
+    }
+}
+
+/// A slot in the active workspace.
+pub fn load_entry(conn: &rusqlite::Connection, id: &str) -> Result<ScheduleEntry, AppError> {
+    conn.query_row(
+        &format!("{} WHERE se.id = ?1 AND ci.workspace_id = ?2", ENTRY_SELECT),
+        params![id, workspace::active_workspace_id(conn)],
+        entry_from_row,
+    )
+    .map_err(|_| AppError::NotFound(format!("Schedule entry '{}' not found", id)))
+}
+
+/// Slots in the active workspace from `from` up to but not including `to`.
+pub fn list_entries(
+    conn: &rusqlite::Connection,
+    from: &DateTime<Utc>,
+    to: &DateTime<Utc>,
+) -> Result<Vec<ScheduleEntry>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE ci.workspace_id = ?1 AND se.scheduled_at >= ?2 AND se.scheduled_at < ?3 \
+         ORDER BY se.scheduled_at ASC, se.part_number ASC",
+        ENTRY_SELECT
+    ))?;
+    let entries = stmt
+        .query_map(
+            params![
+                workspace::active_workspace_id(conn),
+                format_datetime(from),
+                format_datetime(to)
+            ],
+            entry_from_row,
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(entries)
+}
+
+/// Scheduled times of the given outputs, for filling export columns.
+pub fn scheduled_times(
+    conn: &rusqlite::Connection,
+    output_ids: &[String],
+) -> Result<ScheduledTimes, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT part_number, scheduled_at FROM schedule_entries WHERE output_id = ?1",
+    )?;
+    let mut times = ScheduledTimes::new();
+    for output_id in output_ids {
+        let rows = stmt
+            .query_map(params![output_id], |row| {
+                Ok((row.get::<_, Option<u32>>(0)?, row.get::<_, String>(1)?))
+            })?
+            .filter_map(|r| r.ok());
+        for (part_number, scheduled_at) in rows {
+            times.insert((output_id.clone(), part_number), scheduled_at);
+        }
+    }
+    Ok(times)
+}
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<ScheduleEntry> {
+    let format: String = row.get(4)?;
+    let part_number: Option<u32> = row.get(5)?;
+    let output_text: String = row.get(8)?;
+
+    let text = match part_number {
+        Some(number) => parts(&format, &output_text)
+            .and_then(|parts| parts.into_iter().nth((number as usize).saturating_sub(1)))
+            .unwrap_or_default(),
+        None => output_text,
+    };
+
+    Ok(ScheduleEntry {
+        id: row.get(0)?,
+        output_id: row.get(1)?,
+        content_input_id: row.get(2)?,
+        content_title: row.get(3)?,
+        format,
+        part_number,
+        scheduled_at: row.get(6)?,
+        channel: row.get(7)?,
+        preview: preview(&text),
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+fn preview(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= PREVIEW_CHARS {
+        return flat;
+    }
+    let mut cut: String = flat.chars().take(PREVIEW_CHARS).collect();
+    cut.push('…');
+    cut
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn output(format: &str, text: &str) -> RepurposedOutput {
+        RepurposedOutput {
+            id: format!("{}-id", format),
+            content_input_id: "input-id".to_string(),
+            format: format.to_string(),
+            output_text: text.to_string(),
+            created_at: "2026-01-02T03:04:05+00:00".to_string(),
+            voice_version_id: None,
+            voice_score: None,
+            phrase_flags: Vec::new(),
+            approved_at: None,
+            tone: None,
+            length: None,
+        }
+    }
+
+    fn validation_message(error: AppError) -> String {
+        match error {
+            AppError::Validation(message) => message,
+            other => panic!("expected a validation error, got {}", other),
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_rfc3339_naive_and_bare_dates() {
+        assert_eq!(parse_datetime("2026-03-02T10:30:00+02:00").unwrap(), at(2, 8, 30));
+        assert_eq!(parse_datetime("2026-03-02T10:30:00Z").unwrap(), at(2, 10, 30));
+        assert_eq!(parse_datetime("2026-03-02T10:30").unwrap(), at(2, 10, 30));
+        assert_eq!(parse_datetime(" 2026-03-02 10:30:00 ").unwrap(), at(2, 10, 30));
+        assert_eq!(parse_datetime("2026-03-02").unwrap(), at(2, DEFAULT_HOUR, 0));
+
+        for bad in ["", "tomorrow", "2026-13-01", "02/03/2026"] {
+            let message = validation_message(parse_datetime(bad).unwrap_err());
+            assert!(message.starts_with("Invalid date/time"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn validates_part_numbers() {
+        let thread = output("twitter_thread", "1/ First\n\n2/ Second\n\n3/ Third");
+        assert!(validate_part(&thread, None).is_ok());
+        assert!(validate_part(&thread, Some(1)).is_ok());
+        assert!(validate_part(&thread, Some(3)).is_ok());
+        for number in [0, 4] {
+            assert_eq!(
+                validation_message(validate_part(&thread, Some(number)).unwrap_err()),
+                format!("Part {} does not exist; this output has 3 parts", number)
+            );
+        }
+
+        let post = output("linkedin", "A post.");
+        assert!(validate_part(&post, None).is_ok());
+        assert_eq!(
+            validation_message(validate_part(&post, Some(1)).unwrap_err()),
+            "Only threads and email sequences can be scheduled in parts, not 'linkedin'"
+        );
+    }
+
+    #[test]
+    fn spreads_thread_parts_by_interval() {
+        let thread = output("twitter_thread", "1/ First\n\n2/ Second\n\n3/ Third");
+        let times = spread_times(&thread, at(2, 9, 0), Duration::hours(2)).unwrap();
+        assert_eq!(times, vec![(1, at(2, 9, 0)), (2, at(2, 11, 0)), (3, at(2, 13, 0))]);
+    }
+
+    #[test]
+    fn mixes_send_timing_days_with_the_interval() {
+        let sequence = output(
+            "email_sequence",
+            "EMAIL 1: Welcome\nSUBJECT: Hi\n\nHello.\n\n\
+             EMAIL 2: Story\nSUBJECT: A story\nSEND TIMING: Day 3\n\nOnce.\n\n\
+             EMAIL 3: Proof\nSUBJECT: Results\n\nNumbers.\n\n\
+             EMAIL 4: Close\nSUBJECT: Last chance\nSEND TIMING: Immediately\n\nBye.",
+        );
+        let times = spread_times(&sequence, at(2, 9, 0), Duration::days(1)).unwrap();
+        assert_eq!(
+            times,
+            vec![
+                // No timing on the first email: it goes out at the start
+                (1, at(2, 9, 0)),
+                (2, at(4, 9, 0)),
+                // Follows email 2 by the interval
+                (3, at(5, 9, 0)),
+                (4, at(2, 9, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_outputs_without_parts() {
+        let start = at(2, 9, 0);
+        let interval = Duration::days(1);
+        assert_eq!(
+            validation_message(spread_times(&output("email_sequence", "  \n"), start, interval).unwrap_err()),
+            "No emails found in this sequence"
+        );
+        assert_eq!(
+            validation_message(spread_times(&output("twitter_thread", ""), start, interval).unwrap_err()),
+            "No tweets found in this thread"
+        );
+        assert_eq!(
+            validation_message(spread_times(&output("linkedin", "A post."), start, interval).unwrap_err()),
+            "Only threads and email sequences can be spread across days"
+        );
+    }
+}
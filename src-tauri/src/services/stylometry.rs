@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+
+use rusqlite::params;
+
+use crate::errors::AppError;
+use crate::models::brand_voice::{NgramCount, PunctuationHabits, Stylometrics};
+
+/// Window size for the moving-average type-token ratio, so lexical diversity
+/// doesn't fall just because there is more sample text.
+const DIVERSITY_WINDOW: usize = 50;
+const TOP_NGRAMS: usize = 10;
+const TOP_EMOJIS: usize = 5;
+
+const FIRST_PERSON: &[&str] = &[
+    "i", "me", "my", "mine", "myself", "we", "us", "our", "ours", "ourselves", "i'm", "i've",
+    "i'll", "i'd", "we're", "we've", "we'll", "we'd",
+];
+const SECOND_PERSON: &[&str] = &[
+    "you", "your", "yours", "yourself", "yourselves", "you're", "you've", "you'll", "you'd",
+];
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have", "he",
+    "her", "his", "i", "if", "in", "is", "it", "its", "it's", "of", "on", "or", "she", "so",
+    "that", "the", "their", "them", "then", "there", "they", "this", "to", "was", "we", "were",
+    "what", "when", "which", "who", "will", "with", "you", "your",
+];
+
+/// Measures the writing samples locally. Returns `None` when there is no text
+/// to measure.
+pub fn analyze(samples: &[String]) -> Option<Stylometrics> {
+    let samples: Vec<&str> = samples
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if samples.is_empty() {
+        return None;
+    }
+
+    let combined = samples.join("\n\n");
+    let words: Vec<String> = samples.iter().flat_map(|s| normalized_words(s)).collect();
+    if words.is_empty() {
+        return None;
+    }
+    let word_count = words.len();
+    let per_1000 = |count: usize| round1(count as f64 * 1000.0 / word_count as f64);
+
+    let (avg_sentence_length, sentence_length_sd) = mean_and_sd(&sentence_lengths(&combined));
+    let paragraph_lengths: Vec<usize> = samples
+        .iter()
+        .flat_map(|s| s.split("\n\n"))
+        .map(|p| p.split_whitespace().count())
+        .filter(|&n| n > 0)
+        .collect();
+    let (avg_paragraph_length, _) = mean_and_sd(&paragraph_lengths);
+
+    let count_char = |target: char| combined.chars().filter(|&c| c == target).count();
+    let punctuation = PunctuationHabits {
+        exclamations: per_1000(count_char('!')),
+        questions: per_1000(count_char('?')),
+        semicolons: per_1000(count_char(';')),
+        colons: per_1000(count_char(':')),
+        dashes: per_1000(count_char('—') + count_char('–') + combined.matches(" - ").count()),
+        ellipses: per_1000(count_char('…') + combined.matches("...").count()),
+        parentheses: per_1000(count_char('(')),
+    };
+
+    let mut emoji_counts: HashMap<char, usize> = HashMap::new();
+    for c in combined.chars().filter(|&c| is_emoji(c)) {
+        *emoji_counts.entry(c).or_default() += 1;
+    }
+    let emoji_total: usize = emoji_counts.values().sum();
+    let top_emojis = top_counts(emoji_counts, TOP_EMOJIS)
+        .into_iter()
+        .map(|(c, _)| c.to_string())
+        .collect();
+
+    let first_person = words.iter().filter(|w| FIRST_PERSON.contains(&w.as_str())).count();
+    let second_person = words.iter().filter(|w| SECOND_PERSON.contains(&w.as_str())).count();
+    let first_person_share = (first_person + second_person > 0)
+        .then(|| round2(first_person as f64 / (first_person + second_person) as f64));
+
+    Some(Stylometrics {
+        sample_count: samples.len() as u32,
+        word_count: word_count as u32,
+        avg_sentence_length: round1(avg_sentence_length),
+        sentence_length_sd: round1(sentence_length_sd),
+        avg_paragraph_length: round1(avg_paragraph_length),
+        readability_grade: round1(flesch_kincaid_grade(&combined)),
+        lexical_diversity: round2(moving_type_token_ratio(&words)),
+        punctuation,
+        emoji_per_1000_words: per_1000(emoji_total),
+        top_emojis,
+        first_person_per_1000_words: per_1000(first_person),
+        second_person_per_1000_words: per_1000(second_person),
+        first_person_share,
+        frequent_ngrams: frequent_ngrams(&samples),
+    })
+}
+
+/// Recomputes and stores the stylometrics for a profile from its saved samples.
+pub fn refresh_profile(
+    conn: &rusqlite::Connection,
+    profile_id: &str,
+) -> Result<Option<Stylometrics>, AppError> {
+    let samples: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "SELECT sample_text FROM brand_voice_samples WHERE profile_id = ?1 ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map(params![profile_id], |row| row.get(0))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    let stylometrics = analyze(&samples);
+    conn.execute(
+        "UPDATE brand_voice_profiles SET stylometrics_json = ?1 WHERE id = ?2",
+        params![
+            stylometrics.as_ref().map(serde_json::to_string).transpose()?,
+            profile_id
+        ],
+    )?;
+
+    Ok(stylometrics)
+}
+
+/// Computes stylometrics for profiles created before they were stored.
+pub fn backfill(conn: &rusqlite::Connection) -> Result<(), AppError> {
+    let pending: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT p.id FROM brand_voice_profiles p \
+             JOIN brand_voice_samples s ON s.profile_id = p.id \
+             WHERE p.stylometrics_json IS NULL",
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    for profile_id in pending {
+        refresh_profile(conn, &profile_id)?;
+    }
+
+    Ok(())
+}
+
+/// Lowercased words with surrounding punctuation stripped; apostrophes kept.
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .replace('’', "'")
+                .to_lowercase()
+        })
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+fn moving_type_token_ratio(words: &[String]) -> f64 {
+    let ratio = |window: &[String]| {
+        let unique: std::collections::HashSet<&String> = window.iter().collect();
+        unique.len() as f64 / window.len() as f64
+    };
+
+    if words.len() <= DIVERSITY_WINDOW {
+        return ratio(words);
+    }
+
+    let windows: Vec<f64> = words.windows(DIVERSITY_WINDOW).map(ratio).collect();
+    windows.iter().sum::<f64>() / windows.len() as f64
+}
+
+/// Bigrams and trigrams that recur across the samples, ignoring ones made up
+/// only of stopwords.
+fn frequent_ngrams(samples: &[&str]) -> Vec<NgramCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for sample in samples {
+        for sentence in split_sentences(sample) {
+            let words = normalized_words(&sentence);
+            for n in [2, 3] {
+                for gram in words.windows(n) {
+                    if gram.iter().all(|w| STOPWORDS.contains(&w.as_str())) {
+                        continue;
+                    }
+                    *counts.entry(gram.join(" ")).or_default() += 1;
+                }
+            }
+        }
+    }
+    counts.retain(|_, count| *count >= 2);
+
+    top_counts(counts, TOP_NGRAMS)
+        .into_iter()
+        .map(|(ngram, count)| NgramCount {
+            ngram,
+            count: count as u32,
+        })
+        .collect()
+}
+
+fn top_counts<K: Ord>(counts: HashMap<K, usize>, limit: usize) -> Vec<(K, usize)> {
+    let mut entries: Vec<(K, usize)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries.truncate(limit);
+    entries
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F300..=0x1FAFF | 0x2600..=0x27BF | 0x1F1E6..=0x1F1FF | 0x2B50 | 0x2B55
+    )
+}
+
+/// Splits text into sentences on terminal punctuation and line breaks.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            push_sentence(&mut sentences, &mut current);
+            continue;
+        }
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|next| next.is_whitespace()) {
+            push_sentence(&mut sentences, &mut current);
+        }
+    }
+    push_sentence(&mut sentences, &mut current);
+
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, current: &mut String) {
+    let sentence = current.trim();
+    if sentence.split_whitespace().next().is_some() {
+        sentences.push(sentence.to_string());
+    }
+    current.clear();
+}
+
+/// Word count of each sentence.
+pub fn sentence_lengths(text: &str) -> Vec<usize> {
+    split_sentences(text)
+        .iter()
+        .map(|s| s.split_whitespace().count())
+        .collect()
+}
+
+/// Flesch-Kincaid grade level, using a vowel-group syllable estimate.
+pub fn flesch_kincaid_grade(text: &str) -> f64 {
+    let sentences = split_sentences(text).len().max(1) as f64;
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphabetic))
+        .collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+
+    let syllables: usize = words.iter().map(|w| count_syllables(w)).sum();
+    let word_count = words.len() as f64;
+    (0.39 * word_count / sentences + 11.8 * syllables as f64 / word_count - 15.59).max(0.0)
+}
+
+fn count_syllables(word: &str) -> usize {
+    let letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut groups = 0;
+    let mut previous_vowel = false;
+    for &c in &letters {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            groups += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    // Silent trailing "e" ("make", "voice"), but not "-le" ("simple")
+    let n = letters.len();
+    if groups > 1 && n > 2 && letters[n - 1] == 'e' && letters[n - 2] != 'l' && !is_vowel(letters[n - 2]) {
+        groups -= 1;
+    }
+
+    groups.max(1)
+}
+
+pub fn mean_and_sd(values: &[usize]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<usize>() as f64 / n;
+    let variance = values
+        .iter()
+        .map(|&v| (v as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    (mean, variance.sqrt())
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 20 words across five sentences and two samples, with accented words
+    /// and emoji attached to a word.
+    fn samples() -> Vec<String> {
+        vec![
+            "I love café crème🚀🚀! Do you? Naïve élan wins big.".to_string(),
+            "We ship fast; you ship faster — right... Maybe (not) today.".to_string(),
+        ]
+    }
+
+    #[test]
+    fn splits_sentences_on_terminal_punctuation_and_lines() {
+        assert_eq!(
+            split_sentences("¿Qué pasa? ¡Nada! Pi is 3.14 today...\nNo period here"),
+            vec!["¿Qué pasa?", "¡Nada!", "Pi is 3.14 today...", "No period here"]
+        );
+        assert_eq!(sentence_lengths("Wait... what?!  Yes.\n\n"), vec![1, 1, 1]);
+        assert!(split_sentences(" \n\n ").is_empty());
+    }
+
+    #[test]
+    fn measures_sentences_and_words() {
+        let stats = analyze(&samples()).unwrap();
+        assert_eq!(stats.sample_count, 2);
+        // The em dash and the emoji are not words
+        assert_eq!(stats.word_count, 20);
+        // Sentence lengths 4, 2, 4, 8 and 3
+        assert_eq!(stats.avg_sentence_length, 4.2);
+        assert_eq!(stats.sentence_length_sd, 2.0);
+        assert_eq!(stats.avg_paragraph_length, 10.5);
+        // "you" and "ship" repeat: 18 distinct words of 20
+        assert_eq!(stats.lexical_diversity, 0.9);
+        assert_eq!(stats.first_person_per_1000_words, 100.0);
+        assert_eq!(stats.second_person_per_1000_words, 100.0);
+        assert_eq!(stats.first_person_share, Some(0.5));
+        assert!(stats.frequent_ngrams.is_empty());
+    }
+
+    #[test]
+    fn counts_emoji_and_punctuation_per_thousand_words() {
+        let stats = analyze(&samples()).unwrap();
+        assert_eq!(stats.emoji_per_1000_words, 100.0);
+        assert_eq!(stats.top_emojis, vec!["🚀"]);
+
+        let p = &stats.punctuation;
+        assert_eq!(
+            (p.exclamations, p.questions, p.semicolons, p.colons),
+            (50.0, 50.0, 50.0, 0.0)
+        );
+        assert_eq!((p.dashes, p.ellipses, p.parentheses), (50.0, 50.0, 50.0));
+    }
+
+    #[test]
+    fn finds_recurring_ngrams() {
+        let stats = analyze(&[
+            "Ship it today. We ship it fast.".to_string(),
+            "Ship it now, and of the best.".to_string(),
+        ])
+        .unwrap();
+        let ngrams: Vec<(&str, u32)> = stats
+            .frequent_ngrams
+            .iter()
+            .map(|n| (n.ngram.as_str(), n.count))
+            .collect();
+        // "of the" is all stopwords; "it today" and the rest appear once
+        assert_eq!(ngrams, vec![("ship it", 3)]);
+    }
+
+    #[test]
+    fn needs_words_to_measure() {
+        assert!(analyze(&[]).is_none());
+        assert!(analyze(&["  ".to_string(), "🚀 — 👍".to_string()]).is_none());
+    }
+
+    #[test]
+    fn estimates_syllables_and_grade() {
+        let syllables: Vec<usize> = ["make", "simple", "voice", "readability", "the", "Café!"]
+            .iter()
+            .map(|w| count_syllables(w))
+            .collect();
+        assert_eq!(syllables, vec![1, 2, 1, 5, 1, 1]);
+
+        // 5 words and 21 estimated syllables in one sentence
+        let grade = flesch_kincaid_grade("Readability measurements require considerable consideration.");
+        assert!((grade - 35.92).abs() < 1e-9, "{}", grade);
+        assert_eq!(flesch_kincaid_grade("The cat sat."), 0.0);
+        assert_eq!(flesch_kincaid_grade("🚀 42"), 0.0);
+    }
+
+    #[test]
+    fn averages_and_spreads() {
+        assert_eq!(mean_and_sd(&[]), (0.0, 0.0));
+        assert_eq!(mean_and_sd(&[2, 4, 4, 4, 5, 5, 7, 9]), (5.0, 2.0));
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use reqwest::Url;
+use rusqlite::{params, OptionalExtension};
+
+use crate::db::DbState;
+use crate::errors::{AppError, UrlFetchError};
+use crate::services::url_fetcher::{self, FetchOutcome, FetchPolicy, FetchedBody};
+
+const DEFAULT_TTL_SECONDS: u64 = 3600;
+
+/// Total size of cached bodies kept before the oldest entries are evicted.
+const MAX_CACHE_BYTES: i64 = 64 * 1024 * 1024;
+
+pub struct CachedPage {
+    pub body: FetchedBody,
+    pub fetched_at: String,
+}
+
+/// Fetches a page, serving it from `url_cache` while it is within the TTL and
+/// revalidating with ETag/Last-Modified once it has expired.
+pub async fn fetch_page(
+    db: &DbState,
+    url: &str,
+    policy: &FetchPolicy,
+    force_refresh: bool,
+) -> Result<FetchedBody, AppError> {
+    let cached = if force_refresh {
+        None
+    } else {
+        get_cached(db, url).await?
+    };
+
+    if let Some(ref page) = cached {
+        let ttl = get_ttl_seconds(db).await;
+        let age = chrono::DateTime::parse_from_rfc3339(&page.fetched_at)
+            .map(|fetched_at| (chrono::Utc::now() - fetched_at.to_utc()).num_seconds())
+            .unwrap_or(i64::MAX);
+        if age >= 0 && (age as u64) < ttl {
+            return Ok(page.body.clone());
+        }
+    }
+
+    let etag = cached.as_ref().and_then(|page| page.body.header("etag"));
+    let last_modified = cached
+        .as_ref()
+        .and_then(|page| page.body.header("last-modified"));
+
+    let outcome = url_fetcher::fetch_conditional(
+        url,
+        policy,
+        url_fetcher::PAGE_CONTENT_TYPES,
+        etag,
+        last_modified,
+    )
+    .await?;
+
+    match (outcome, cached) {
+        (FetchOutcome::Fetched(body), _) => {
+            store(db, url, &body).await?;
+            Ok(body)
+        }
+        (FetchOutcome::NotModified, Some(page)) => {
+            let conn = db.conn.lock().await;
+            conn.execute(
+                "UPDATE url_cache SET fetched_at = ?1 WHERE url = ?2",
+                params![chrono::Utc::now().to_rfc3339(), url],
+            )?;
+            Ok(page.body)
+        }
+        (FetchOutcome::NotModified, None) => Err(UrlFetchError::Request(
+            "URL returned 304 Not Modified but nothing is cached".to_string(),
+        )
+        .into()),
+    }
+}
+
+pub async fn get_cached(db: &DbState, url: &str) -> Result<Option<CachedPage>, AppError> {
+    let conn = db.conn.lock().await;
+
+    let row = conn
+        .query_row(
+            "SELECT final_url, body, headers_json, fetched_at FROM url_cache WHERE url = ?1",
+            params![url],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Vec<u8>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        )
+        .optional()?;
+
+    let Some((final_url, bytes, headers_json, fetched_at)) = row else {
+        return Ok(None);
+    };
+
+    conn.execute(
+        "UPDATE url_cache SET last_used_at = ?1 WHERE url = ?2",
+        params![chrono::Utc::now().to_rfc3339(), url],
+    )?;
+
+    let final_url = Url::parse(&final_url)
+        .map_err(|e| AppError::Database(format!("Invalid cached URL '{}': {}", final_url, e)))?;
+    let headers: BTreeMap<String, String> = serde_json::from_str(&headers_json)?;
+
+    Ok(Some(CachedPage {
+        body: FetchedBody {
+            url: final_url,
+            bytes,
+            headers,
+        },
+        fetched_at,
+    }))
+}
+
+async fn store(db: &DbState, url: &str, body: &FetchedBody) -> Result<(), AppError> {
+    let headers_json = serde_json::to_string(&body.headers)?;
+    let ttl = get_ttl_seconds(db).await;
+    let conn = db.conn.lock().await;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT OR REPLACE INTO url_cache (url, final_url, body, headers_json, etag, last_modified, fetched_at, last_used_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+        params![
+            url,
+            body.url.as_str(),
+            body.bytes,
+            headers_json,
+            body.header("etag"),
+            body.header("last-modified"),
+            now
+        ],
+    )?;
+
+    prune(&conn, url, ttl, MAX_CACHE_BYTES)
+}
+
+/// Evicts entries until the cached bodies fit in `max_bytes`: expired entries
+/// first, then the least recently used. The entry just stored is kept.
+fn prune(
+    conn: &rusqlite::Connection,
+    keep_url: &str,
+    ttl_seconds: u64,
+    max_bytes: i64,
+) -> Result<(), AppError> {
+    let mut total: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(LENGTH(body)), 0) FROM url_cache",
+        [],
+        |row| row.get(0),
+    )?;
+    if total <= max_bytes {
+        return Ok(());
+    }
+
+    let expired_before = (chrono::Utc::now()
+        - chrono::Duration::seconds(ttl_seconds.min(i64::MAX as u64) as i64))
+    .to_rfc3339();
+    let candidates: Vec<(String, i64)> = {
+        let mut stmt = conn.prepare(
+            "SELECT url, LENGTH(body) FROM url_cache WHERE url != ?1 \
+             ORDER BY fetched_at < ?2 DESC, COALESCE(last_used_at, fetched_at) ASC",
+        )?;
+        let rows = stmt.query_map(params![keep_url, expired_before], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    for (url, size) in candidates {
+        if total <= max_bytes {
+            break;
+        }
+        conn.execute("DELETE FROM url_cache WHERE url = ?1", params![url])?;
+        total -= size;
+    }
+
+    Ok(())
+}
+
+pub async fn get_ttl_seconds(db: &DbState) -> u64 {
+    let conn = db.conn.lock().await;
+    conn.query_row(
+        "SELECT CAST(value AS INTEGER) FROM app_settings WHERE key = 'url_cache_ttl_seconds'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|ttl| ttl.max(0) as u64)
+    .unwrap_or(DEFAULT_TTL_SECONDS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(conn: &rusqlite::Connection, url: &str, size: usize, fetched_ago: i64, used_ago: i64) {
+        let ago = |secs: i64| (chrono::Utc::now() - chrono::Duration::seconds(secs)).to_rfc3339();
+        conn.execute(
+            "INSERT INTO url_cache (url, final_url, body, fetched_at, last_used_at) VALUES (?1, ?1, ?2, ?3, ?4)",
+            params![url, vec![0u8; size], ago(fetched_ago), ago(used_ago)],
+        )
+        .unwrap();
+    }
+
+    fn cached_urls(conn: &rusqlite::Connection) -> Vec<String> {
+        let mut stmt = conn.prepare("SELECT url FROM url_cache ORDER BY url").unwrap();
+        let urls = stmt.query_map([], |row| row.get(0)).unwrap();
+        urls.map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn evicts_expired_then_least_recently_used_entries() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::db::run_migrations(&conn).unwrap();
+
+        insert(&conn, "https://a.example/expired", 100, 7200, 10);
+        insert(&conn, "https://b.example/stale-use", 100, 60, 600);
+        insert(&conn, "https://c.example/recent-use", 100, 60, 5);
+        insert(&conn, "https://d.example/new", 100, 0, 0);
+
+        prune(&conn, "https://d.example/new", 3600, 400).unwrap();
+        assert_eq!(cached_urls(&conn).len(), 4, "within the limit nothing is evicted");
+
+        prune(&conn, "https://d.example/new", 3600, 250).unwrap();
+        assert_eq!(
+            cached_urls(&conn),
+            ["https://c.example/recent-use", "https://d.example/new"]
+        );
+
+        prune(&conn, "https://d.example/new", 3600, 0).unwrap();
+        assert_eq!(cached_urls(&conn), ["https://d.example/new"]);
+    }
+}
@@ -1,46 +1,465 @@
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+use reqwest::header::{CONTENT_TYPE, IF_MODIFIED_SINCE, IF_NONE_MATCH, LOCATION};
+use reqwest::{StatusCode, Url};
 use scraper::{Html, Selector};
 
-use crate::errors::AppError;
+use crate::db::DbState;
+use crate::errors::{AppError, UrlFetchError};
 use crate::models::content::FetchedContent;
 
-pub async fn fetch_url(url: &str) -> Result<FetchedContent, AppError> {
-    let client = reqwest::Client::builder()
-        .user_agent("ContentEngine/1.0")
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| AppError::UrlFetch(format!("Failed to create HTTP client: {}", e)))?;
+pub const USER_AGENT: &str = "ContentEngine/1.0";
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| AppError::UrlFetch(format!("Failed to fetch URL: {}", e)))?;
+const MAX_REDIRECTS: usize = 5;
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Content types accepted when fetching a page for text extraction.
+pub const PAGE_CONTENT_TYPES: &[&str] = &["text/html", "application/xhtml+xml", "text/plain"];
+
+/// Hosts and networks that are exempt from the private-address block.
+#[derive(Debug, Clone, Default)]
+pub struct FetchPolicy {
+    allowed_hosts: Vec<String>,
+    allowed_networks: Vec<(IpAddr, u8)>,
+}
+
+impl FetchPolicy {
+    /// Builds a policy from allowlist entries: hostnames, IP addresses or CIDR ranges.
+    pub fn from_allowlist(entries: &[String]) -> Result<Self, AppError> {
+        let mut policy = FetchPolicy::default();
 
-    let status = response.status();
-    if !status.is_success() {
-        return Err(AppError::UrlFetch(format!(
-            "URL returned status {}",
-            status
-        )));
+        for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let (addr, prefix) = match entry.split_once('/') {
+                Some((addr, prefix)) => (addr, Some(prefix)),
+                None => (entry, None),
+            };
+
+            if let Ok(ip) = addr.parse::<IpAddr>() {
+                let max_prefix = if ip.is_ipv4() { 32 } else { 128 };
+                let prefix = match prefix {
+                    Some(p) => p.parse::<u8>().ok().filter(|p| *p <= max_prefix).ok_or_else(|| {
+                        AppError::Validation(format!("Invalid network prefix in '{}'", entry))
+                    })?,
+                    None => max_prefix,
+                };
+                policy.allowed_networks.push((ip, prefix));
+            } else if prefix.is_none()
+                && entry
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+            {
+                policy.allowed_hosts.push(entry.to_ascii_lowercase());
+            } else {
+                return Err(AppError::Validation(format!(
+                    "Invalid allowlist entry '{}'",
+                    entry
+                )));
+            }
+        }
+
+        Ok(policy)
     }
 
-    let html = response
-        .text()
+    fn allows_host(&self, host: &str) -> bool {
+        self.allowed_hosts.iter().any(|h| h.eq_ignore_ascii_case(host))
+    }
+
+    fn allows_address(&self, ip: IpAddr) -> bool {
+        self.allowed_networks
+            .iter()
+            .any(|(network, prefix)| network_contains(*network, *prefix, ip))
+    }
+}
+
+/// Splits the stored `url_fetch_allowlist` setting into entries.
+pub fn parse_allowlist_setting(value: &str) -> Vec<String> {
+    value
+        .split([',', '\n'])
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+pub async fn load_fetch_policy(db: &DbState) -> Result<FetchPolicy, AppError> {
+    let conn = db.conn.lock().await;
+    let value: String = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'url_fetch_allowlist'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or_default();
+
+    FetchPolicy::from_allowlist(&parse_allowlist_setting(&value))
+}
+
+pub async fn fetch_url(url: &str, policy: &FetchPolicy) -> Result<FetchedContent, AppError> {
+    let html = fetch_text(url, policy, PAGE_CONTENT_TYPES).await?;
+    parse_page(&html)
+}
+
+/// A successful response body together with the headers needed to decode it.
+#[derive(Debug, Clone)]
+pub struct FetchedBody {
+    pub url: Url,
+    pub bytes: Vec<u8>,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl FetchedBody {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    pub fn text(&self) -> String {
+        let tld = self
+            .url
+            .host_str()
+            .and_then(|host| host.rsplit('.').next())
+            .map(|tld| tld.as_bytes());
+        decode_body(&self.bytes, self.header("content-type"), tld)
+    }
+}
+
+pub enum FetchOutcome {
+    NotModified,
+    Fetched(FetchedBody),
+}
+
+/// Fetches a URL and returns the decoded response body without any extraction.
+pub async fn fetch_text(
+    url: &str,
+    policy: &FetchPolicy,
+    content_types: &[&str],
+) -> Result<String, AppError> {
+    match fetch_conditional(url, policy, content_types, None, None).await? {
+        FetchOutcome::Fetched(body) => Ok(body.text()),
+        FetchOutcome::NotModified => Err(UrlFetchError::Request(
+            "URL returned 304 Not Modified for an unconditional request".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Fetches a URL, sending `If-None-Match` / `If-Modified-Since` when validators
+/// from an earlier response are given.
+///
+/// Redirects are followed manually so that every hop is resolved and checked
+/// against the blocked address ranges before a connection is made.
+pub async fn fetch_conditional(
+    url: &str,
+    policy: &FetchPolicy,
+    content_types: &[&str],
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> Result<FetchOutcome, AppError> {
+    let mut current = Url::parse(url)
+        .map_err(|e| UrlFetchError::Request(format!("Invalid URL: {}", e)))?;
+
+    for _ in 0..=MAX_REDIRECTS {
+        if current.scheme() != "http" && current.scheme() != "https" {
+            return Err(UrlFetchError::Request(format!(
+                "Unsupported URL scheme '{}'",
+                current.scheme()
+            ))
+            .into());
+        }
+
+        let addrs = resolve_checked(&current, policy).await?;
+
+        let mut builder = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(std::time::Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none());
+        // Pin the connection to the addresses we just checked
+        if let Some(host) = current.host_str() {
+            builder = builder.resolve_to_addrs(host, &addrs);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| UrlFetchError::Request(format!("Failed to create HTTP client: {}", e)))?;
+
+        let mut request = client.get(current.clone());
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| UrlFetchError::Request(format!("Failed to fetch URL: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(FetchOutcome::NotModified);
+        }
+
+        if status.is_redirection() {
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| {
+                    UrlFetchError::Request(format!("URL returned status {} without a Location", status))
+                })?;
+            current = current
+                .join(location)
+                .map_err(|e| UrlFetchError::Request(format!("Invalid redirect target: {}", e)))?;
+            continue;
+        }
+
+        if !status.is_success() {
+            return Err(UrlFetchError::Request(format!("URL returned status {}", status)).into());
+        }
+
+        return read_body(response, current, content_types)
+            .await
+            .map(FetchOutcome::Fetched);
+    }
+
+    Err(UrlFetchError::TooManyRedirects(MAX_REDIRECTS).into())
+}
+
+async fn resolve_checked(url: &Url, policy: &FetchPolicy) -> Result<Vec<SocketAddr>, AppError> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| UrlFetchError::Request("URL has no host".to_string()))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let bare_host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let addrs: Vec<SocketAddr> = match bare_host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((bare_host, port))
+            .await
+            .map_err(|e| UrlFetchError::Request(format!("Failed to resolve {}: {}", host, e)))?
+            .collect(),
+    };
+
+    if addrs.is_empty() {
+        return Err(UrlFetchError::Request(format!("No addresses found for {}", host)).into());
+    }
+
+    if policy.allows_host(bare_host) {
+        return Ok(addrs);
+    }
+
+    if let Some(blocked) = addrs
+        .iter()
+        .map(|addr| addr.ip())
+        .find(|ip| is_blocked_address(*ip) && !policy.allows_address(*ip))
+    {
+        return Err(UrlFetchError::BlockedAddress {
+            host: host.to_string(),
+            address: blocked,
+        }
+        .into());
+    }
+
+    Ok(addrs)
+}
+
+async fn read_body(
+    mut response: reqwest::Response,
+    url: Url,
+    content_types: &[&str],
+) -> Result<FetchedBody, AppError> {
+    let headers: BTreeMap<String, String> = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str().to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+
+    // A response without a Content-Type could be anything, so it is refused
+    // rather than guessed at.
+    let mime = headers
+        .get(CONTENT_TYPE.as_str())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    if !content_types.contains(&mime.as_str()) {
+        let shown = if mime.is_empty() { "(none)".to_string() } else { mime };
+        return Err(UrlFetchError::UnsupportedContentType(shown).into());
+    }
+
+    if response.content_length().is_some_and(|len| len > MAX_BODY_BYTES as u64) {
+        return Err(UrlFetchError::BodyTooLarge { limit: MAX_BODY_BYTES }.into());
+    }
+
+    let mut bytes: Vec<u8> = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
         .await
-        .map_err(|e| AppError::UrlFetch(format!("Failed to read response body: {}", e)))?;
+        .map_err(|e| UrlFetchError::Request(format!("Failed to read response body: {}", e)))?
+    {
+        if bytes.len() + chunk.len() > MAX_BODY_BYTES {
+            return Err(UrlFetchError::BodyTooLarge { limit: MAX_BODY_BYTES }.into());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(FetchedBody {
+        url,
+        bytes,
+        headers,
+    })
+}
+
+/// Decodes a response body following the HTML encoding sniffing order:
+/// byte order mark, HTTP `charset`, `<meta>` / XML declaration prescan, and
+/// finally a statistical guess.
+pub fn decode_body(body: &[u8], content_type: Option<&str>, tld: Option<&[u8]>) -> String {
+    let encoding = Encoding::for_bom(body)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| content_type.and_then(charset_from_content_type))
+        .or_else(|| prescan_charset(body))
+        .unwrap_or_else(|| {
+            let mut detector = EncodingDetector::new();
+            detector.feed(body, true);
+            detector.guess(tld, true)
+        });
+
+    let (text, _) = encoding.decode_with_bom_removal(body);
+    text.into_owned()
+}
+
+fn charset_from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type
+        .split(';')
+        .skip(1)
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+        .and_then(|(_, label)| Encoding::for_label(label.trim().trim_matches(['"', '\'']).as_bytes()))
+}
+
+/// Looks for a charset in `<meta charset>`, `<meta http-equiv="Content-Type">`
+/// or an XML declaration within the first 1024 bytes.
+fn prescan_charset(body: &[u8]) -> Option<&'static Encoding> {
+    let head = &body[..body.len().min(1024)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
 
-    let document = Html::parse_document(&html);
+    let declared = head
+        .match_indices("<meta")
+        .filter_map(|(start, _)| {
+            let tag = &head[start..];
+            let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+            charset_attribute(tag, "charset")
+        })
+        .next()
+        .or_else(|| {
+            let decl = head.strip_prefix("<?xml")?;
+            charset_attribute(&decl[..decl.find("?>")?], "encoding")
+        })?;
+
+    let encoding = Encoding::for_label(declared.as_bytes())?;
+    // A document that could be read as ASCII cannot really be UTF-16
+    if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+        return Some(encoding_rs::UTF_8);
+    }
+    if encoding == encoding_rs::X_USER_DEFINED {
+        return Some(encoding_rs::WINDOWS_1252);
+    }
+    Some(encoding)
+}
+
+fn charset_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let after = &tag[tag.find(name)? + name.len()..];
+    let value = after.trim_start().strip_prefix('=')?.trim_start();
+    let value = value.trim_start_matches(['"', '\'']);
+    let end = value
+        .find(|c: char| c == '"' || c == '\'' || c == ';' || c == '/' || c.is_whitespace())
+        .unwrap_or(value.len());
+    Some(&value[..end]).filter(|v| !v.is_empty())
+}
+
+fn is_blocked_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_blocked_ipv4(v4),
+        IpAddr::V6(v6) => {
+            if v6.is_loopback() || v6.is_unspecified() {
+                return true;
+            }
+            if let Some(v4) = embedded_ipv4(v6) {
+                return is_blocked_ipv4(v4);
+            }
+            let first = v6.segments()[0];
+            v6.is_multicast()
+                || (first & 0xffc0) == 0xfe80 // link-local fe80::/10
+                || (first & 0xffc0) == 0xfec0 // site-local fec0::/10
+                || (first & 0xfe00) == 0xfc00 // unique local fc00::/7
+        }
+    }
+}
+
+/// The IPv4 address carried by an IPv4-mapped (`::ffff:a.b.c.d`),
+/// IPv4-compatible (`::a.b.c.d`), NAT64 (`64:ff9b::/96`) or 6to4
+/// (`2002::/16`) address, since those reach the IPv4 host.
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let [.., a, b, c, d] = ip.octets();
+    let low = Ipv4Addr::new(a, b, c, d);
+
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        Some(v4)
+    } else if segments[..6] == [0; 6] || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        Some(low)
+    } else if segments[0] == 0x2002 {
+        let [a, b] = segments[1].to_be_bytes();
+        let [c, d] = segments[2].to_be_bytes();
+        Some(Ipv4Addr::new(a, b, c, d))
+    } else {
+        None
+    }
+}
+
+fn is_blocked_ipv4(ip: Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || octets[0] == 0
+        || (octets[0] == 100 && (octets[1] & 0xc0) == 64) // shared 100.64.0.0/10
+        || (octets[0] == 192 && octets[1] == 0 && octets[2] == 0) // IETF protocol 192.0.0.0/24
+        || (octets[0] == 198 && (octets[1] & 0xfe) == 18) // benchmarking 198.18.0.0/15
+        || octets[0] >= 240 // reserved 240.0.0.0/4
+}
+
+fn network_contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(net), IpAddr::V4(addr)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(net) & mask == u32::from(addr) & mask
+        }
+        (IpAddr::V6(net), IpAddr::V6(addr)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(net) & mask == u128::from(addr) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Extracts title, canonical URL and main text from an HTML document.
+pub fn parse_page(html: &str) -> Result<FetchedContent, AppError> {
+    let document = Html::parse_document(html);
 
     // Extract title
     let title = extract_title(&document);
 
+    let canonical_url = extract_canonical_url(&document);
+
     // Extract main text content
     let text = extract_text_content(&document);
 
     if text.trim().is_empty() {
-        return Err(AppError::UrlFetch(
-            "No text content found at URL".to_string(),
-        ));
+        return Err(UrlFetchError::Request("No text content found at URL".to_string()).into());
     }
 
     let word_count = text.split_whitespace().count() as u32;
@@ -49,15 +468,25 @@ pub async fn fetch_url(url: &str) -> Result<FetchedContent, AppError> {
         title,
         text,
         word_count,
+        canonical_url,
     })
 }
 
+fn extract_canonical_url(document: &Html) -> Option<String> {
+    let selector = Selector::parse(r#"link[rel="canonical"]"#).ok()?;
+    document
+        .select(&selector)
+        .filter_map(|el| el.value().attr("href"))
+        .map(|href| href.trim().to_string())
+        .find(|href| href.starts_with("http://") || href.starts_with("https://"))
+}
+
 fn extract_title(document: &Html) -> Option<String> {
     // Try og:title first
     if let Ok(selector) = Selector::parse(r#"meta[property="og:title"]"#) {
         if let Some(el) = document.select(&selector).next() {
             if let Some(content) = el.value().attr("content") {
-                let title = content.trim().to_string();
+                let title = clean_text(content);
                 if !title.is_empty() {
                     return Some(title);
                 }
@@ -68,7 +497,7 @@ fn extract_title(document: &Html) -> Option<String> {
     // Fall back to <title> tag
     if let Ok(selector) = Selector::parse("title") {
         if let Some(el) = document.select(&selector).next() {
-            let title = el.text().collect::<String>().trim().to_string();
+            let title = clean_text(&el.text().collect::<String>());
             if !title.is_empty() {
                 return Some(title);
             }
@@ -142,10 +571,291 @@ fn collect_text_recursive(
 }
 
 fn clean_text(text: &str) -> String {
+    let normalized = normalize_text(text);
+
     // Collapse whitespace and trim
-    text.split_whitespace()
+    normalized
+        .split_whitespace()
         .collect::<Vec<&str>>()
         .join(" ")
         .trim()
         .to_string()
 }
+
+/// Maps non-breaking and other fixed-width spaces to plain spaces and drops
+/// invisible formatting characters. Typographic quotes and dashes are kept, as
+/// are zero-width joiners and non-joiners, which emoji sequences and some
+/// scripts need.
+fn normalize_text(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{3000}' => Some(' '),
+            '\u{200B}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' => None,
+            '\u{2028}' | '\u{2029}' => Some('\n'),
+            other => Some(other),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    fn local_policy() -> FetchPolicy {
+        FetchPolicy::from_allowlist(&["127.0.0.1".to_string()]).unwrap()
+    }
+
+    fn encode(text: &str, encoding: &'static Encoding) -> Vec<u8> {
+        encoding.encode(text).0.into_owned()
+    }
+
+    #[test]
+    fn decodes_with_the_header_charset() {
+        let body = encode("<p>日本語のテキスト</p>", encoding_rs::SHIFT_JIS);
+        let text = decode_body(&body, Some("text/html; charset=\"Shift_JIS\""), None);
+        assert_eq!(text, "<p>日本語のテキスト</p>");
+    }
+
+    #[test]
+    fn a_byte_order_mark_beats_the_header() {
+        let mut body = vec![0xEF, 0xBB, 0xBF];
+        body.extend_from_slice("<p>Café</p>".as_bytes());
+        let text = decode_body(&body, Some("text/html; charset=windows-1252"), None);
+        assert_eq!(text, "<p>Café</p>");
+    }
+
+    #[test]
+    fn reads_meta_charset_and_http_equiv() {
+        let meta = encode(
+            "<html><head><meta charset=\"iso-8859-2\"></head><body>Zażółć</body></html>",
+            encoding_rs::ISO_8859_2,
+        );
+        assert!(decode_body(&meta, Some("text/html"), None).contains("Zażółć"));
+
+        let http_equiv = encode(
+            "<html><head><META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=windows-1252\"></head>\
+             <body>“Quoted” – café</body></html>",
+            encoding_rs::WINDOWS_1252,
+        );
+        assert!(decode_body(&http_equiv, None, None).contains("“Quoted” – café"));
+
+        // A UTF-16 declaration in an ASCII-compatible document means UTF-8
+        let utf16 = "<meta charset=utf-16><p>naïve</p>".as_bytes();
+        assert!(decode_body(utf16, None, None).contains("naïve"));
+    }
+
+    #[test]
+    fn guesses_the_encoding_when_nothing_is_declared() {
+        let body = encode(
+            "<p>Les élèves étaient très fâchés. Ça coûte cher, à vrai dire, \
+             et la forêt près du château était déjà là.</p>",
+            encoding_rs::WINDOWS_1252,
+        );
+        let text = decode_body(&body, Some("text/html"), Some(b"fr"));
+        assert!(text.contains("élèves étaient très fâchés"), "{}", text);
+    }
+
+    #[test]
+    fn normalises_spaces_but_keeps_typography_and_literal_entities() {
+        let html = "<html><body><p>Non\u{00A0}breaking\u{200B} and\u{2009}thin — \
+                    “smart” quotes</p><p>Write &amp;lt; to escape &lt; in HTML.</p></body></html>";
+        let page = parse_page(html).unwrap();
+
+        assert_eq!(
+            page.text,
+            "Non breaking and thin — “smart” quotes Write &lt; to escape < in HTML."
+        );
+        assert_eq!(page.word_count, 14);
+    }
+
+    #[test]
+    fn keeps_joiners_but_drops_other_invisible_characters() {
+        let html = "<html><body><p>\u{FEFF}Dev\u{00AD}ops by 👩\u{200D}💻                     and می\u{200C}خواهم\u{2060}!</p></body></html>";
+        let page = parse_page(html).unwrap();
+
+        assert_eq!(page.text, "Devops by 👩\u{200D}💻 and می\u{200C}خواهم!");
+        assert!(page.text.contains("👩‍💻"));
+    }
+
+    #[test]
+    fn blocks_non_public_addresses() {
+        for addr in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "192.0.0.8",
+            "198.18.0.1",
+            "198.19.255.255",
+            "240.0.0.1",
+            "255.255.255.255",
+            "224.0.0.1",
+            "::1",
+            "::",
+            "fe80::1",
+            "fec0::1",
+            "fd00::1",
+            "ff02::1",
+            "::ffff:10.0.0.1",
+            "::10.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "2002:7f00:1::",
+        ] {
+            assert!(is_blocked_address(ip(addr)), "{} should be blocked", addr);
+        }
+    }
+
+    #[test]
+    fn allows_public_addresses() {
+        for addr in [
+            "93.184.216.34",
+            "198.20.0.1",
+            "192.0.1.1",
+            "8.8.8.8",
+            "2606:4700::1111",
+            "::ffff:8.8.8.8",
+            "64:ff9b::808:808",
+            "2002:808:808::",
+        ] {
+            assert!(!is_blocked_address(ip(addr)), "{} should be allowed", addr);
+        }
+    }
+
+    #[test]
+    fn parses_allowlist_entries() {
+        let policy = FetchPolicy::from_allowlist(&parse_allowlist_setting(
+            "intranet.local, 10.0.0.0/8\n192.168.1.5\nfd00::/8",
+        ))
+        .unwrap();
+
+        assert!(policy.allows_host("Intranet.Local"));
+        assert!(!policy.allows_host("example.com"));
+        assert!(policy.allows_address(ip("10.200.1.1")));
+        assert!(policy.allows_address(ip("192.168.1.5")));
+        assert!(!policy.allows_address(ip("192.168.1.6")));
+        assert!(policy.allows_address(ip("fd12::1")));
+        assert!(!policy.allows_address(ip("fe80::1")));
+
+        assert!(FetchPolicy::from_allowlist(&["10.0.0.0/33".to_string()]).is_err());
+        assert!(FetchPolicy::from_allowlist(&["bad host!".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn refuses_private_addresses_unless_allowlisted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("hello", "text/plain"))
+            .mount(&server)
+            .await;
+
+        let error = fetch_text(&server.uri(), &FetchPolicy::default(), PAGE_CONTENT_TYPES)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, AppError::UrlFetch(UrlFetchError::BlockedAddress { .. })),
+            "{}",
+            error
+        );
+
+        let text = fetch_text(&server.uri(), &local_policy(), PAGE_CONTENT_TYPES)
+            .await
+            .unwrap();
+        assert_eq!(text, "hello");
+    }
+
+    #[tokio::test]
+    async fn checks_every_redirect_hop() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/to-metadata"))
+            .respond_with(
+                ResponseTemplate::new(302).insert_header("Location", "http://169.254.169.254/latest"),
+            )
+            .mount(&server)
+            .await;
+
+        let error = fetch_text(&format!("{}/to-metadata", server.uri()), &local_policy(), PAGE_CONTENT_TYPES)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, AppError::UrlFetch(UrlFetchError::BlockedAddress { .. })),
+            "{}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_after_too_many_redirects() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/loop"))
+            .respond_with(ResponseTemplate::new(301).insert_header("Location", "/loop"))
+            .expect(MAX_REDIRECTS as u64 + 1)
+            .mount(&server)
+            .await;
+
+        let error = fetch_text(&format!("{}/loop", server.uri()), &local_policy(), PAGE_CONTENT_TYPES)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, AppError::UrlFetch(UrlFetchError::TooManyRedirects(MAX_REDIRECTS))),
+            "{}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_bodies() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(vec![b'a'; MAX_BODY_BYTES + 1], "text/plain"),
+            )
+            .mount(&server)
+            .await;
+
+        let error = fetch_text(&server.uri(), &local_policy(), PAGE_CONTENT_TYPES)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, AppError::UrlFetch(UrlFetchError::BodyTooLarge { .. })),
+            "{}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unexpected_or_missing_content_types() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/image"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(vec![0u8; 8], "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/untyped"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"<p>hi</p>".to_vec()))
+            .mount(&server)
+            .await;
+
+        for (url, expected) in [("/image", "image/png"), ("/untyped", "(none)")] {
+            let error = fetch_text(&format!("{}{}", server.uri(), url), &local_policy(), PAGE_CONTENT_TYPES)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&error, AppError::UrlFetch(UrlFetchError::UnsupportedContentType(t)) if t == expected),
+                "{}",
+                error
+            );
+        }
+    }
+}
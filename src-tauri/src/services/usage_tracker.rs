@@ -1,31 +1,29 @@
-use chrono::{Datelike, Utc};
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate, Utc};
 use rusqlite::params;
 
 use crate::db::DbState;
 use crate::errors::AppError;
-use crate::models::usage::UsageInfo;
+use crate::models::usage::{UsageInfo, WorkspaceUsage};
+use crate::services::workspace;
 
 pub async fn get_usage_info(db: &DbState) -> Result<UsageInfo, AppError> {
     let conn = db.conn.lock().await;
+    let workspace_id = workspace::active_workspace_id(&conn);
 
     let now = Utc::now();
     let month_start = format!("{}-{:02}-01 00:00:00", now.year(), now.month());
 
     let used: u32 = conn
         .query_row(
-            "SELECT COALESCE(SUM(format_count), 0) FROM usage_records WHERE created_at >= ?1",
-            params![month_start],
+            "SELECT COALESCE(SUM(format_count), 0) FROM usage_records WHERE workspace_id = ?1 AND created_at >= ?2",
+            params![workspace_id, month_start],
             |row| row.get(0),
         )
         .map_err(|e| AppError::Database(format!("Failed to query usage: {}", e)))?;
 
-    let limit: u32 = conn
-        .query_row(
-            "SELECT CAST(value AS INTEGER) FROM app_settings WHERE key = 'monthly_usage_limit'",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(50);
+    let limit = workspace::monthly_limit(&conn, &workspace_id);
 
     // Calculate reset date (first of next month)
     let next_month = if now.month() == 12 {
@@ -52,8 +50,11 @@ pub async fn check_usage_limit(db: &DbState) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Counts a repurposing against `workspace_id`, which the caller reads before
+/// generating so a workspace switch in the meantime can't dodge the limit.
 pub async fn record_usage(
     db: &DbState,
+    workspace_id: &str,
     content_input_id: &str,
     format_count: u32,
 ) -> Result<(), AppError> {
@@ -61,8 +62,8 @@ pub async fn record_usage(
     let id = uuid::Uuid::new_v4().to_string();
 
     conn.execute(
-        "INSERT INTO usage_records (id, content_input_id, format_count) VALUES (?1, ?2, ?3)",
-        params![id, content_input_id, format_count],
+        "INSERT INTO usage_records (id, content_input_id, format_count, workspace_id) VALUES (?1, ?2, ?3, ?4)",
+        params![id, content_input_id, format_count, workspace_id],
     )?;
 
     Ok(())
@@ -70,13 +71,8 @@ pub async fn record_usage(
 
 pub async fn get_api_key(db: &DbState) -> Result<String, AppError> {
     let conn = db.conn.lock().await;
-    let key: String = conn
-        .query_row(
-            "SELECT value FROM app_settings WHERE key = 'claude_api_key'",
-            [],
-            |row| row.get(0),
-        )
-        .map_err(|e| AppError::Database(format!("Failed to get API key: {}", e)))?;
+    let workspace_id = workspace::active_workspace_id(&conn);
+    let key = workspace::get_setting(&conn, &workspace_id, "claude_api_key").unwrap_or_default();
 
     if key.is_empty() {
         return Err(AppError::ApiKeyMissing);
@@ -84,3 +80,62 @@ pub async fn get_api_key(db: &DbState) -> Result<String, AppError> {
 
     Ok(key)
 }
+
+/// Usage per workspace for one calendar month ("YYYY-MM", default the current
+/// month), including workspaces with no usage.
+pub async fn get_usage_report(
+    db: &DbState,
+    month: Option<&str>,
+) -> Result<Vec<WorkspaceUsage>, AppError> {
+    let month_start = match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+            .map_err(|_| {
+                AppError::Validation(format!("Invalid month '{}' (expected YYYY-MM)", month))
+            })?,
+        None => Utc::now().date_naive().with_day(1).unwrap_or_default(),
+    };
+    let next_month = month_start
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(NaiveDate::MAX);
+
+    // Bare dates compare correctly against both SQLite datetimes and RFC 3339
+    let from = month_start.format("%Y-%m-%d").to_string();
+    let to = next_month.format("%Y-%m-%d").to_string();
+
+    let conn = db.conn.lock().await;
+    let mut report = Vec::new();
+
+    for workspace_id in workspace::list_workspace_ids(&conn)? {
+        let workspace = workspace::get_workspace(&conn, &workspace_id)?;
+
+        let (repurpose_runs, formats_generated): (u32, u32) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(format_count), 0) FROM usage_records \
+             WHERE workspace_id = ?1 AND created_at >= ?2 AND created_at < ?3",
+            params![workspace_id, from, to],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT ro.format, COUNT(*) FROM repurposed_outputs ro \
+             JOIN content_inputs ci ON ci.id = ro.content_input_id \
+             WHERE ci.workspace_id = ?1 AND ro.created_at >= ?2 AND ro.created_at < ?3 \
+             GROUP BY ro.format",
+        )?;
+        let outputs_by_format: BTreeMap<String, u32> = stmt
+            .query_map(params![workspace_id, from, to], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        report.push(WorkspaceUsage {
+            workspace_id,
+            workspace_name: workspace.name,
+            month: month_start.format("%Y-%m").to_string(),
+            repurpose_runs,
+            formats_generated,
+            limit: workspace.monthly_usage_limit,
+            outputs_by_format,
+        });
+    }
+
+    Ok(report)
+}
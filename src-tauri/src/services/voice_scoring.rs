@@ -0,0 +1,289 @@
+use crate::models::brand_voice::{StyleAttributes, VoiceCriterionScore, VoiceJudgement, VoiceScore};
+use crate::services::phrase_guard;
+use crate::services::stylometry::{flesch_kincaid_grade, mean_and_sd, sentence_lengths};
+
+const AVOID_PHRASES_WEIGHT: f64 = 0.3;
+const SIGNATURE_PHRASES_WEIGHT: f64 = 0.15;
+const SENTENCE_LENGTH_WEIGHT: f64 = 0.2;
+const READABILITY_WEIGHT: f64 = 0.2;
+const LLM_JUDGEMENT_WEIGHT: f64 = 0.3;
+
+/// Points lost per distinct avoided phrase found in the output.
+const AVOID_PHRASE_PENALTY: f64 = 25.0;
+/// Points lost per grade level of readability difference from the samples.
+const READABILITY_GRADE_PENALTY: f64 = 15.0;
+
+/// Whether outputs should also be rated by Claude (`voice_scoring_use_llm`).
+pub fn llm_scoring_enabled(conn: &rusqlite::Connection) -> bool {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'voice_scoring_use_llm'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|value| value == "true")
+    .unwrap_or(false)
+}
+
+/// Scores an output against a voice using only local checks. Criteria that
+/// cannot be measured (no signature phrases, no samples) are left out rather
+/// than scored as zero.
+pub fn score_output(text: &str, style: &StyleAttributes, samples: &[String]) -> VoiceScore {
+    let mut criteria = vec![score_avoid_phrases(text, &style.avoid_phrases)];
+
+    if !style.signature_phrases.is_empty() {
+        criteria.push(score_signature_phrases(text, &style.signature_phrases));
+    }
+
+    let sample_text = samples.join("\n\n");
+    if !sample_text.trim().is_empty() {
+        criteria.push(score_sentence_length(text, &sample_text));
+        criteria.push(score_readability(text, &sample_text));
+    }
+
+    VoiceScore::from_criteria(criteria)
+}
+
+/// Adds the LLM's judgement as an extra weighted criterion.
+pub fn with_judgement(score: VoiceScore, judgement: VoiceJudgement) -> VoiceScore {
+    let mut criteria = score.criteria;
+    criteria.push(VoiceCriterionScore {
+        criterion: "llm_judgement".to_string(),
+        score: judgement.score.clamp(0.0, 100.0),
+        weight: LLM_JUDGEMENT_WEIGHT,
+        detail: judgement.rationale,
+    });
+    VoiceScore::from_criteria(criteria)
+}
+
+impl VoiceScore {
+    fn from_criteria(criteria: Vec<VoiceCriterionScore>) -> Self {
+        let total_weight: f64 = criteria.iter().map(|c| c.weight).sum();
+        let overall = if total_weight > 0.0 {
+            criteria.iter().map(|c| c.score * c.weight).sum::<f64>() / total_weight
+        } else {
+            0.0
+        };
+
+        VoiceScore {
+            overall: round1(overall),
+            criteria,
+            scored_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+fn score_avoid_phrases(text: &str, avoid_phrases: &[String]) -> VoiceCriterionScore {
+    let banned = phrase_guard::collect_banned(avoid_phrases, &[]);
+    let found = phrase_guard::distinct_phrases(&phrase_guard::find_banned(text, &banned));
+
+    let detail = if found.is_empty() {
+        "No avoided phrases used".to_string()
+    } else {
+        format!("Uses avoided phrases: {}", found.join(", "))
+    };
+
+    VoiceCriterionScore {
+        criterion: "avoid_phrases".to_string(),
+        score: (100.0 - AVOID_PHRASE_PENALTY * found.len() as f64).max(0.0),
+        weight: AVOID_PHRASES_WEIGHT,
+        detail,
+    }
+}
+
+fn score_signature_phrases(text: &str, signature_phrases: &[String]) -> VoiceCriterionScore {
+    let used: Vec<&str> = signature_phrases
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty() && contains_phrase(text, p))
+        .collect();
+
+    // One or two signature phrases per output is plenty; more would be forced.
+    let target = signature_phrases.len().min(2);
+    let score = 100.0 * used.len().min(target) as f64 / target as f64;
+
+    let detail = if used.is_empty() {
+        "No signature phrases used".to_string()
+    } else {
+        format!("Uses signature phrases: {}", used.join(", "))
+    };
+
+    VoiceCriterionScore {
+        criterion: "signature_phrases".to_string(),
+        score,
+        weight: SIGNATURE_PHRASES_WEIGHT,
+        detail,
+    }
+}
+
+fn score_sentence_length(text: &str, sample_text: &str) -> VoiceCriterionScore {
+    let (output_mean, output_sd) = mean_and_sd(&sentence_lengths(text));
+    let (sample_mean, sample_sd) = mean_and_sd(&sentence_lengths(sample_text));
+
+    // Relative distance in both the typical length and its spread
+    let mean_gap = (output_mean - sample_mean).abs() / sample_mean.max(1.0);
+    let sd_gap = (output_sd - sample_sd).abs() / sample_mean.max(1.0);
+    let score = (100.0 * (1.0 - mean_gap - 0.5 * sd_gap)).clamp(0.0, 100.0);
+
+    VoiceCriterionScore {
+        criterion: "sentence_length".to_string(),
+        score: round1(score),
+        weight: SENTENCE_LENGTH_WEIGHT,
+        detail: format!(
+            "Average sentence {:.1} words (samples {:.1}), spread {:.1} (samples {:.1})",
+            output_mean, sample_mean, output_sd, sample_sd
+        ),
+    }
+}
+
+fn score_readability(text: &str, sample_text: &str) -> VoiceCriterionScore {
+    let output_grade = flesch_kincaid_grade(text);
+    let sample_grade = flesch_kincaid_grade(sample_text);
+    let score =
+        (100.0 - READABILITY_GRADE_PENALTY * (output_grade - sample_grade).abs()).clamp(0.0, 100.0);
+
+    VoiceCriterionScore {
+        criterion: "readability".to_string(),
+        score: round1(score),
+        weight: READABILITY_WEIGHT,
+        detail: format!(
+            "Reading grade {:.1} (samples {:.1})",
+            output_grade, sample_grade
+        ),
+    }
+}
+
+/// Case-insensitive phrase search that only matches on word boundaries.
+pub fn contains_phrase(text: &str, phrase: &str) -> bool {
+    let text = text.to_lowercase();
+    let phrase = phrase.to_lowercase();
+    if phrase.is_empty() {
+        return false;
+    }
+
+    text.match_indices(&phrase).any(|(start, _)| {
+        let end = start + phrase.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(signature: &[&str], avoid: &[&str]) -> StyleAttributes {
+        StyleAttributes {
+            signature_phrases: signature.iter().map(|p| p.to_string()).collect(),
+            avoid_phrases: avoid.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn criterion(name: &str, score: f64, weight: f64) -> VoiceCriterionScore {
+        VoiceCriterionScore {
+            criterion: name.to_string(),
+            score,
+            weight,
+            detail: String::new(),
+        }
+    }
+
+    fn scores(score: &VoiceScore) -> Vec<(&str, f64)> {
+        score
+            .criteria
+            .iter()
+            .map(|c| (c.criterion.as_str(), c.score))
+            .collect()
+    }
+
+    #[test]
+    fn weights_criteria_into_the_overall_score() {
+        let score = VoiceScore::from_criteria(vec![criterion("a", 100.0, 0.3), criterion("b", 50.0, 0.2)]);
+        assert_eq!(score.overall, 80.0);
+        assert_eq!(score.criteria.len(), 2);
+
+        // Rounded to one decimal
+        let score = VoiceScore::from_criteria(vec![criterion("a", 100.0, 1.0), criterion("b", 0.0, 2.0)]);
+        assert_eq!(score.overall, 33.3);
+
+        assert_eq!(VoiceScore::from_criteria(Vec::new()).overall, 0.0);
+    }
+
+    #[test]
+    fn scores_only_what_can_be_measured() {
+        let text = "We leverage synergy. Then we leveraged it again.";
+        let score = score_output(text, &style(&[], &["leverage", "synergy", "circle back"]), &[]);
+        assert_eq!(scores(&score), vec![("avoid_phrases", 50.0)]);
+        assert_eq!(score.overall, 50.0);
+        assert_eq!(score.criteria[0].detail, "Uses avoided phrases: leverage, synergy");
+
+        let clean = score_output("Plain words here.", &style(&[], &[]), &["  ".to_string()]);
+        assert_eq!(scores(&clean), vec![("avoid_phrases", 100.0)]);
+    }
+
+    #[test]
+    fn caps_the_signature_phrase_target_at_two() {
+        let style = style(&["here's the thing", "bottom line", "in short"], &[]);
+
+        let one = score_output("Here's the thing: it works.", &style, &[]);
+        assert_eq!(scores(&one), vec![("avoid_phrases", 100.0), ("signature_phrases", 50.0)]);
+
+        let two = score_output("Here's the thing. Bottom line: it works.", &style, &[]);
+        assert_eq!(two.criteria[1].score, 100.0);
+        // (100 * 0.3 + 100 * 0.15) / 0.45
+        assert_eq!(two.overall, 100.0);
+
+        // Word boundaries: "in shortage" is not "in short"
+        let partial = score_output("We are in shortage.", &style, &[]);
+        assert_eq!(partial.criteria[1].score, 0.0);
+    }
+
+    #[test]
+    fn matches_samples_written_the_same_way() {
+        let text = "Short sentences work. They keep readers moving. Long ones can wander off.";
+        let score = score_output(text, &style(&[], &[]), &[text.to_string()]);
+        assert_eq!(
+            scores(&score),
+            vec![("avoid_phrases", 100.0), ("sentence_length", 100.0), ("readability", 100.0)]
+        );
+
+        let rambling = "This sentence goes on and on, adding clause after clause, qualifying \
+                        every observation with considerable elaboration and additional \
+                        circumstantial justification until the reader forgets the beginning.";
+        let score = score_output(rambling, &style(&[], &[]), &[text.to_string()]);
+        assert!(score.criteria[1].score < 50.0, "{:?}", scores(&score));
+        assert!(score.criteria[2].score < 50.0, "{:?}", scores(&score));
+    }
+
+    #[test]
+    fn merges_the_judgement_as_a_weighted_criterion() {
+        let local = VoiceScore::from_criteria(vec![criterion("avoid_phrases", 100.0, AVOID_PHRASES_WEIGHT)]);
+        let judged = with_judgement(
+            local.clone(),
+            VoiceJudgement {
+                score: 40.0,
+                rationale: "Too formal".to_string(),
+            },
+        );
+        assert_eq!(scores(&judged), vec![("avoid_phrases", 100.0), ("llm_judgement", 40.0)]);
+        assert_eq!(judged.criteria[1].detail, "Too formal");
+        // (100 * 0.3 + 40 * 0.3) / 0.6
+        assert_eq!(judged.overall, 70.0);
+
+        // Out-of-range judgements are clamped
+        let clamped = with_judgement(
+            local,
+            VoiceJudgement {
+                score: 140.0,
+                rationale: String::new(),
+            },
+        );
+        assert_eq!(clamped.criteria[1].score, 100.0);
+        assert_eq!(clamped.overall, 100.0);
+    }
+}
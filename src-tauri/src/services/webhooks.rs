@@ -0,0 +1,521 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use rusqlite::params;
+use sha2::Sha256;
+use tokio::sync::Mutex;
+
+use crate::errors::AppError;
+use crate::models::webhook::{DeliveryStatus, WebhookEvent};
+use crate::services::workspace;
+
+/// Attempts per delivery, including the first.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Seconds before the first retry; each later retry waits twice as long.
+const BACKOFF_BASE_SECS: u64 = 2;
+
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Pending deliveries older than this are not resent after a restart.
+const RESUME_WINDOW_HOURS: i64 = 24;
+
+/// Deliveries kept per endpoint; older ones are pruned as new ones are queued.
+const DELIVERY_LOG_LIMIT: u32 = 500;
+
+/// Where one delivery goes and what it carries.
+pub struct PendingDelivery {
+    pub id: String,
+    pub event: WebhookEvent,
+    pub url: String,
+    pub secret: String,
+    pub body: String,
+}
+
+pub struct DeliveryOutcome {
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Queues `event` for every enabled endpoint in the active workspace that
+/// subscribes to it, then delivers in the background. Webhooks never fail the
+/// command that triggered them; problems end up in the delivery log.
+pub async fn dispatch(
+    conn: &Arc<Mutex<rusqlite::Connection>>,
+    event: WebhookEvent,
+    data: serde_json::Value,
+) {
+    let workspace_id = workspace::active_workspace_id(&*conn.lock().await);
+    dispatch_to_workspace(conn, &workspace_id, event, data).await;
+}
+
+/// Like [`dispatch`], for a command that read its workspace before a long
+/// await the user may have switched workspaces during.
+pub async fn dispatch_to_workspace(
+    conn: &Arc<Mutex<rusqlite::Connection>>,
+    workspace_id: &str,
+    event: WebhookEvent,
+    data: serde_json::Value,
+) {
+    let deliveries = {
+        let conn = conn.lock().await;
+        match subscribed_endpoints(&conn, workspace_id, event) {
+            Ok(endpoints) if !endpoints.is_empty() => {
+                queue(&conn, workspace_id, &endpoints, event, data).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        }
+    };
+    if deliveries.is_empty() {
+        return;
+    }
+
+    deliver_in_background(Arc::clone(conn), deliveries);
+}
+
+/// Delivers to every endpoint at once, so a slow or retrying endpoint doesn't
+/// hold up the others, and logs each outcome as it finishes.
+pub fn deliver_in_background(
+    conn: Arc<Mutex<rusqlite::Connection>>,
+    deliveries: Vec<PendingDelivery>,
+) {
+    if deliveries.is_empty() {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let client = http_client();
+        let sends = deliveries.iter().map(|delivery| {
+            let client = &client;
+            let conn = &conn;
+            async move {
+                let outcome = deliver(client, delivery, MAX_ATTEMPTS, backoff).await;
+                let conn = conn.lock().await;
+                let _ = record_outcome(&conn, &delivery.id, &outcome);
+            }
+        });
+        futures::future::join_all(sends).await;
+    });
+}
+
+/// Deliveries left pending when the app last quit. Those still worth sending
+/// are returned to be delivered again with the body they were queued with;
+/// ones older than a day, or whose endpoint has since been disabled, are
+/// marked failed.
+pub fn interrupted_deliveries(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<PendingDelivery>, AppError> {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::hours(RESUME_WINDOW_HOURS)).to_rfc3339();
+    let mut stmt = conn.prepare(
+        "SELECT d.id, d.event, d.payload, d.created_at, e.url, e.secret, e.enabled \
+         FROM webhook_deliveries d JOIN webhook_endpoints e ON e.id = d.endpoint_id \
+         WHERE d.status = ?1",
+    )?;
+    let rows: Vec<(String, String, String, String, String, String, bool)> = stmt
+        .query_map(params![DeliveryStatus::Pending.to_string()], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut deliveries = Vec::new();
+    for (id, event, body, created_at, url, secret, enabled) in rows {
+        let event = serde_json::from_value::<WebhookEvent>(serde_json::Value::String(event));
+        let error = match event {
+            _ if !enabled => "Endpoint was disabled before delivery finished",
+            _ if created_at < cutoff => "Delivery was interrupted and is too old to resend",
+            Err(_) => "Delivery was interrupted and its event is unknown",
+            Ok(event) => {
+                deliveries.push(PendingDelivery {
+                    id,
+                    event,
+                    url,
+                    secret,
+                    body,
+                });
+                continue;
+            }
+        };
+        conn.execute(
+            "UPDATE webhook_deliveries SET status = ?1, error = ?2, completed_at = ?3 WHERE id = ?4",
+            params![
+                DeliveryStatus::Failed.to_string(),
+                error,
+                chrono::Utc::now().to_rfc3339(),
+                id
+            ],
+        )?;
+    }
+    Ok(deliveries)
+}
+
+/// Ids, URLs and secrets of the workspace's enabled endpoints listening for
+/// `event`.
+fn subscribed_endpoints(
+    conn: &rusqlite::Connection,
+    workspace_id: &str,
+    event: WebhookEvent,
+) -> Result<Vec<(String, String, String)>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, url, secret, events_json FROM webhook_endpoints \
+         WHERE workspace_id = ?1 AND enabled = 1",
+    )?;
+    let endpoints = stmt
+        .query_map(params![workspace_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .filter(|(_, _, _, events)| {
+            serde_json::from_str::<Vec<WebhookEvent>>(events)
+                .map(|events| events.contains(&event))
+                .unwrap_or(false)
+        })
+        .map(|(id, url, secret, _)| (id, url, secret))
+        .collect();
+    Ok(endpoints)
+}
+
+/// Logs a pending delivery per endpoint. All of them carry the same body, so
+/// receivers can deduplicate on its `id`.
+pub fn queue(
+    conn: &rusqlite::Connection,
+    workspace_id: &str,
+    endpoints: &[(String, String, String)],
+    event: WebhookEvent,
+    data: serde_json::Value,
+) -> Result<Vec<PendingDelivery>, AppError> {
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let body = serde_json::to_string(&serde_json::json!({
+        "id": uuid::Uuid::new_v4().to_string(),
+        "event": event,
+        "created_at": created_at,
+        "workspace_id": workspace_id,
+        "data": data,
+    }))?;
+
+    let mut deliveries = Vec::with_capacity(endpoints.len());
+    for (endpoint_id, url, secret) in endpoints {
+        let id = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO webhook_deliveries (id, endpoint_id, event, payload, status, attempts, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6)",
+            params![
+                id,
+                endpoint_id,
+                event.to_string(),
+                body,
+                DeliveryStatus::Pending.to_string(),
+                created_at
+            ],
+        )?;
+        conn.execute(
+            "DELETE FROM webhook_deliveries WHERE endpoint_id = ?1 AND id NOT IN \
+             (SELECT id FROM webhook_deliveries WHERE endpoint_id = ?1 \
+              ORDER BY created_at DESC LIMIT ?2)",
+            params![endpoint_id, DELIVERY_LOG_LIMIT],
+        )?;
+        deliveries.push(PendingDelivery {
+            id,
+            event,
+            url: url.clone(),
+            secret: secret.clone(),
+            body: body.clone(),
+        });
+    }
+    Ok(deliveries)
+}
+
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Wait before retry number `retry` (1-based): 2s, 4s, 8s, ...
+pub fn backoff(retry: u32) -> Duration {
+    Duration::from_secs(BACKOFF_BASE_SECS << (retry - 1).min(10))
+}
+
+/// POSTs a delivery, retrying network errors, timeouts, 408, 429 and 5xx
+/// responses up to `max_attempts` times. Other 4xx responses are final.
+pub async fn deliver(
+    client: &reqwest::Client,
+    delivery: &PendingDelivery,
+    max_attempts: u32,
+    backoff: impl Fn(u32) -> Duration,
+) -> DeliveryOutcome {
+    let mut outcome = DeliveryOutcome {
+        status: DeliveryStatus::Failed,
+        attempts: 0,
+        response_status: None,
+        error: None,
+    };
+
+    while outcome.attempts < max_attempts.max(1) {
+        if outcome.attempts > 0 {
+            tokio::time::sleep(backoff(outcome.attempts)).await;
+        }
+        outcome.attempts += 1;
+
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let signature = match sign(&delivery.secret, &timestamp, &delivery.body) {
+            Ok(signature) => signature,
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                return outcome;
+            }
+        };
+        let result = client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "ContentEngine-Webhooks/1")
+            .header("X-ContentEngine-Event", delivery.event.to_string())
+            .header("X-ContentEngine-Delivery", &delivery.id)
+            .header("X-ContentEngine-Timestamp", &timestamp)
+            .header("X-ContentEngine-Signature", format!("sha256={}", signature))
+            .body(delivery.body.clone())
+            .send()
+            .await;
+
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                outcome.response_status = Some(status.as_u16());
+                if status.is_success() {
+                    outcome.status = DeliveryStatus::Delivered;
+                    outcome.error = None;
+                    return outcome;
+                }
+                outcome.error = Some(format!("Endpoint returned status {}", status));
+                let retryable = status.is_server_error()
+                    || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                if !retryable {
+                    return outcome;
+                }
+            }
+            Err(e) => {
+                outcome.response_status = None;
+                outcome.error = Some(format!("Request failed: {}", e));
+            }
+        }
+    }
+    outcome
+}
+
+pub fn record_outcome(
+    conn: &rusqlite::Connection,
+    delivery_id: &str,
+    outcome: &DeliveryOutcome,
+) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE webhook_deliveries SET status = ?1, attempts = ?2, response_status = ?3, \
+         error = ?4, completed_at = ?5 WHERE id = ?6",
+        params![
+            outcome.status.to_string(),
+            outcome.attempts,
+            outcome.response_status,
+            outcome.error,
+            chrono::Utc::now().to_rfc3339(),
+            delivery_id
+        ],
+    )?;
+    Ok(())
+}
+
+/// Hex HMAC-SHA256 of "{timestamp}.{body}". Signing the timestamp lets
+/// receivers reject replayed deliveries.
+pub fn sign(secret: &str, timestamp: &str, body: &str) -> Result<String, AppError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| AppError::Validation(format!("Invalid webhook secret: {}", e)))?;
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, header_exists, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::db::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn add_endpoint(conn: &rusqlite::Connection, id: &str, enabled: bool) -> (String, String, String) {
+        conn.execute(
+            "INSERT INTO webhook_endpoints (id, workspace_id, url, secret, events_json, enabled, created_at) \
+             VALUES (?1, 'default', 'https://example.com/hook', 'whsec_test', '[\"test\"]', ?2, ?3)",
+            params![id, enabled, chrono::Utc::now().to_rfc3339()],
+        )
+        .unwrap();
+        (id.to_string(), "https://example.com/hook".to_string(), "whsec_test".to_string())
+    }
+
+    fn delivery_to(url: String) -> PendingDelivery {
+        PendingDelivery {
+            id: "delivery-1".to_string(),
+            event: WebhookEvent::Test,
+            url,
+            secret: "whsec_test".to_string(),
+            body: "{\"id\":\"evt_1\"}".to_string(),
+        }
+    }
+
+    #[test]
+    fn signs_timestamp_and_body() {
+        // Python: hmac.new(b"whsec_test", b'1700000000.{"id":"evt_1"}', hashlib.sha256).hexdigest()
+        assert_eq!(
+            sign("whsec_test", "1700000000", "{\"id\":\"evt_1\"}").unwrap(),
+            "c89214b5b5da833daed6f0b8c5bb6bd58cea9022bd80ccc78230f3942d632925"
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_delivered() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(header("X-ContentEngine-Event", "test"))
+            .and(header_exists("X-ContentEngine-Signature"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let outcome = deliver(&http_client(), &delivery_to(server.uri()), 5, |_| Duration::ZERO).await;
+
+        assert_eq!(outcome.status, DeliveryStatus::Delivered);
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(outcome.response_status, Some(204));
+        assert_eq!(outcome.error, None);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_final() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(410))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let outcome = deliver(&http_client(), &delivery_to(server.uri()), 5, |_| Duration::ZERO).await;
+
+        assert_eq!(outcome.status, DeliveryStatus::Failed);
+        assert_eq!(outcome.attempts, 1);
+        assert_eq!(outcome.response_status, Some(410));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let outcome = deliver(&http_client(), &delivery_to(server.uri()), 3, |_| Duration::ZERO).await;
+
+        assert_eq!(outcome.status, DeliveryStatus::Failed);
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(outcome.response_status, Some(500));
+    }
+
+    #[test]
+    fn logs_the_outcome_and_attempt_count() {
+        let conn = test_db();
+        let endpoint = add_endpoint(&conn, "endpoint-1", true);
+        let queued = queue(&conn, "default", &[endpoint], WebhookEvent::Test, serde_json::json!({})).unwrap();
+
+        record_outcome(
+            &conn,
+            &queued[0].id,
+            &DeliveryOutcome {
+                status: DeliveryStatus::Failed,
+                attempts: 4,
+                response_status: Some(502),
+                error: Some("Endpoint returned status 502 Bad Gateway".to_string()),
+            },
+        )
+        .unwrap();
+
+        let (status, attempts, response_status): (String, u32, Option<u16>) = conn
+            .query_row(
+                "SELECT status, attempts, response_status FROM webhook_deliveries WHERE id = ?1",
+                params![queued[0].id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(status, "failed");
+        assert_eq!(attempts, 4);
+        assert_eq!(response_status, Some(502));
+    }
+
+    #[test]
+    fn resumes_recent_deliveries_and_fails_the_rest() {
+        let conn = test_db();
+        let enabled = add_endpoint(&conn, "endpoint-1", true);
+        let disabled = add_endpoint(&conn, "endpoint-2", false);
+        let recent = queue(&conn, "default", std::slice::from_ref(&enabled), WebhookEvent::Test, serde_json::json!({})).unwrap();
+        let stale = queue(&conn, "default", &[enabled], WebhookEvent::Test, serde_json::json!({})).unwrap();
+        let orphaned = queue(&conn, "default", &[disabled], WebhookEvent::Test, serde_json::json!({})).unwrap();
+        conn.execute(
+            "UPDATE webhook_deliveries SET created_at = ?1 WHERE id = ?2",
+            params![
+                (chrono::Utc::now() - chrono::Duration::days(2)).to_rfc3339(),
+                stale[0].id
+            ],
+        )
+        .unwrap();
+
+        let resumed = interrupted_deliveries(&conn).unwrap();
+
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].id, recent[0].id);
+        assert_eq!(resumed[0].body, recent[0].body);
+        let status_of = |id: &str| -> String {
+            conn.query_row(
+                "SELECT status FROM webhook_deliveries WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(status_of(&recent[0].id), "pending");
+        assert_eq!(status_of(&stale[0].id), "failed");
+        assert_eq!(status_of(&orphaned[0].id), "failed");
+    }
+}
@@ -0,0 +1,135 @@
+use rusqlite::params;
+
+use crate::errors::AppError;
+use crate::models::platform::{LengthPreset, TonePreset};
+use crate::models::workspace::Workspace;
+
+pub const DEFAULT_WORKSPACE_ID: &str = "default";
+
+/// Settings each workspace can override. A workspace without its own value
+/// falls back to the app-wide value in `app_settings`.
+pub const WORKSPACE_SETTING_KEYS: [&str; 6] = [
+    "claude_api_key",
+    "monthly_usage_limit",
+    "default_tone",
+    "default_length",
+    "pdf_template",
+    "email_template",
+];
+
+const DEFAULT_MONTHLY_LIMIT: u32 = 50;
+
+/// The workspace new content, voices and usage belong to. Falls back to the
+/// default workspace if the stored id no longer exists.
+pub fn active_workspace_id(conn: &rusqlite::Connection) -> String {
+    conn.query_row(
+        "SELECT w.id FROM app_settings s JOIN workspaces w ON w.id = s.value \
+         WHERE s.key = 'active_workspace_id'",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap_or_else(|_| DEFAULT_WORKSPACE_ID.to_string())
+}
+
+pub fn set_active_workspace(conn: &rusqlite::Connection, workspace_id: &str) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('active_workspace_id', ?1)",
+        params![workspace_id],
+    )?;
+    Ok(())
+}
+
+pub fn get_setting(conn: &rusqlite::Connection, workspace_id: &str, key: &str) -> Option<String> {
+    let own: Option<String> = conn
+        .query_row(
+            "SELECT value FROM workspace_settings WHERE workspace_id = ?1 AND key = ?2",
+            params![workspace_id, key],
+            |row| row.get(0),
+        )
+        .ok();
+
+    own.or_else(|| {
+        conn.query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .ok()
+    })
+}
+
+pub fn set_setting(
+    conn: &rusqlite::Connection,
+    workspace_id: &str,
+    key: &str,
+    value: &str,
+) -> Result<(), AppError> {
+    if !WORKSPACE_SETTING_KEYS.contains(&key) {
+        return Err(AppError::Validation(format!(
+            "'{}' is not a workspace setting",
+            key
+        )));
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO workspace_settings (workspace_id, key, value) VALUES (?1, ?2, ?3)",
+        params![workspace_id, key, value],
+    )?;
+    Ok(())
+}
+
+pub fn monthly_limit(conn: &rusqlite::Connection, workspace_id: &str) -> u32 {
+    get_setting(conn, workspace_id, "monthly_usage_limit")
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_MONTHLY_LIMIT)
+}
+
+pub fn get_workspace(conn: &rusqlite::Connection, id: &str) -> Result<Workspace, AppError> {
+    let (name, created_at): (String, String) = conn
+        .query_row(
+            "SELECT name, created_at FROM workspaces WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| AppError::NotFound(format!("Workspace '{}' not found", id)))?;
+
+    let default_voice_id: Option<String> = conn
+        .query_row(
+            "SELECT id FROM brand_voice_profiles WHERE workspace_id = ?1 AND is_default = 1",
+            params![id],
+            |row| row.get(0),
+        )
+        .ok();
+
+    let has_api_key = get_setting(conn, id, "claude_api_key").is_some_and(|key| !key.is_empty());
+
+    Ok(Workspace {
+        id: id.to_string(),
+        name,
+        is_active: active_workspace_id(conn) == id,
+        default_voice_id,
+        default_tone: get_setting(conn, id, "default_tone")
+            .and_then(|value| parse_preset(&value))
+            .unwrap_or(TonePreset::Professional),
+        default_length: get_setting(conn, id, "default_length")
+            .and_then(|value| parse_preset(&value))
+            .unwrap_or(LengthPreset::Medium),
+        monthly_usage_limit: monthly_limit(conn, id),
+        has_api_key,
+        created_at,
+    })
+}
+
+pub fn list_workspace_ids(conn: &rusqlite::Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn.prepare("SELECT id FROM workspaces ORDER BY id = 'default' DESC, name ASC")?;
+    let ids = stmt
+        .query_map([], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(ids)
+}
+
+/// Tone and length presets are stored as their snake_case serde names.
+fn parse_preset<T: serde::de::DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).ok()
+}
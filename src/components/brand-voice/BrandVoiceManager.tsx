@@ -36,8 +36,8 @@ export function BrandVoiceManager() {
 
       {showCreate && (
         <BrandVoiceUpload
-          onAnalyze={async (name: string, samples: string[]) => {
-            await analyze(name, samples);
+          onAnalyze={async (name, samples, sources) => {
+            await analyze(name, samples, sources);
             setShowCreate(false);
           }}
           isAnalyzing={isAnalyzing}
@@ -118,7 +118,10 @@ export function BrandVoiceManager() {
 
               {expandedId === voice.id && (
                 <div className="border-t border-border p-4">
-                  <BrandVoicePreview attributes={voice.style_attributes} />
+                  <BrandVoicePreview
+                    attributes={voice.style_attributes}
+                    stylometrics={voice.stylometrics}
+                  />
                 </div>
               )}
             </div>
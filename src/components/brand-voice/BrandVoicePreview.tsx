@@ -1,10 +1,11 @@
-import type { StyleAttributes } from '../../types/brandVoice';
+import type { StyleAttributes, Stylometrics } from '../../types/brandVoice';
 
 interface BrandVoicePreviewProps {
   attributes: StyleAttributes;
+  stylometrics?: Stylometrics | null;
 }
 
-export function BrandVoicePreview({ attributes }: BrandVoicePreviewProps) {
+export function BrandVoicePreview({ attributes, stylometrics }: BrandVoicePreviewProps) {
   return (
     <div className="space-y-4">
       <div className="grid grid-cols-2 gap-4">
@@ -63,6 +64,37 @@ export function BrandVoicePreview({ attributes }: BrandVoicePreviewProps) {
           </ul>
         </div>
       )}
+
+      {stylometrics && (
+        <div>
+          <span className="text-xs font-semibold uppercase text-text-secondary">Measured Style</span>
+          <dl className="mt-2 grid grid-cols-2 gap-x-4 gap-y-1 text-sm">
+            <dt className="text-text-secondary">Sentence length</dt>
+            <dd className="text-text">{stylometrics.avg_sentence_length} words</dd>
+            <dt className="text-text-secondary">Paragraph length</dt>
+            <dd className="text-text">{stylometrics.avg_paragraph_length} words</dd>
+            <dt className="text-text-secondary">Reading grade</dt>
+            <dd className="text-text">{stylometrics.readability_grade}</dd>
+            <dt className="text-text-secondary">Lexical diversity</dt>
+            <dd className="text-text">{stylometrics.lexical_diversity}</dd>
+            <dt className="text-text-secondary">Emoji per 1000 words</dt>
+            <dd className="text-text">
+              {stylometrics.emoji_per_1000_words} {stylometrics.top_emojis.join(' ')}
+            </dd>
+            {stylometrics.first_person_share !== null && (
+              <>
+                <dt className="text-text-secondary">First person</dt>
+                <dd className="text-text">{Math.round(stylometrics.first_person_share * 100)}%</dd>
+              </>
+            )}
+          </dl>
+          {stylometrics.frequent_ngrams.length > 0 && (
+            <p className="mt-2 text-xs text-text-secondary">
+              Recurring: {stylometrics.frequent_ngrams.map((n) => n.ngram).join(', ')}
+            </p>
+          )}
+        </div>
+      )}
     </div>
   );
 }
@@ -1,62 +1,100 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { LoadingSpinner } from '../common/LoadingSpinner';
+import { api } from '../../lib/tauriApi';
+import type { SampleExcerpt, SampleSource } from '../../types/brandVoice';
+import type { RepurposedOutput } from '../../types/content';
 
 interface BrandVoiceUploadProps {
-  onAnalyze: (name: string, samples: string[]) => Promise<void>;
+  onAnalyze: (name: string, samples: string[], sources: SampleSource[]) => Promise<void>;
   isAnalyzing: boolean;
 }
 
-interface SampleEntry {
-  text: string;
-}
+/** Matches the backend's SAMPLE_TOKEN_BUDGET; above it a representative subset is analyzed. */
+const SAMPLE_TOKEN_BUDGET = 8000;
+
+/** Same rough estimate as the backend: about four characters per token. */
+const estimateTokens = (text: string) => Math.ceil([...text].length / 4);
+
+const describeSource = (source: SampleSource, outputs: RepurposedOutput[]) => {
+  switch (source.kind) {
+    case 'url':
+      return source.url;
+    case 'file':
+      return source.path;
+    case 'output': {
+      const output = outputs.find((o) => o.id === source.output_id);
+      return output ? `${output.format}: ${output.output_text.slice(0, 60)}...` : source.output_id;
+    }
+  }
+};
 
-const EMPTY_SAMPLE: SampleEntry = { text: '' };
-const MIN_SAMPLES = 3;
-const MAX_SAMPLES = 5;
+const inputClass =
+  'w-full rounded-lg border border-border bg-surface-alt px-3 py-2 text-sm text-text placeholder:text-text-secondary focus:border-primary focus:outline-none focus:ring-2 focus:ring-primary/20';
 
 export function BrandVoiceUpload({ onAnalyze, isAnalyzing }: BrandVoiceUploadProps) {
   const [name, setName] = useState('');
-  const [samples, setSamples] = useState<SampleEntry[]>([
-    { ...EMPTY_SAMPLE },
-    { ...EMPTY_SAMPLE },
-    { ...EMPTY_SAMPLE },
-  ]);
+  const [samples, setSamples] = useState<string[]>(['']);
+  const [sources, setSources] = useState<SampleSource[]>([]);
+  const [urlDraft, setUrlDraft] = useState('');
+  const [pathDraft, setPathDraft] = useState('');
+  const [approvedOutputs, setApprovedOutputs] = useState<RepurposedOutput[]>([]);
+  const [preview, setPreview] = useState<SampleExcerpt[] | null>(null);
+  const [previewError, setPreviewError] = useState<string | null>(null);
+  const [isPreviewing, setIsPreviewing] = useState(false);
+
+  useEffect(() => {
+    api.listApprovedOutputs().then(setApprovedOutputs).catch(() => setApprovedOutputs([]));
+  }, []);
+
+  // Any edit makes an earlier preview stale
+  useEffect(() => {
+    setPreview(null);
+    setPreviewError(null);
+  }, [samples, sources]);
 
   const updateSample = (index: number, value: string) => {
-    setSamples((prev) => prev.map((s, i) => (i === index ? { text: value } : s)));
+    setSamples((prev) => prev.map((s, i) => (i === index ? value : s)));
   };
 
-  const addSample = () => {
-    if (samples.length < MAX_SAMPLES) {
-      setSamples((prev) => [...prev, { ...EMPTY_SAMPLE }]);
-    }
+  const addSource = (source: SampleSource) => {
+    setSources((prev) => [...prev, source]);
   };
 
-  const removeSample = (index: number) => {
-    if (samples.length > MIN_SAMPLES) {
-      setSamples((prev) => prev.filter((_, i) => i !== index));
+  const filledSamples = samples.map((s) => s.trim()).filter((s) => s.length > 0);
+  const pastedTokens = filledSamples.reduce((sum, s) => sum + estimateTokens(s), 0);
+  const totalTokens = preview
+    ? preview.reduce((sum, excerpt) => sum + estimateTokens(excerpt.text), 0)
+    : pastedTokens;
+  const overBudget = totalTokens > SAMPLE_TOKEN_BUDGET;
+  const canAnalyze =
+    name.trim().length > 0 && (filledSamples.length > 0 || sources.length > 0) && !isAnalyzing;
+
+  const handlePreview = async () => {
+    setIsPreviewing(true);
+    setPreviewError(null);
+    try {
+      setPreview(await api.previewVoiceSamples(filledSamples, sources));
+    } catch (err) {
+      setPreviewError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsPreviewing(false);
     }
   };
 
-  const filledSamples = samples.filter((s) => s.text.trim().length > 0);
-  const canAnalyze = name.trim().length > 0 && filledSamples.length >= MIN_SAMPLES && !isAnalyzing;
-
   const handleSubmit = async () => {
     if (!canAnalyze) return;
-    const validSamples = samples.filter((s) => s.text.trim().length > 0);
-    await onAnalyze(
-      name.trim(),
-      validSamples.map((s) => s.text.trim()),
-    );
+    await onAnalyze(name.trim(), filledSamples, sources);
     setName('');
-    setSamples([{ ...EMPTY_SAMPLE }, { ...EMPTY_SAMPLE }, { ...EMPTY_SAMPLE }]);
+    setSamples(['']);
+    setSources([]);
   };
 
   return (
     <div className="rounded-xl border border-border bg-surface p-6 shadow-sm">
       <h3 className="text-lg font-semibold text-text">Create Brand Voice</h3>
       <p className="mt-1 text-sm text-text-secondary">
-        Provide 3-5 writing samples and we will analyze your style.
+        Add as many writing samples as you like: pasted text, web pages, text files or approved
+        outputs. Long documents are split into excerpts.
       </p>
 
       <div className="mt-4">
@@ -69,7 +107,7 @@ export function BrandVoiceUpload({ onAnalyze, isAnalyzing }: BrandVoiceUploadPro
           value={name}
           onChange={(e) => setName(e.target.value)}
           placeholder="e.g., My Blog Voice"
-          className="mt-1 w-full rounded-lg border border-border bg-surface-alt px-3 py-2.5 text-sm text-text placeholder:text-text-secondary focus:border-primary focus:outline-none focus:ring-2 focus:ring-primary/20"
+          className={`mt-1 ${inputClass} py-2.5`}
         />
       </div>
 
@@ -78,9 +116,9 @@ export function BrandVoiceUpload({ onAnalyze, isAnalyzing }: BrandVoiceUploadPro
           <div key={i} className="rounded-lg border border-border p-4">
             <div className="flex items-center justify-between">
               <span className="text-sm font-medium text-text">Sample {i + 1}</span>
-              {samples.length > MIN_SAMPLES && (
+              {samples.length > 1 && (
                 <button
-                  onClick={() => removeSample(i)}
+                  onClick={() => setSamples((prev) => prev.filter((_, j) => j !== i))}
                   className="text-xs text-text-secondary hover:text-danger transition-colors"
                 >
                   Remove
@@ -88,24 +126,121 @@ export function BrandVoiceUpload({ onAnalyze, isAnalyzing }: BrandVoiceUploadPro
               )}
             </div>
             <textarea
-              value={sample.text}
+              value={sample}
               onChange={(e) => updateSample(i, e.target.value)}
               placeholder="Paste a writing sample here..."
               rows={4}
-              className="mt-2 w-full resize-none rounded-lg border border-border bg-surface-alt px-3 py-2 text-sm text-text placeholder:text-text-secondary focus:border-primary focus:outline-none focus:ring-2 focus:ring-primary/20"
+              className={`mt-2 resize-none ${inputClass}`}
             />
           </div>
         ))}
       </div>
 
-      {samples.length < MAX_SAMPLES && (
+      <button
+        onClick={() => setSamples((prev) => [...prev, ''])}
+        className="mt-3 text-sm font-medium text-primary hover:text-primary-hover transition-colors"
+      >
+        + Add Another Sample
+      </button>
+
+      <div className="mt-6 space-y-3 rounded-lg border border-border p-4">
+        <span className="text-sm font-medium text-text">Other Sources</span>
+
+        <div className="flex gap-2">
+          <input
+            type="url"
+            value={urlDraft}
+            onChange={(e) => setUrlDraft(e.target.value)}
+            placeholder="https://example.com/a-post-you-wrote"
+            className={inputClass}
+          />
+          <button
+            onClick={() => {
+              addSource({ kind: 'url', url: urlDraft.trim() });
+              setUrlDraft('');
+            }}
+            disabled={!urlDraft.trim()}
+            className="shrink-0 rounded-lg border border-border px-3 py-2 text-sm font-medium text-text hover:bg-surface-alt disabled:opacity-50"
+          >
+            Add URL
+          </button>
+        </div>
+
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={pathDraft}
+            onChange={(e) => setPathDraft(e.target.value)}
+            placeholder="/path/to/writing.md (.txt, .md or .html)"
+            className={inputClass}
+          />
+          <button
+            onClick={() => {
+              addSource({ kind: 'file', path: pathDraft.trim() });
+              setPathDraft('');
+            }}
+            disabled={!pathDraft.trim()}
+            className="shrink-0 rounded-lg border border-border px-3 py-2 text-sm font-medium text-text hover:bg-surface-alt disabled:opacity-50"
+          >
+            Add File
+          </button>
+        </div>
+
+        <select
+          value=""
+          onChange={(e) => e.target.value && addSource({ kind: 'output', output_id: e.target.value })}
+          disabled={approvedOutputs.length === 0}
+          className={inputClass}
+        >
+          <option value="">
+            {approvedOutputs.length === 0 ? 'No approved outputs yet' : 'Add an approved output...'}
+          </option>
+          {approvedOutputs.map((output) => (
+            <option key={output.id} value={output.id}>
+              {output.format}: {output.output_text.slice(0, 80)}
+            </option>
+          ))}
+        </select>
+
+        {sources.length > 0 && (
+          <ul className="space-y-1">
+            {sources.map((source, i) => (
+              <li key={i} className="flex items-center justify-between gap-2 text-xs text-text-secondary">
+                <span className="truncate">
+                  <span className="font-medium uppercase">{source.kind}</span>{' '}
+                  {describeSource(source, approvedOutputs)}
+                </span>
+                <button
+                  onClick={() => setSources((prev) => prev.filter((_, j) => j !== i))}
+                  className="hover:text-danger transition-colors"
+                >
+                  Remove
+                </button>
+              </li>
+            ))}
+          </ul>
+        )}
+      </div>
+
+      <div className="mt-4 flex items-center justify-between gap-3 text-xs">
+        <p className={overBudget ? 'text-warning' : 'text-text-secondary'}>
+          {preview
+            ? `${preview.length} excerpt${preview.length !== 1 ? 's' : ''}, `
+            : sources.length > 0
+              ? 'Pasted text: '
+              : ''}
+          ~{totalTokens.toLocaleString()} of {SAMPLE_TOKEN_BUDGET.toLocaleString()} tokens
+          {overBudget && ' — a representative selection will be analyzed'}
+        </p>
         <button
-          onClick={addSample}
-          className="mt-3 text-sm font-medium text-primary hover:text-primary-hover transition-colors"
+          onClick={handlePreview}
+          disabled={isPreviewing || (filledSamples.length === 0 && sources.length === 0)}
+          className="shrink-0 font-medium text-primary hover:text-primary-hover disabled:opacity-50"
         >
-          + Add Another Sample
+          {isPreviewing ? 'Checking...' : 'Preview Excerpts'}
         </button>
-      )}
+      </div>
+      {previewError && <p className="mt-2 text-xs text-danger">{previewError}</p>}
 
       <div className="mt-6">
         <button
@@ -122,10 +257,8 @@ export function BrandVoiceUpload({ onAnalyze, isAnalyzing }: BrandVoiceUploadPro
             'Analyze My Voice'
           )}
         </button>
-        {filledSamples.length < MIN_SAMPLES && (
-          <p className="mt-2 text-xs text-warning">
-            {MIN_SAMPLES - filledSamples.length} more sample{MIN_SAMPLES - filledSamples.length !== 1 ? 's' : ''} needed
-          </p>
+        {filledSamples.length === 0 && sources.length === 0 && (
+          <p className="mt-2 text-xs text-warning">Add at least one sample or source</p>
         )}
       </div>
     </div>
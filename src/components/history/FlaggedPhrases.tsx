@@ -0,0 +1,25 @@
+import type { FlaggedPhrase } from '../../types/content';
+
+interface FlaggedPhrasesProps {
+  flags: FlaggedPhrase[];
+}
+
+export function FlaggedPhrases({ flags }: FlaggedPhrasesProps) {
+  return (
+    <div className="rounded-xl border border-danger/40 bg-surface p-4 shadow-sm">
+      <span className="text-sm font-medium text-danger">
+        {flags.length === 1 ? '1 banned phrase' : `${flags.length} banned phrases`} left in this output
+      </span>
+      <ul className="mt-2 space-y-1">
+        {flags.map((flag) => (
+          <li key={`${flag.start}-${flag.phrase}`} className="text-sm text-text">
+            &ldquo;{flag.matched_text}&rdquo;
+            <span className="ml-2 text-xs text-text-secondary">
+              {flag.source === 'global' ? 'banned everywhere' : `voice avoids "${flag.phrase}"`}
+            </span>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
@@ -6,6 +6,8 @@ import type { OutputFormat } from '../../types/platform';
 import { OutputPanel } from '../output/OutputPanel';
 import { LoadingSpinner } from '../common/LoadingSpinner';
 import { ErrorDisplay } from '../common/ErrorDisplay';
+import { FlaggedPhrases } from './FlaggedPhrases';
+import { VoiceScoreBreakdown } from './VoiceScoreBreakdown';
 
 export function HistoryDetail() {
   const { id } = useParams<{ id: string }>();
@@ -16,6 +18,7 @@ export function HistoryDetail() {
   const [error, setError] = useState<string | null>(null);
   const [showOriginal, setShowOriginal] = useState(false);
   const [exporting, setExporting] = useState(false);
+  const [exportWarnings, setExportWarnings] = useState<string[]>([]);
 
   useEffect(() => {
     if (!id) return;
@@ -36,7 +39,8 @@ export function HistoryDetail() {
     if (!id) return;
     setExporting(true);
     try {
-      await api.exportPdf(id);
+      const result = await api.exportPdf(id);
+      setExportWarnings(result.warnings);
     } catch (err) {
       setError(err instanceof Error ? err.message : String(err));
     } finally {
@@ -48,6 +52,8 @@ export function HistoryDetail() {
   if (error) return <ErrorDisplay message={error} />;
   if (!detail) return null;
 
+  const activeOutput = detail.outputs.find((o) => o.format === activeFormat) ?? detail.outputs[0];
+
   return (
     <div className="space-y-6">
       <div className="flex items-center justify-between">
@@ -71,6 +77,17 @@ export function HistoryDetail() {
         </button>
       </div>
 
+      {exportWarnings.length > 0 && (
+        <div className="rounded-lg border border-warning/30 bg-warning/10 p-3 text-xs text-warning">
+          <p className="font-medium">The PDF was exported with warnings:</p>
+          <ul className="mt-1 list-disc pl-4">
+            {exportWarnings.map((warning) => (
+              <li key={warning}>{warning}</li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       <div className="rounded-xl border border-border bg-surface shadow-sm">
         <button
           onClick={() => setShowOriginal(!showOriginal)}
@@ -98,6 +115,12 @@ export function HistoryDetail() {
         activeFormat={activeFormat}
         onFormatChange={setActiveFormat}
       />
+
+      {activeOutput && activeOutput.phrase_flags.length > 0 && (
+        <FlaggedPhrases flags={activeOutput.phrase_flags} />
+      )}
+
+      {activeOutput?.voice_score && <VoiceScoreBreakdown score={activeOutput.voice_score} />}
     </div>
   );
 }
@@ -0,0 +1,37 @@
+import type { VoiceScore } from '../../types/brandVoice';
+
+const CRITERION_LABELS: Record<string, string> = {
+  avoid_phrases: 'Avoided phrases',
+  signature_phrases: 'Signature phrases',
+  sentence_length: 'Sentence length',
+  readability: 'Readability',
+  llm_judgement: 'Editor judgement',
+};
+
+interface VoiceScoreBreakdownProps {
+  score: VoiceScore;
+}
+
+export function VoiceScoreBreakdown({ score }: VoiceScoreBreakdownProps) {
+  return (
+    <div className="rounded-xl border border-border bg-surface p-4 shadow-sm">
+      <div className="flex items-baseline justify-between">
+        <span className="text-sm font-medium text-text">Voice consistency</span>
+        <span className="text-lg font-bold text-text">{Math.round(score.overall)}/100</span>
+      </div>
+      <ul className="mt-3 space-y-2">
+        {score.criteria.map((criterion) => (
+          <li key={criterion.criterion} className="text-sm">
+            <div className="flex justify-between">
+              <span className="text-text">
+                {CRITERION_LABELS[criterion.criterion] ?? criterion.criterion}
+              </span>
+              <span className="text-text-secondary">{Math.round(criterion.score)}</span>
+            </div>
+            <p className="text-xs text-text-secondary">{criterion.detail}</p>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
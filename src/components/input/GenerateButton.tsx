@@ -13,7 +13,7 @@ export function GenerateButton() {
 
   return (
     <button
-      onClick={generate}
+      onClick={() => generate()}
       disabled={!canGenerate}
       className="w-full rounded-lg bg-primary px-6 py-3 text-sm font-semibold text-white shadow-md transition-all hover:bg-primary-hover hover:shadow-lg disabled:cursor-not-allowed disabled:opacity-50 disabled:shadow-none"
     >
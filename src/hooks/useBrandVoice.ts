@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback } from 'react';
 import { useAppStore } from '../stores/appStore';
 import { api } from '../lib/tauriApi';
+import type { SampleSource } from '../types/brandVoice';
 
 export function useBrandVoice() {
   const { brandVoices, setBrandVoices } = useAppStore();
@@ -25,11 +26,11 @@ export function useBrandVoice() {
   }, [loadVoices]);
 
   const analyze = useCallback(
-    async (name: string, samples: string[]) => {
+    async (name: string, samples: string[], sources: SampleSource[] = []) => {
       setIsAnalyzing(true);
       setError(null);
       try {
-        await api.analyzeBrandVoice({ name, samples });
+        await api.analyzeBrandVoice({ name, samples, sources });
         await loadVoices();
       } catch (err) {
         setError(err instanceof Error ? err.message : String(err));
@@ -4,6 +4,13 @@ import { api } from '../lib/tauriApi';
 import type { RepurposeRequest } from '../types/content';
 import type { OutputFormat } from '../types/platform';
 
+export interface GenerateOptions {
+  /** Adds the outputs to this earlier input. */
+  existingInputId?: string;
+  /** Saves the input even though it duplicates an earlier one. */
+  saveAnyway?: boolean;
+}
+
 export function useRepurpose() {
   const {
     rawContent,
@@ -21,12 +28,14 @@ export function useRepurpose() {
     setGenerationError,
     setOutputs,
     setActiveOutputFormat,
+    setDuplicateOf,
   } = useAppStore();
 
-  const generate = useCallback(async () => {
+  const generate = useCallback(async (options: GenerateOptions = {}) => {
     setIsGenerating(true);
     setGenerationError(null);
     setOutputs([]);
+    setDuplicateOf(null);
 
     try {
       let content: string;
@@ -40,19 +49,33 @@ export function useRepurpose() {
         content = rawContent;
       }
 
+      // Stop at a duplicate so the user can attach to it or save anyway
+      const sourceUrlValue = useUrl ? sourceUrl : undefined;
+      if (!options.existingInputId && !options.saveAnyway) {
+        const check = await api.checkDuplicateContent(content, sourceUrlValue);
+        const match = check.exact ?? check.near[0] ?? null;
+        if (match) {
+          setDuplicateOf(match);
+          return;
+        }
+      }
+
       const request: RepurposeRequest = {
         content,
-        source_url: useUrl ? sourceUrl : undefined,
+        source_url: sourceUrlValue,
         title: title || fetchedTitle,
         formats: selectedFormats,
-        tone,
-        length,
+        tone: tone ?? undefined,
+        length: length ?? undefined,
         voice_id: selectedBrandVoiceId ?? undefined,
         config: Object.keys(platformConfig).length > 0 ? platformConfig : undefined,
+        existing_input_id: options.existingInputId,
+        save_anyway: options.saveAnyway,
       };
 
       const response = await api.repurposeContent(request);
       setOutputs(response.outputs);
+      setDuplicateOf(response.duplicate_of);
 
       if (response.outputs.length > 0) {
         setActiveOutputFormat(response.outputs[0].format as OutputFormat);
@@ -77,6 +100,7 @@ export function useRepurpose() {
     setGenerationError,
     setOutputs,
     setActiveOutputFormat,
+    setDuplicateOf,
   ]);
 
   return { generate, isGenerating, error: generationError };
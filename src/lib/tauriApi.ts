@@ -1,20 +1,67 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { ContentInput, FetchedContent, RepurposeRequest, RepurposeResponse, HistoryPage, HistoryDetail } from '../types/content';
-import type { BrandVoiceProfile } from '../types/brandVoice';
-import type { UsageInfo } from '../types/usage';
+import type { ContentInput, FetchedContent, RepurposedOutput, RepurposeRequest, RepurposeResponse, HistoryPage, HistoryDetail, HistoryFilter, CrawlRequest, CrawlReport, DuplicateCheck } from '../types/content';
+import type {
+  BlendVoicesRequest,
+  BrandVoiceProfile,
+  BrandVoiceSample,
+  BrandVoiceVersion,
+  FormatVoiceOverride,
+  SampleExcerpt,
+  SampleSource,
+  StyleFieldDiff,
+  Stylometrics,
+  UpdateVoiceRequest,
+  VoiceComparison,
+  VoiceScore,
+} from '../types/brandVoice';
+import type { UsageInfo, WorkspaceUsage } from '../types/usage';
+import type { UpdateWorkspaceRequest, Workspace } from '../types/workspace';
+import type { OutputFormat } from '../types/platform';
+import type {
+  EmailTemplate,
+  ExportManifest,
+  ExportRequest,
+  IcsExportRequest,
+  PdfExport,
+  PdfTemplate,
+  RenderedEmail,
+} from '../types/export';
+import type {
+  CalendarView,
+  CalendarViewKind,
+  ScheduleEntry,
+  ScheduleRequest,
+  SpreadScheduleRequest,
+} from '../types/schedule';
+import type {
+  PublishedPost,
+  PublishingAccount,
+  SavePublishingAccountRequest,
+} from '../types/publishing';
+import type { SaveWebhookRequest, WebhookDelivery, WebhookEndpoint } from '../types/webhook';
 
 interface AnalyzeVoiceRequest {
   name: string;
   description?: string;
   samples: string[];
+  sources?: SampleSource[];
 }
 
 export const api = {
-  saveContent: (params: { text: string; source_url?: string; title?: string }) =>
+  saveContent: (params: { text: string; source_url?: string; title?: string; reuse_existing?: boolean }) =>
     invoke<ContentInput>('save_content', params),
 
-  fetchUrl: (url: string) =>
-    invoke<FetchedContent>('fetch_url', { url }),
+  checkDuplicateContent: (text: string, sourceUrl?: string) =>
+    invoke<DuplicateCheck>('check_duplicate_content', { text, source_url: sourceUrl }),
+
+  fetchUrl: (url: string, forceRefresh?: boolean) =>
+    invoke<FetchedContent>('fetch_url', { url, force_refresh: forceRefresh }),
+
+  reextractCachedUrl: (url: string) =>
+    invoke<FetchedContent>('reextract_cached_url', { url }),
+
+  crawlSite: (request: CrawlRequest) =>
+    invoke<CrawlReport>('crawl_site', { request }),
 
   repurposeContent: (request: RepurposeRequest) =>
     invoke<RepurposeResponse>('repurpose_content', { request }),
@@ -28,21 +75,238 @@ export const api = {
 
   setDefaultVoice: (id: string) => invoke<void>('set_default_voice', { id }),
 
-  getHistory: (page?: number, pageSize?: number) =>
-    invoke<HistoryPage>('get_history', { page, page_size: pageSize }),
+  updateBrandVoice: (request: UpdateVoiceRequest) =>
+    invoke<BrandVoiceProfile>('update_brand_voice', { request }),
+
+  getVoiceSamples: (profileId: string) =>
+    invoke<BrandVoiceSample[]>('get_voice_samples', { profile_id: profileId }),
+
+  addVoiceSamples: (profileId: string, samples: string[], sources?: SampleSource[]) =>
+    invoke<BrandVoiceSample[]>('add_voice_samples', { profile_id: profileId, samples, sources }),
+
+  previewVoiceSamples: (samples: string[], sources: SampleSource[]) =>
+    invoke<SampleExcerpt[]>('preview_voice_samples', { samples, sources }),
+
+  removeVoiceSample: (sampleId: string) =>
+    invoke<void>('remove_voice_sample', { sample_id: sampleId }),
+
+  reanalyzeBrandVoice: (id: string) =>
+    invoke<BrandVoiceProfile>('reanalyze_brand_voice', { id }),
+
+  listVoiceVersions: (profileId: string) =>
+    invoke<BrandVoiceVersion[]>('list_voice_versions', { profile_id: profileId }),
+
+  diffVoiceVersions: (fromVersionId: string, toVersionId: string) =>
+    invoke<StyleFieldDiff[]>('diff_voice_versions', {
+      from_version_id: fromVersionId,
+      to_version_id: toVersionId,
+    }),
+
+  restoreVoiceVersion: (versionId: string) =>
+    invoke<BrandVoiceProfile>('restore_voice_version', { version_id: versionId }),
+
+  setVoiceFormatOverride: (
+    profileId: string,
+    format: OutputFormat,
+    voiceOverride: FormatVoiceOverride | null,
+  ) =>
+    invoke<BrandVoiceProfile>('set_voice_format_override', {
+      profile_id: profileId,
+      format,
+      voice_override: voiceOverride,
+    }),
+
+  compareBrandVoices: (leftId: string, rightId: string) =>
+    invoke<VoiceComparison>('compare_brand_voices', { left_id: leftId, right_id: rightId }),
+
+  blendBrandVoices: (request: BlendVoicesRequest) =>
+    invoke<BrandVoiceProfile>('blend_brand_voices', { request }),
+
+  exportBrandVoice: (id: string, includeSamples?: boolean, targetPath?: string) =>
+    invoke<string>('export_brand_voice', {
+      id,
+      include_samples: includeSamples,
+      target_path: targetPath,
+    }),
+
+  exportAllBrandVoices: (includeSamples?: boolean, targetPath?: string) =>
+    invoke<string>('export_all_brand_voices', {
+      include_samples: includeSamples,
+      target_path: targetPath,
+    }),
+
+  importBrandVoice: (path: string) =>
+    invoke<BrandVoiceProfile[]>('import_brand_voice', { path }),
+
+  analyzeVoiceStylometrics: (samples: string[]) =>
+    invoke<Stylometrics>('analyze_voice_stylometrics', { samples }),
+
+  scoreOutputVoice: (outputId: string, voiceId?: string, useLlm?: boolean) =>
+    invoke<VoiceScore>('score_output_voice', {
+      output_id: outputId,
+      voice_id: voiceId,
+      use_llm: useLlm,
+    }),
+
+  getHistory: (page?: number, pageSize?: number, filter?: HistoryFilter) =>
+    invoke<HistoryPage>('get_history', { page, page_size: pageSize, filter }),
 
   getHistoryDetail: (id: string) =>
     invoke<HistoryDetail>('get_history_detail', { id }),
 
+  setOutputApproved: (outputId: string, approved: boolean) =>
+    invoke<string | null>('set_output_approved', { output_id: outputId, approved }),
+
+  listApprovedOutputs: (limit?: number) =>
+    invoke<RepurposedOutput[]>('list_approved_outputs', { limit }),
+
   deleteHistoryItem: (id: string) =>
     invoke<void>('delete_history_item', { id }),
 
-  exportPdf: (contentInputId: string) =>
-    invoke<string>('export_pdf', { content_input_id: contentInputId }),
+  exportPdf: (contentInputId: string, targetPath?: string) =>
+    invoke<PdfExport>('export_pdf', { content_input_id: contentInputId, target_path: targetPath }),
+
+  exportSelection: (request: ExportRequest) =>
+    invoke<ExportManifest>('export_selection', { request }),
+
+  renderEmailHtml: (outputId: string) =>
+    invoke<RenderedEmail[]>('render_email_html', { output_id: outputId }),
+
+  exportEmail: (outputId: string, eml?: boolean, targetDir?: string) =>
+    invoke<string[]>('export_email', { output_id: outputId, eml, target_dir: targetDir }),
+
+  exportIcs: (request: IcsExportRequest) => invoke<string>('export_ics', { request }),
+
+  getEmailTemplate: () => invoke<EmailTemplate>('get_email_template'),
+
+  setEmailTemplate: (template: EmailTemplate) =>
+    invoke<EmailTemplate>('set_email_template', { template }),
+
+  getPdfTemplate: () => invoke<PdfTemplate>('get_pdf_template'),
+
+  setPdfTemplate: (template: PdfTemplate) =>
+    invoke<PdfTemplate>('set_pdf_template', { template }),
+
+  exportDocx: (contentInputId: string, targetPath?: string) =>
+    invoke<string>('export_docx', { content_input_id: contentInputId, target_path: targetPath }),
+
+  exportMarkdown: (contentInputId: string, perOutput?: boolean, targetDir?: string) =>
+    invoke<string[]>('export_markdown', {
+      content_input_id: contentInputId,
+      per_output: perOutput,
+      target_dir: targetDir,
+    }),
+
+  exportHistoryMarkdown: (filter?: HistoryFilter, perOutput?: boolean, targetDir?: string) =>
+    invoke<string>('export_history_markdown', { filter, per_output: perOutput, target_dir: targetDir }),
+
+  exportJson: (contentInputId?: string, filter?: HistoryFilter, targetPath?: string) =>
+    invoke<string>('export_json', {
+      content_input_id: contentInputId,
+      filter,
+      target_path: targetPath,
+    }),
+
+  exportCsv: (
+    contentInputId?: string,
+    filter?: HistoryFilter,
+    splitParts?: boolean,
+    targetPath?: string,
+  ) =>
+    invoke<string>('export_csv', {
+      content_input_id: contentInputId,
+      filter,
+      split_parts: splitParts,
+      target_path: targetPath,
+    }),
+
+  scheduleOutput: (request: ScheduleRequest) =>
+    invoke<ScheduleEntry>('schedule_output', { request }),
+
+  spreadSchedule: (request: SpreadScheduleRequest) =>
+    invoke<ScheduleEntry[]>('spread_schedule', { request }),
+
+  moveScheduleEntry: (id: string, scheduledAt: string, channel?: string) =>
+    invoke<ScheduleEntry>('move_schedule_entry', { id, scheduled_at: scheduledAt, channel }),
+
+  unscheduleEntry: (id: string) => invoke<void>('unschedule_entry', { id }),
+
+  listSchedule: (from: string, to: string) =>
+    invoke<ScheduleEntry[]>('list_schedule', { from, to }),
+
+  getCalendarView: (kind: CalendarViewKind, date?: string) =>
+    invoke<CalendarView>('get_calendar_view', {
+      kind,
+      date,
+      utc_offset_minutes: -new Date().getTimezoneOffset(),
+    }),
+
+  listPublishingAccounts: () => invoke<PublishingAccount[]>('list_publishing_accounts'),
+
+  savePublishingAccount: (request: SavePublishingAccountRequest) =>
+    invoke<PublishingAccount>('save_publishing_account', { request }),
+
+  deletePublishingAccount: (id: string) => invoke<void>('delete_publishing_account', { id }),
+
+  publishOutput: (outputId: string, accountId: string) =>
+    invoke<PublishedPost[]>('publish_output', { output_id: outputId, account_id: accountId }),
+
+  getPublishedPosts: (outputId: string) =>
+    invoke<PublishedPost[]>('get_published_posts', { output_id: outputId }),
+
+  listWebhooks: () => invoke<WebhookEndpoint[]>('list_webhooks'),
+
+  saveWebhook: (request: SaveWebhookRequest) =>
+    invoke<WebhookEndpoint>('save_webhook', { request }),
+
+  deleteWebhook: (id: string) => invoke<void>('delete_webhook', { id }),
+
+  listWebhookDeliveries: (endpointId?: string, limit?: number) =>
+    invoke<WebhookDelivery[]>('list_webhook_deliveries', { endpoint_id: endpointId, limit }),
+
+  sendTestWebhook: (id: string) => invoke<WebhookDelivery>('send_test_webhook', { id }),
 
   getUsageInfo: () => invoke<UsageInfo>('get_usage_info'),
 
+  getUsageReport: (month?: string) => invoke<WorkspaceUsage[]>('get_usage_report', { month }),
+
+  listWorkspaces: () => invoke<Workspace[]>('list_workspaces'),
+
+  getActiveWorkspace: () => invoke<Workspace>('get_active_workspace'),
+
+  createWorkspace: (name: string) => invoke<Workspace>('create_workspace', { name }),
+
+  switchWorkspace: (id: string) => invoke<Workspace>('switch_workspace', { id }),
+
+  updateWorkspace: (request: UpdateWorkspaceRequest) =>
+    invoke<Workspace>('update_workspace', { request }),
+
+  deleteWorkspace: (id: string) => invoke<void>('delete_workspace', { id }),
+
   getApiKey: () => invoke<string>('get_api_key'),
 
   setApiKey: (apiKey: string) => invoke<void>('set_api_key', { api_key: apiKey }),
+
+  getUrlFetchAllowlist: () => invoke<string[]>('get_url_fetch_allowlist'),
+
+  setUrlFetchAllowlist: (entries: string[]) =>
+    invoke<void>('set_url_fetch_allowlist', { entries }),
+
+  getUrlCacheTtl: () => invoke<number>('get_url_cache_ttl'),
+
+  setUrlCacheTtl: (seconds: number) => invoke<void>('set_url_cache_ttl', { seconds }),
+
+  getVoiceScoringUseLlm: () => invoke<boolean>('get_voice_scoring_use_llm'),
+
+  setVoiceScoringUseLlm: (enabled: boolean) =>
+    invoke<void>('set_voice_scoring_use_llm', { enabled }),
+
+  getBannedPhrases: () => invoke<string[]>('get_banned_phrases'),
+
+  setBannedPhrases: (phrases: string[]) => invoke<void>('set_banned_phrases', { phrases }),
+
+  getPhraseEnforcementMode: () => invoke<'reask' | 'flag'>('get_phrase_enforcement_mode'),
+
+  setPhraseEnforcementMode: (mode: 'reask' | 'flag') =>
+    invoke<void>('set_phrase_enforcement_mode', { mode }),
 };
@@ -1,3 +1,4 @@
+import { useEffect } from 'react';
 import { useAppStore } from '../stores/appStore';
 import { useRepurpose } from '../hooks/useRepurpose';
 import { ContentInput } from '../components/input/ContentInput';
@@ -8,11 +9,33 @@ import { LengthControl } from '../components/controls/LengthControl';
 import { BrandVoiceSelector } from '../components/controls/BrandVoiceSelector';
 import { OutputPanel } from '../components/output/OutputPanel';
 import { ErrorDisplay } from '../components/common/ErrorDisplay';
+import { api } from '../lib/tauriApi';
 
 export function NewContentPage() {
-  const { outputs, activeOutputFormat, setActiveOutputFormat, generationError, setGenerationError } =
-    useAppStore();
-  const { error } = useRepurpose();
+  const {
+    outputs,
+    activeOutputFormat,
+    setActiveOutputFormat,
+    generationError,
+    setGenerationError,
+    duplicateOf,
+    setDuplicateOf,
+  } = useAppStore();
+  const { generate, isGenerating, error } = useRepurpose();
+
+  // Start from the workspace's default tone and length unless already picked
+  useEffect(() => {
+    api
+      .getActiveWorkspace()
+      .then((workspace) => {
+        const { tone, length, setTone, setLength } = useAppStore.getState();
+        if (tone === null) setTone(workspace.default_tone);
+        if (length === null) setLength(workspace.default_length);
+      })
+      .catch(() => {
+        // Without the workspace the backend still falls back to its defaults
+      });
+  }, []);
 
   const displayError = generationError ?? error;
 
@@ -47,6 +70,46 @@ export function NewContentPage() {
             />
           )}
 
+          {duplicateOf && (
+            <div className="rounded-lg border border-warning/30 bg-warning/5 p-4">
+              <div className="flex items-start gap-3">
+                <p className="flex-1 text-sm text-text-secondary">
+                  {duplicateOf.reason === 'similar_text'
+                    ? `This content is ${Math.round(duplicateOf.similarity * 100)}% similar to`
+                    : 'This content was already saved as'}{' '}
+                  <span className="font-medium text-text">
+                    {duplicateOf.title ?? 'an earlier input'}
+                  </span>{' '}
+                  from {new Date(duplicateOf.created_at).toLocaleDateString()}. Nothing has
+                  been generated yet.
+                </p>
+                <button
+                  onClick={() => setDuplicateOf(null)}
+                  className="text-text-secondary hover:text-text transition-colors"
+                  aria-label="Dismiss warning"
+                >
+                  x
+                </button>
+              </div>
+              <div className="mt-3 flex gap-2">
+                <button
+                  onClick={() => generate({ existingInputId: duplicateOf.content_input_id })}
+                  disabled={isGenerating}
+                  className="rounded-lg bg-primary px-3 py-1.5 text-xs font-semibold text-white hover:bg-primary-hover disabled:opacity-50"
+                >
+                  Add to Existing Input
+                </button>
+                <button
+                  onClick={() => generate({ saveAnyway: true })}
+                  disabled={isGenerating}
+                  className="rounded-lg border border-border px-3 py-1.5 text-xs font-medium text-text hover:bg-surface-alt disabled:opacity-50"
+                >
+                  Save as New
+                </button>
+              </div>
+            </div>
+          )}
+
           <GenerateButton />
         </div>
       </div>
@@ -1,6 +1,6 @@
 import { create } from 'zustand';
 import type { OutputFormat, TonePreset, LengthPreset, PlatformConfig } from '../types/platform';
-import type { RepurposedOutput } from '../types/content';
+import type { DuplicateMatch, RepurposedOutput } from '../types/content';
 import type { BrandVoiceProfile } from '../types/brandVoice';
 import type { UsageInfo } from '../types/usage';
 
@@ -13,8 +13,9 @@ interface AppState {
 
   // Generation settings
   selectedFormats: OutputFormat[];
-  tone: TonePreset;
-  length: LengthPreset;
+  /** Null until picked or pre-filled; the backend then uses the workspace default. */
+  tone: TonePreset | null;
+  length: LengthPreset | null;
   selectedBrandVoiceId: string | null;
   platformConfig: PlatformConfig;
 
@@ -23,6 +24,7 @@ interface AppState {
   generationError: string | null;
   outputs: RepurposedOutput[];
   activeOutputFormat: OutputFormat | null;
+  duplicateOf: DuplicateMatch | null;
 
   // Brand voices
   brandVoices: BrandVoiceProfile[];
@@ -47,6 +49,7 @@ interface AppState {
   setGenerationError: (error: string | null) => void;
   setOutputs: (outputs: RepurposedOutput[]) => void;
   setActiveOutputFormat: (format: OutputFormat | null) => void;
+  setDuplicateOf: (match: DuplicateMatch | null) => void;
   setBrandVoices: (voices: BrandVoiceProfile[]) => void;
   setUsage: (usage: UsageInfo | null) => void;
   setSettingsOpen: (open: boolean) => void;
@@ -59,14 +62,15 @@ const initialFormState = {
   sourceUrl: '',
   useUrl: false,
   selectedFormats: [] as OutputFormat[],
-  tone: 'professional' as TonePreset,
-  length: 'medium' as LengthPreset,
+  tone: null as TonePreset | null,
+  length: null as LengthPreset | null,
   selectedBrandVoiceId: null as string | null,
   platformConfig: {} as PlatformConfig,
   isGenerating: false,
   generationError: null as string | null,
   outputs: [] as RepurposedOutput[],
   activeOutputFormat: null as OutputFormat | null,
+  duplicateOf: null as DuplicateMatch | null,
 };
 
 export const useAppStore = create<AppState>((set) => ({
@@ -93,6 +97,7 @@ export const useAppStore = create<AppState>((set) => ({
   setGenerationError: (error) => set({ generationError: error }),
   setOutputs: (outputs) => set({ outputs }),
   setActiveOutputFormat: (format) => set({ activeOutputFormat: format }),
+  setDuplicateOf: (match) => set({ duplicateOf: match }),
   setBrandVoices: (voices) => set({ brandVoices: voices }),
   setUsage: (usage) => set({ usage }),
   setSettingsOpen: (open) => set({ settingsOpen: open }),
@@ -1,3 +1,5 @@
+import type { OutputFormat } from './platform';
+
 export interface StyleAttributes {
   tone: string;
   vocabulary_level: string;
@@ -5,6 +7,17 @@ export interface StyleAttributes {
   personality_traits: string[];
   signature_phrases: string[];
   avoid_phrases: string[];
+  format_overrides: Partial<Record<OutputFormat, FormatVoiceOverride>>;
+}
+
+export type EmojiPolicy = 'none' | 'sparing' | 'liberal';
+
+/** Per-format adjustments; unset fields use the base attributes. */
+export interface FormatVoiceOverride {
+  tone: string | null;
+  vocabulary_level: string | null;
+  emoji_policy: EmojiPolicy | null;
+  extra_signature_phrases: string[];
 }
 
 export interface BrandVoiceProfile {
@@ -15,4 +28,124 @@ export interface BrandVoiceProfile {
   is_default: boolean;
   created_at: string;
   updated_at: string;
+  stylometrics: Stylometrics | null;
+}
+
+/** Locally measured features of a voice's samples; rates are per 1000 words. */
+export interface Stylometrics {
+  sample_count: number;
+  word_count: number;
+  avg_sentence_length: number;
+  sentence_length_sd: number;
+  avg_paragraph_length: number;
+  readability_grade: number;
+  lexical_diversity: number;
+  punctuation: PunctuationHabits;
+  emoji_per_1000_words: number;
+  top_emojis: string[];
+  first_person_per_1000_words: number;
+  second_person_per_1000_words: number;
+  first_person_share: number | null;
+  frequent_ngrams: NgramCount[];
+}
+
+export interface PunctuationHabits {
+  exclamations: number;
+  questions: number;
+  semicolons: number;
+  colons: number;
+  dashes: number;
+  ellipses: number;
+  parentheses: number;
+}
+
+export interface NgramCount {
+  ngram: string;
+  count: number;
+}
+
+export interface BrandVoiceSample {
+  id: string;
+  profile_id: string;
+  sample_text: string;
+  created_at: string;
+  source_kind: SampleSourceKind;
+  source_ref: string | null;
+}
+
+export type SampleSourceKind = 'pasted' | 'url' | 'file' | 'output' | 'import' | 'blend';
+
+export type SampleSource =
+  | { kind: 'url'; url: string }
+  | { kind: 'file'; path: string }
+  | { kind: 'output'; output_id: string };
+
+export interface SampleExcerpt {
+  text: string;
+  source_kind: SampleSourceKind;
+  source_ref: string | null;
+}
+
+export interface UpdateVoiceRequest {
+  id: string;
+  name?: string;
+  description?: string;
+  /** Overrides inside style_attributes are ignored; send these to replace them. */
+  style_attributes?: StyleAttributes;
+  format_overrides?: Partial<Record<OutputFormat, FormatVoiceOverride>>;
+}
+
+export interface BrandVoiceVersion {
+  id: string;
+  profile_id: string;
+  version_number: number;
+  style_attributes: StyleAttributes;
+  change_source: string;
+  created_at: string;
+}
+
+export interface StyleFieldDiff {
+  field: keyof StyleAttributes;
+  before: string | string[] | null;
+  after: string | string[] | null;
+  added: string[];
+  removed: string[];
+}
+
+export interface VoiceCriterionScore {
+  criterion: 'avoid_phrases' | 'signature_phrases' | 'sentence_length' | 'readability' | 'llm_judgement';
+  score: number;
+  weight: number;
+  detail: string;
+}
+
+export interface VoiceScore {
+  overall: number;
+  criteria: VoiceCriterionScore[];
+  scored_at: string;
+}
+
+export interface StylometricDiff {
+  metric: string;
+  left: number;
+  right: number;
+  difference: number;
+}
+
+export interface VoiceComparison {
+  left: BrandVoiceProfile;
+  right: BrandVoiceProfile;
+  style_differences: StyleFieldDiff[];
+  stylometric_differences: StylometricDiff[];
+}
+
+export interface BlendSource {
+  profile_id: string;
+  weight: number;
+}
+
+export interface BlendVoicesRequest {
+  name: string;
+  description?: string;
+  sources: BlendSource[];
 }
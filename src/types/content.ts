@@ -1,3 +1,4 @@
+import type { VoiceScore } from './brandVoice';
 import type { OutputFormat, TonePreset, LengthPreset, PlatformConfig } from './platform';
 
 export interface ContentInput {
@@ -13,6 +14,7 @@ export interface FetchedContent {
   title: string | null;
   text: string;
   word_count: number;
+  canonical_url: string | null;
 }
 
 export interface KeyPoints {
@@ -30,6 +32,21 @@ export interface RepurposedOutput {
   format: string;
   output_text: string;
   created_at: string;
+  voice_version_id: string | null;
+  voice_score: VoiceScore | null;
+  phrase_flags: FlaggedPhrase[];
+  approved_at: string | null;
+  tone: string | null;
+  length: string | null;
+}
+
+/** A banned phrase left in an output; offsets index the output text directly. */
+export interface FlaggedPhrase {
+  phrase: string;
+  matched_text: string;
+  start: number;
+  end: number;
+  source: 'voice' | 'global';
 }
 
 export interface RepurposeRequest {
@@ -37,15 +54,21 @@ export interface RepurposeRequest {
   source_url?: string;
   title?: string;
   formats: OutputFormat[];
-  tone: TonePreset;
-  length: LengthPreset;
+  tone?: TonePreset;
+  length?: LengthPreset;
   voice_id?: string;
   config?: PlatformConfig;
+  /** Adds the outputs to this earlier input instead of saving a new one. */
+  existing_input_id?: string;
+  /** Saves the input even when it duplicates an earlier one. */
+  save_anyway?: boolean;
 }
 
 export interface RepurposeResponse {
-  content_input_id: string;
+  /** Null when nothing was saved because the input is a duplicate. */
+  content_input_id: string | null;
   outputs: RepurposedOutput[];
+  duplicate_of: DuplicateMatch | null;
 }
 
 export interface TwitterThreadData {
@@ -88,3 +111,62 @@ export interface HistoryDetail {
   input: ContentInput;
   outputs: RepurposedOutput[];
 }
+
+/** Dates are inclusive YYYY-MM-DD. */
+export interface HistoryFilter {
+  search?: string;
+  format?: OutputFormat;
+  from?: string;
+  to?: string;
+}
+
+export interface JsonExportItem {
+  input: ContentInput;
+  key_points: KeyPoints | null;
+  outputs: RepurposedOutput[];
+}
+
+export interface JsonExport {
+  schema_version: number;
+  exported_at: string;
+  items: JsonExportItem[];
+}
+
+export interface CrawlRequest {
+  sitemap_url?: string;
+  index_url?: string;
+  link_pattern?: string;
+  delay_ms?: number;
+  max_pages?: number;
+}
+
+export interface CrawlProgress {
+  url: string;
+  current: number;
+  total: number;
+  status: 'imported' | 'skipped' | 'failed';
+}
+
+export interface CrawlPageIssue {
+  url: string;
+  reason: string;
+}
+
+export interface CrawlReport {
+  imported: ContentInput[];
+  skipped: CrawlPageIssue[];
+  failed: CrawlPageIssue[];
+}
+
+export interface DuplicateMatch {
+  content_input_id: string;
+  title: string | null;
+  created_at: string;
+  similarity: number;
+  reason: 'content_hash' | 'canonical_url' | 'similar_text';
+}
+
+export interface DuplicateCheck {
+  exact: DuplicateMatch | null;
+  near: DuplicateMatch[];
+}
@@ -0,0 +1,76 @@
+import type { OutputFormat } from './platform';
+
+export interface PdfTemplate {
+  accent_color: string;
+  text_color: string;
+  font_family: string | null;
+  logo_path: string | null;
+  header_text: string | null;
+  footer_text: string | null;
+  show_page_numbers: boolean;
+  cover_page: boolean;
+}
+
+export interface EmailTemplate {
+  brand_name: string | null;
+  /** A hosted image; email clients don't load local files. */
+  logo_url: string | null;
+  accent_color: string;
+  background_color: string;
+  text_color: string;
+  font_family: string;
+  footer_text: string | null;
+  from_address: string | null;
+}
+
+export interface RenderedEmail {
+  number: number | null;
+  subject: string;
+  preview: string | null;
+  send_timing: string | null;
+  html: string;
+  text: string;
+}
+
+export interface PdfExport {
+  path: string;
+  warnings: string[];
+}
+
+export type ExportFileType = 'pdf' | 'docx' | 'markdown' | 'json' | 'csv';
+
+export interface ExportRequest {
+  content_input_ids?: string[];
+  output_ids?: string[];
+  formats?: OutputFormat[];
+  file_type: ExportFileType;
+  /** One file per content input inside a ZIP archive. */
+  zip?: boolean;
+  /** One CSV row per tweet or email. */
+  split_parts?: boolean;
+  target_path?: string;
+}
+
+export interface ExportedFile {
+  path: string;
+  content_input_ids: string[];
+  output_ids: string[];
+  size_bytes: number;
+}
+
+export interface ExportManifest {
+  archive_path: string | null;
+  files: ExportedFile[];
+  warnings: string[];
+}
+
+export interface IcsExportRequest {
+  output_ids: string[];
+  /** RFC 3339, YYYY-MM-DDTHH:MM (UTC) or a bare date. */
+  start_at: string;
+  /** Hours between posts; defaults to 24. */
+  cadence_hours?: number | null;
+  /** Minutes each event lasts; defaults to 30. */
+  duration_minutes?: number | null;
+  target_path?: string | null;
+}
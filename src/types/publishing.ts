@@ -0,0 +1,36 @@
+export type PublishPlatform = 'mastodon' | 'bluesky';
+
+export interface PublishingAccount {
+  id: string;
+  platform: PublishPlatform;
+  label: string;
+  /** The Mastodon instance or Bluesky PDS. */
+  base_url: string;
+  /** The Bluesky handle; unused for Mastodon. */
+  handle: string | null;
+  masked_secret: string;
+  created_at: string;
+}
+
+export interface SavePublishingAccountRequest {
+  /** Updates this account when set, otherwise creates one. */
+  id?: string | null;
+  platform: PublishPlatform;
+  label: string;
+  /** Defaults to https://bsky.social for Bluesky; required for Mastodon. */
+  base_url?: string | null;
+  handle?: string | null;
+  /** Mastodon access token or Bluesky app password; kept when omitted on update. */
+  secret?: string | null;
+}
+
+export interface PublishedPost {
+  id: string;
+  output_id: string;
+  account_id: string | null;
+  platform: PublishPlatform;
+  part_number: number;
+  remote_id: string;
+  url: string;
+  published_at: string;
+}
@@ -0,0 +1,50 @@
+import type { OutputFormat } from './platform';
+
+/** A calendar slot for an output, or for one tweet or email of it. */
+export interface ScheduleEntry {
+  id: string;
+  output_id: string;
+  content_input_id: string;
+  content_title: string | null;
+  format: OutputFormat;
+  part_number: number | null;
+  /** RFC 3339, UTC. */
+  scheduled_at: string;
+  channel: string;
+  preview: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ScheduleRequest {
+  output_id: string;
+  part_number?: number | null;
+  scheduled_at: string;
+  /** Defaults to the channel the output's format is written for. */
+  channel?: string | null;
+}
+
+export interface SpreadScheduleRequest {
+  output_id: string;
+  start_at: string;
+  /** Gap between parts without a send timing; defaults to 24. */
+  interval_hours?: number | null;
+  channel?: string | null;
+}
+
+export type CalendarViewKind = 'week' | 'month';
+
+export interface CalendarDay {
+  /** YYYY-MM-DD in the requested UTC offset. */
+  date: string;
+  /** False for leading and trailing days outside the month. */
+  in_range: boolean;
+  entries: ScheduleEntry[];
+}
+
+export interface CalendarView {
+  kind: CalendarViewKind;
+  start_date: string;
+  end_date: string;
+  days: CalendarDay[];
+}
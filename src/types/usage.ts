@@ -3,3 +3,13 @@ export interface UsageInfo {
   limit: number;
   resets_at: string;
 }
+
+export interface WorkspaceUsage {
+  workspace_id: string;
+  workspace_name: string;
+  month: string;
+  repurpose_runs: number;
+  formats_generated: number;
+  limit: number;
+  outputs_by_format: Record<string, number>;
+}
@@ -0,0 +1,36 @@
+export type WebhookEvent = 'repurpose_completed' | 'export_completed' | 'content_deleted' | 'test';
+
+export interface WebhookEndpoint {
+  id: string;
+  url: string;
+  /** Signs each delivery: X-ContentEngine-Signature is sha256=HMAC(secret, "{timestamp}.{body}"). */
+  secret: string;
+  events: WebhookEvent[];
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface SaveWebhookRequest {
+  /** Updates this endpoint when set, otherwise creates one. */
+  id?: string | null;
+  url: string;
+  /** Defaults to every content event. */
+  events?: WebhookEvent[] | null;
+  enabled?: boolean | null;
+  /** Generated for new endpoints when omitted; kept when updating. */
+  secret?: string | null;
+}
+
+export type DeliveryStatus = 'pending' | 'delivered' | 'failed';
+
+export interface WebhookDelivery {
+  id: string;
+  endpoint_id: string;
+  event: WebhookEvent;
+  status: DeliveryStatus;
+  attempts: number;
+  response_status: number | null;
+  error: string | null;
+  created_at: string;
+  completed_at: string | null;
+}
@@ -0,0 +1,24 @@
+import type { LengthPreset, TonePreset } from './platform';
+
+export interface Workspace {
+  id: string;
+  name: string;
+  is_active: boolean;
+  default_voice_id: string | null;
+  default_tone: TonePreset;
+  default_length: LengthPreset;
+  monthly_usage_limit: number;
+  has_api_key: boolean;
+  created_at: string;
+}
+
+export interface UpdateWorkspaceRequest {
+  id: string;
+  name?: string;
+  default_voice_id?: string;
+  default_tone?: TonePreset;
+  default_length?: LengthPreset;
+  monthly_usage_limit?: number;
+  /** An empty string removes the workspace's key. */
+  api_key?: string;
+}